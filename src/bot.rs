@@ -1,4 +1,4 @@
-use crate::{cache::RegistryCache, commands, handlers};
+use crate::{cache::RegistryCache, commands, handlers, utils::MemoDraft};
 use std::sync::Arc;
 use teloxide::{
     dispatching::{dialogue, dialogue::InMemStorage, UpdateHandler},
@@ -33,6 +33,39 @@ pub enum State {
         chain: String,
         message_id: Option<teloxide::types::MessageId>,
     },
+    AwaitingPortfolioAddress {
+        chain: String,
+        message_id: Option<teloxide::types::MessageId>,
+    },
+    AwaitingAccountAddress {
+        chain: String,
+        message_id: Option<teloxide::types::MessageId>,
+    },
+    AwaitingGrantsAddress {
+        chain: String,
+        message_id: Option<teloxide::types::MessageId>,
+    },
+    AwaitingPacketChannel {
+        chain: String,
+        message_id: Option<teloxide::types::MessageId>,
+    },
+    AwaitingAssetDenom {
+        chain: String,
+        message_id: Option<teloxide::types::MessageId>,
+    },
+    AwaitingEscrowChannel {
+        chain: String,
+        message_id: Option<teloxide::types::MessageId>,
+    },
+    AwaitingRateLimit {
+        chain: String,
+        message_id: Option<teloxide::types::MessageId>,
+    },
+    BuildingMemo {
+        chain: String,
+        message_id: Option<teloxide::types::MessageId>,
+        draft: MemoDraft,
+    },
     AwaitingOsmosisPoolIncentives {
         chain: String,
         message_id: Option<teloxide::types::MessageId>,
@@ -70,7 +103,9 @@ fn schema() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync + 'static>>
         .branch(case![commands::Command::Testnet].endpoint(handlers::show_testnets))
         .branch(case![commands::Command::Testnets].endpoint(handlers::show_testnets))
         .branch(case![commands::Command::Mainnet].endpoint(handlers::show_mainnets))
-        .branch(case![commands::Command::Mainnets].endpoint(handlers::show_mainnets));
+        .branch(case![commands::Command::Mainnets].endpoint(handlers::show_mainnets))
+        .branch(case![commands::Command::Route(args)].endpoint(handlers::find_route))
+        .branch(case![commands::Command::Address(address)].endpoint(handlers::convert_address));
 
     let message_handler = Update::filter_message()
         .branch(command_handler)
@@ -86,6 +121,42 @@ fn schema() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync + 'static>>
             case![State::AwaitingWalletAddress { chain, message_id }]
                 .endpoint(handlers::handle_wallet_address),
         )
+        .branch(
+            case![State::AwaitingPortfolioAddress { chain, message_id }]
+                .endpoint(handlers::handle_portfolio_address),
+        )
+        .branch(
+            case![State::AwaitingAccountAddress { chain, message_id }]
+                .endpoint(handlers::handle_account_address),
+        )
+        .branch(
+            case![State::AwaitingGrantsAddress { chain, message_id }]
+                .endpoint(handlers::handle_grants_address),
+        )
+        .branch(
+            case![State::AwaitingPacketChannel { chain, message_id }]
+                .endpoint(handlers::handle_packet_channel),
+        )
+        .branch(
+            case![State::AwaitingAssetDenom { chain, message_id }]
+                .endpoint(handlers::handle_asset_denom),
+        )
+        .branch(
+            case![State::AwaitingEscrowChannel { chain, message_id }]
+                .endpoint(handlers::handle_escrow_channel),
+        )
+        .branch(
+            case![State::AwaitingRateLimit { chain, message_id }]
+                .endpoint(handlers::handle_rate_limit),
+        )
+        .branch(
+            case![State::BuildingMemo {
+                chain,
+                message_id,
+                draft
+            }]
+            .endpoint(handlers::handle_memo_builder),
+        )
         .branch(
             case![State::AwaitingOsmosisPoolIncentives { chain, message_id }]
                 .endpoint(handlers::handle_osmosis_pool_incentives),
@@ -126,6 +197,49 @@ fn schema() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync + 'static>>
             case![State::AwaitingWalletAddress { chain, message_id }]
                 .endpoint(handlers::handle_chain_action),
         )
+        .branch(
+            case![State::AwaitingPortfolioAddress { chain, message_id }]
+                .endpoint(handlers::handle_chain_action),
+        )
+        .branch(
+            case![State::AwaitingAccountAddress { chain, message_id }]
+                .endpoint(handlers::handle_chain_action),
+        )
+        .branch(
+            case![State::AwaitingGrantsAddress { chain, message_id }]
+                .endpoint(handlers::handle_chain_action),
+        )
+        .branch(
+            case![State::AwaitingPacketChannel { chain, message_id }]
+                .endpoint(handlers::handle_chain_action),
+        )
+        .branch(
+            case![State::AwaitingAssetDenom { chain, message_id }]
+                .endpoint(handlers::handle_chain_action),
+        )
+        .branch(
+            case![State::AwaitingEscrowChannel { chain, message_id }]
+                .endpoint(handlers::handle_chain_action),
+        )
+        .branch(
+            case![State::AwaitingRateLimit { chain, message_id }]
+                .endpoint(handlers::handle_chain_action),
+        )
+        .branch(
+            case![State::BuildingMemo {
+                chain,
+                message_id,
+                draft
+            }]
+            .map(
+                |(chain, message_id, _draft): (
+                    String,
+                    Option<teloxide::types::MessageId>,
+                    MemoDraft,
+                )| (chain, message_id),
+            )
+            .endpoint(handlers::handle_chain_action),
+        )
         .branch(
             case![State::AwaitingOsmosisPoolIncentives { chain, message_id }]
                 .endpoint(handlers::handle_chain_action),
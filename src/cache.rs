@@ -1,7 +1,27 @@
-use cosmos_chain_registry::get::{AssetList, ChainInfo};
+use crate::utils::{
+    fetch_usd_prices, registry_channel_counterparties, registry_ibc_denoms, BalanceCursor,
+    ChainAddressPrefix, ChainSwitch, CoinGeckoPrices, IbcChannelSummary, IbcGraph,
+    OsmosisSqsPrices, PriceQuery, PriceSource, RegistryIbcAsset, UsdPrices,
+};
+use cosmos_chain_registry::get::{AssetList, ChainInfo, IBCPath};
 use dashmap::DashMap;
+use futures::{stream, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// Every `ibc/` denom listed in a mainnet asset list, mapped to the assets that carry it.
+pub type IbcDenomIndex = HashMap<String, Vec<RegistryIbcAsset>>;
+/// Counterparty chain name by one chain's `(port, channel)`, from the registry `_IBC` files.
+pub type ChannelCounterparties = HashMap<(String, String), String>;
+
+const ASSETLIST_FETCH_CONCURRENCY: usize = 16;
+const CHAIN_FETCH_CONCURRENCY: usize = 16;
+/// Prices move much faster than registry data, so they get their own short TTL.
+const PRICE_TTL: Duration = Duration::from_secs(120);
+
 pub struct CachedItem<T> {
     data: T,
     timestamp: Instant,
@@ -10,7 +30,20 @@ pub struct CachedItem<T> {
 pub struct RegistryCache {
     chains: DashMap<String, CachedItem<ChainInfo>>,
     assets: DashMap<String, CachedItem<AssetList>>,
+    registry_json: DashMap<String, CachedItem<Value>>,
+    ibc_paths: DashMap<String, CachedItem<IBCPath>>,
+    chain_id_index: DashMap<String, CachedItem<Arc<HashMap<String, String>>>>,
     chain_list: DashMap<String, CachedItem<Vec<String>>>,
+    ibc_denom_index: DashMap<String, CachedItem<Arc<IbcDenomIndex>>>,
+    ibc_graph: DashMap<String, CachedItem<Arc<IbcGraph>>>,
+    channel_counterparties: DashMap<String, CachedItem<Arc<ChannelCounterparties>>>,
+    chain_prefixes: DashMap<String, CachedItem<Arc<Vec<ChainAddressPrefix>>>>,
+    ibc_channels: DashMap<String, CachedItem<Arc<Vec<IbcChannelSummary>>>>,
+    balance_cursors: DashMap<String, CachedItem<BalanceCursor>>,
+    chain_switches: DashMap<String, CachedItem<ChainSwitch>>,
+    next_cursor_id: AtomicU64,
+    usd_prices: DashMap<String, CachedItem<Option<f64>>>,
+    price_sources: Vec<Arc<dyn PriceSource>>,
     ttl: Duration,
 }
 
@@ -19,11 +52,31 @@ impl RegistryCache {
         Self {
             chains: DashMap::new(),
             assets: DashMap::new(),
+            registry_json: DashMap::new(),
+            ibc_paths: DashMap::new(),
+            chain_id_index: DashMap::new(),
             chain_list: DashMap::new(),
+            ibc_denom_index: DashMap::new(),
+            ibc_graph: DashMap::new(),
+            channel_counterparties: DashMap::new(),
+            chain_prefixes: DashMap::new(),
+            ibc_channels: DashMap::new(),
+            balance_cursors: DashMap::new(),
+            chain_switches: DashMap::new(),
+            next_cursor_id: AtomicU64::new(0),
+            usd_prices: DashMap::new(),
+            price_sources: vec![Arc::new(OsmosisSqsPrices), Arc::new(CoinGeckoPrices)],
             ttl: Duration::from_secs(ttl_minutes * 60),
         }
     }
 
+    /// Replaces the default Osmosis SQS then CoinGecko price sources, tried in order.
+    #[cfg(test)]
+    pub fn with_price_sources(mut self, price_sources: Vec<Arc<dyn PriceSource>>) -> Self {
+        self.price_sources = price_sources;
+        self
+    }
+
     pub async fn get_chain(&self, name: &str) -> anyhow::Result<Option<ChainInfo>> {
         // Check cache first
         if let Some(cached) = self.chains.get(name) {
@@ -99,6 +152,141 @@ impl RegistryCache {
         Ok(assets)
     }
 
+    /// The registry `_IBC` file connecting two chains, in either order.
+    pub async fn get_ibc_path(
+        &self,
+        chain_a: &str,
+        chain_b: &str,
+    ) -> anyhow::Result<Option<IBCPath>> {
+        let key = format!("{}-{}", chain_a.min(chain_b), chain_a.max(chain_b));
+
+        // Check cache first
+        if let Some(cached) = self.ibc_paths.get(&key) {
+            if cached.timestamp.elapsed() < self.ttl {
+                return Ok(Some(cached.data.clone()));
+            } else {
+                drop(cached);
+                self.ibc_paths.remove(&key);
+            }
+        }
+
+        let path = cosmos_chain_registry::get::get_path(chain_a, chain_b)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        if let Some(ref p) = path {
+            self.ibc_paths.insert(
+                key,
+                CachedItem {
+                    data: p.clone(),
+                    timestamp: Instant::now(),
+                },
+            );
+        }
+        Ok(path)
+    }
+
+    /// Raw `chain.json`, for registry fields the typed `ChainInfo` does not model.
+    pub async fn get_chain_json(&self, name: &str) -> anyhow::Result<Option<Value>> {
+        self.get_registry_json(name, "chain.json").await
+    }
+
+    /// Raw `assetlist.json`, for fields such as `traces` the typed `AssetList` does not model.
+    pub async fn get_assetlist_json(&self, name: &str) -> anyhow::Result<Option<Value>> {
+        self.get_registry_json(name, "assetlist.json").await
+    }
+
+    async fn get_registry_json(&self, name: &str, file: &str) -> anyhow::Result<Option<Value>> {
+        let key = format!("{name}/{file}");
+
+        // Check cache first
+        if let Some(cached) = self.registry_json.get(&key) {
+            if cached.timestamp.elapsed() < self.ttl {
+                return Ok(Some(cached.data.clone()));
+            } else {
+                drop(cached);
+                self.registry_json.remove(&key);
+            }
+        }
+
+        // Try mainnet first
+        let json = crate::utils::fetch_registry_json(&key).await?;
+
+        // If not found, try testnets
+        let json = if json.is_none() {
+            crate::utils::fetch_registry_json(&format!("testnets/{key}")).await?
+        } else {
+            json
+        };
+
+        if let Some(ref j) = json {
+            self.registry_json.insert(
+                key,
+                CachedItem {
+                    data: j.clone(),
+                    timestamp: Instant::now(),
+                },
+            );
+        }
+        Ok(json)
+    }
+
+    /// Resolves an on-chain ID such as `osmosis-1` or `phoenix-1` to its registry name, using
+    /// an index of every registry chain's `chain_id`.
+    pub async fn chain_name_for_chain_id(&self, chain_id: &str) -> Option<String> {
+        match self.chain_id_index().await {
+            Ok(index) => index.get(chain_id).cloned(),
+            Err(e) => {
+                log::warn!("Could not build the chain ID index: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Registry name by on-chain `chain_id`, for every mainnet chain.
+    pub async fn chain_id_index(&self) -> anyhow::Result<Arc<HashMap<String, String>>> {
+        // Check cache first
+        if let Some(cached) = self.chain_id_index.get("mainnets") {
+            if cached.timestamp.elapsed() < self.ttl {
+                return Ok(cached.data.clone());
+            } else {
+                drop(cached);
+                self.chain_id_index.remove("mainnets");
+            }
+        }
+
+        let chains = self.list_chains().await?;
+        let index: HashMap<String, String> = stream::iter(
+            chains
+                .into_iter()
+                .filter(|chain| !chain.starts_with('_') && !chain.starts_with('.')),
+        )
+        .map(|chain| async move {
+            match self.get_chain(&chain).await {
+                Ok(Some(info)) if !info.chain_id.is_empty() => Some((info.chain_id, chain)),
+                Ok(_) => None,
+                Err(e) => {
+                    log::warn!("Could not fetch chain info for {}: {}", chain, e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(CHAIN_FETCH_CONCURRENCY)
+        .filter_map(|entry| async move { entry })
+        .collect()
+        .await;
+
+        let index = Arc::new(index);
+        self.chain_id_index.insert(
+            "mainnets".to_string(),
+            CachedItem {
+                data: index.clone(),
+                timestamp: Instant::now(),
+            },
+        );
+        Ok(index)
+    }
+
     pub async fn list_chains(&self) -> anyhow::Result<Vec<String>> {
         // Check cache first
         if let Some(cached) = self.chain_list.get("mainnets") {
@@ -155,4 +343,280 @@ impl RegistryCache {
 
         Ok(testnets)
     }
+
+    /// Builds, or returns the cached, index of IBC denoms across all mainnet asset lists.
+    pub async fn ibc_denom_index(&self) -> anyhow::Result<Arc<IbcDenomIndex>> {
+        // Check cache first
+        if let Some(cached) = self.ibc_denom_index.get("mainnets") {
+            if cached.timestamp.elapsed() < self.ttl {
+                return Ok(cached.data.clone());
+            } else {
+                drop(cached);
+                self.ibc_denom_index.remove("mainnets");
+            }
+        }
+
+        let chains = self.list_chains().await?;
+        let assetlists: Vec<(String, Option<Value>)> = stream::iter(
+            chains
+                .into_iter()
+                .filter(|chain| !chain.starts_with('_') && !chain.starts_with('.')),
+        )
+        .map(|chain| async move {
+            let assetlist = match self.get_assetlist_json(&chain).await {
+                Ok(assetlist) => assetlist,
+                Err(e) => {
+                    log::warn!("Could not fetch asset list for {}: {}", chain, e);
+                    None
+                }
+            };
+            (chain, assetlist)
+        })
+        .buffer_unordered(ASSETLIST_FETCH_CONCURRENCY)
+        .collect()
+        .await;
+
+        let mut index = IbcDenomIndex::new();
+        for (chain, assetlist) in assetlists {
+            let Some(assetlist) = assetlist else {
+                continue;
+            };
+            for (denom, asset) in registry_ibc_denoms(&chain, &assetlist) {
+                index.entry(denom).or_default().push(asset);
+            }
+        }
+
+        let index = Arc::new(index);
+        self.ibc_denom_index.insert(
+            "mainnets".to_string(),
+            CachedItem {
+                data: index.clone(),
+                timestamp: Instant::now(),
+            },
+        );
+        Ok(index)
+    }
+
+    /// Builds, or returns the cached, graph of mainnet chains the registry `_IBC` files connect.
+    pub async fn ibc_graph(&self) -> anyhow::Result<Arc<IbcGraph>> {
+        // Check cache first
+        if let Some(cached) = self.ibc_graph.get("mainnets") {
+            if cached.timestamp.elapsed() < self.ttl {
+                return Ok(cached.data.clone());
+            } else {
+                drop(cached);
+                self.ibc_graph.remove("mainnets");
+            }
+        }
+
+        let chains = self.list_chains().await?;
+        let path_names = cosmos_chain_registry::get::list_paths()
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let graph = Arc::new(IbcGraph::from_path_names(&path_names, &chains));
+        self.ibc_graph.insert(
+            "mainnets".to_string(),
+            CachedItem {
+                data: graph.clone(),
+                timestamp: Instant::now(),
+            },
+        );
+        Ok(graph)
+    }
+
+    /// Builds, or returns the cached, index of the channels the registry lists on `chain`, so
+    /// each chain's `_IBC` files are read once per TTL rather than on every lookup.
+    pub async fn channel_counterparties(
+        &self,
+        chain: &str,
+    ) -> anyhow::Result<Arc<ChannelCounterparties>> {
+        // Check cache first
+        if let Some(cached) = self.channel_counterparties.get(chain) {
+            if cached.timestamp.elapsed() < self.ttl {
+                return Ok(cached.data.clone());
+            } else {
+                drop(cached);
+                self.channel_counterparties.remove(chain);
+            }
+        }
+
+        let graph = self.ibc_graph().await?;
+        let index: ChannelCounterparties = stream::iter(graph.neighbours(chain))
+            .map(|counterparty| async move {
+                match self.get_ibc_path(chain, &counterparty).await {
+                    Ok(Some(path)) => registry_channel_counterparties(&path, chain),
+                    Ok(None) => Vec::new(),
+                    Err(e) => {
+                        log::warn!("Could not fetch IBC path {}-{}: {}", chain, counterparty, e);
+                        Vec::new()
+                    }
+                }
+            })
+            .buffer_unordered(CHAIN_FETCH_CONCURRENCY)
+            .flat_map(stream::iter)
+            .collect()
+            .await;
+
+        let index = Arc::new(index);
+        self.channel_counterparties.insert(
+            chain.to_string(),
+            CachedItem {
+                data: index.clone(),
+                timestamp: Instant::now(),
+            },
+        );
+        Ok(index)
+    }
+
+    /// Bech32 prefix and coin type of every mainnet chain, sorted by chain name.
+    pub async fn chain_prefixes(&self) -> anyhow::Result<Arc<Vec<ChainAddressPrefix>>> {
+        // Check cache first
+        if let Some(cached) = self.chain_prefixes.get("mainnets") {
+            if cached.timestamp.elapsed() < self.ttl {
+                return Ok(cached.data.clone());
+            } else {
+                drop(cached);
+                self.chain_prefixes.remove("mainnets");
+            }
+        }
+
+        let chains = self.list_chains().await?;
+        let mut prefixes: Vec<ChainAddressPrefix> = stream::iter(
+            chains
+                .into_iter()
+                .filter(|chain| !chain.starts_with('_') && !chain.starts_with('.')),
+        )
+        .map(|chain| async move {
+            match self.get_chain(&chain).await {
+                Ok(Some(info)) if !info.bech32_prefix.is_empty() => Some(ChainAddressPrefix {
+                    chain_name: chain,
+                    bech32_prefix: info.bech32_prefix,
+                    slip44: info.slip44,
+                }),
+                Ok(_) => None,
+                Err(e) => {
+                    log::warn!("Could not fetch chain info for {}: {}", chain, e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(CHAIN_FETCH_CONCURRENCY)
+        .filter_map(|prefix| async move { prefix })
+        .collect()
+        .await;
+        prefixes.sort_by(|a, b| a.chain_name.cmp(&b.chain_name));
+
+        let prefixes = Arc::new(prefixes);
+        self.chain_prefixes.insert(
+            "mainnets".to_string(),
+            CachedItem {
+                data: prefixes.clone(),
+                timestamp: Instant::now(),
+            },
+        );
+        Ok(prefixes)
+    }
+
+    /// Remembers a chain's live channel list, so later registry lookups can spot closed channels.
+    pub fn store_ibc_channels(&self, chain: &str, channels: Vec<IbcChannelSummary>) {
+        self.ibc_channels.insert(
+            chain.to_string(),
+            CachedItem {
+                data: Arc::new(channels),
+                timestamp: Instant::now(),
+            },
+        );
+    }
+
+    /// The live channel list last stored for a chain, if it is still within the TTL.
+    pub fn cached_ibc_channels(&self, chain: &str) -> Option<Arc<Vec<IbcChannelSummary>>> {
+        self.ibc_channels
+            .get(chain)
+            .filter(|cached| cached.timestamp.elapsed() < self.ttl)
+            .map(|cached| cached.data.clone())
+    }
+
+    /// Keeps a balance page cursor for the cache TTL and returns the token that refers to it.
+    pub fn store_balance_cursor(&self, cursor: BalanceCursor) -> String {
+        self.balance_cursors
+            .retain(|_, cached| cached.timestamp.elapsed() < self.ttl);
+
+        let token = format!("{:x}", self.next_cursor_id.fetch_add(1, Ordering::Relaxed));
+        self.balance_cursors.insert(
+            token.clone(),
+            CachedItem {
+                data: cursor,
+                timestamp: Instant::now(),
+            },
+        );
+        token
+    }
+
+    pub fn balance_cursor(&self, token: &str) -> Option<BalanceCursor> {
+        self.balance_cursors
+            .get(token)
+            .filter(|cached| cached.timestamp.elapsed() < self.ttl)
+            .map(|cached| cached.data.clone())
+    }
+
+    /// Keeps a pending chain switch for the cache TTL and returns the token that refers to it.
+    pub fn store_chain_switch(&self, switch: ChainSwitch) -> String {
+        self.chain_switches
+            .retain(|_, cached| cached.timestamp.elapsed() < self.ttl);
+
+        let token = format!("{:x}", self.next_cursor_id.fetch_add(1, Ordering::Relaxed));
+        self.chain_switches.insert(
+            token.clone(),
+            CachedItem {
+                data: switch,
+                timestamp: Instant::now(),
+            },
+        );
+        token
+    }
+
+    pub fn chain_switch(&self, token: &str) -> Option<ChainSwitch> {
+        self.chain_switches
+            .get(token)
+            .filter(|cached| cached.timestamp.elapsed() < self.ttl)
+            .map(|cached| cached.data.clone())
+    }
+
+    /// USD prices for a chain's denoms. Misses are remembered too, so unpriced assets do not
+    /// hit every source again until the price TTL runs out.
+    pub async fn usd_prices(&self, chain: &str, queries: &[PriceQuery]) -> UsdPrices {
+        let mut prices = UsdPrices::new();
+        let mut missing = Vec::new();
+        for query in queries {
+            let key = format!("{chain}/{}", query.denom);
+            match self.usd_prices.get(&key) {
+                Some(cached) if cached.timestamp.elapsed() < PRICE_TTL => {
+                    if let Some(price) = cached.data {
+                        prices.insert(query.denom.clone(), price);
+                    }
+                }
+                _ => missing.push(query.clone()),
+            }
+        }
+        if missing.is_empty() {
+            return prices;
+        }
+
+        let found = fetch_usd_prices(&self.price_sources, &missing).await;
+        for query in &missing {
+            let price = found.get(&query.denom).copied();
+            self.usd_prices.insert(
+                format!("{chain}/{}", query.denom),
+                CachedItem {
+                    data: price,
+                    timestamp: Instant::now(),
+                },
+            );
+            if let Some(price) = price {
+                prices.insert(query.denom.clone(), price);
+            }
+        }
+        prices
+    }
 }
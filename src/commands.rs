@@ -20,4 +20,8 @@ pub enum Command {
     Mainnet,
     #[command(description = "Show mainnet chains")]
     Mainnets,
+    #[command(description = "Find IBC routes: /route <source> <destination>")]
+    Route(String),
+    #[command(description = "Convert an address for every registry chain: /address <address>")]
+    Address(String),
 }
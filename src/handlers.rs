@@ -2,16 +2,37 @@ use crate::{
     bot::{MyDialogue, State},
     cache::RegistryCache,
     utils::{
-        escape_markdown, extract_channel_from_path, find_healthy_grpc_endpoint,
-        first_endpoint_address, format_channel_input, format_osmosis_pool_incentives,
-        format_osmosis_pool_info, format_osmosis_token_price, format_wallet_balances,
-        get_polkachu_installation_url, query_abci_info_grpc, query_balances_grpc_first,
-        query_ibc_channel_info_grpc_first, query_ibc_denom_grpc_first,
-        query_osmosis_pool_incentives, query_osmosis_pool_info, query_osmosis_token_price,
-        WalletBalance, PAGE_SIZE,
+        asset_display_unit, canonical_transfer_channel, channel_end_mismatches,
+        check_registry_trace, check_wallet_address, decode_address, display_unit_for_denom,
+        escape_markdown, find_healthy_grpc_endpoint, find_registry_asset, first_endpoint_address,
+        format_account_info, format_address_conversions, format_address_grants,
+        format_asset_denom_lookup, format_balance_summary, format_chain_params,
+        format_channel_input, format_consistency_report, format_denom_route, format_escrow_report,
+        format_fee_card, format_forward_memo, format_global_denom_matches, format_ibc_channels,
+        format_ibc_routes, format_light_client_status, format_osmosis_pool_incentives,
+        format_osmosis_pool_info, format_osmosis_token_price, format_pending_packets,
+        format_portfolio_scan, format_rate_limits, format_raw_denom_hash, format_software_card,
+        format_validator_info, format_wallet_balances, get_polkachu_installation_url,
+        parse_chain_software, query_abci_info_grpc, query_account_grpc_first,
+        query_address_grants_grpc_first, query_balances_grpc_first, query_chain_params_grpc_first,
+        query_channel_escrows_grpc_first, query_cw20_balances_grpc_first,
+        query_ibc_channel_info_grpc_first, query_ibc_channels_grpc_first,
+        query_ibc_denom_grpc_first, query_live_base_fee_grpc_first, query_osmosis_pool_incentives,
+        query_osmosis_pool_info, query_osmosis_token_price, query_pending_packets_grpc,
+        query_rate_limits_grpc_first, query_validator_grpc_first, rank_ibc_routes,
+        registry_cw20_tokens, source_slip44, split_trace_path, to_base_units,
+        verify_destination_denom, without_closed_channels, AddressKind, AssetDenomLookup, Balance,
+        BalanceCursor, ChainAddressPrefix, ChainPortfolio, ChainSwitch, ChannelConsistency,
+        ChannelEnd, ConsistencyReport, DenomRoute, DenomRouteHop, DenomUnits, DenomVerification,
+        GlobalDenomMatch, IbcChannelSummary, IbcDenomHop, IbcDenomTrace, IbcRoute, MemoDraft,
+        MemoStep, OsmosisSwap, PortfolioScan, PriceQuery, RateLimitLookup, RegistryChannelEnd,
+        RegistryTransferChannel, RouteHop, WalletAddressCheck, WalletBalance, ETHERMINT_SLIP44,
+        MAX_BALANCE_PAGES, MAX_ROUTE_HOPS, OSMOSIS_CROSSCHAIN_SWAPS_CONTRACT, PAGE_SIZE, PFM_HUBS,
     },
 };
-use cosmos_chain_registry::AssetList;
+use cosmos_chain_registry::{assets::Asset, AssetList, ChainInfo, IBCPath};
+use futures::{stream, StreamExt};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use teloxide::{
     prelude::*,
@@ -53,13 +74,176 @@ pub async fn help(bot: Bot, msg: Message) -> Result<(), Box<dyn std::error::Erro
         Commands:\n\
         /start - Start the bot\n\
         /restart - Clear session and restart\n\
-        /help - Show this message\n\n\
-        You can also type a chain name directly to select it.",
+        /help - Show this message\n\
+        /route <source> <destination> - Find IBC transfer routes between chains\n\
+        /address <address> - Convert an address for every registry chain\n\n\
+        You can also type a chain name directly to select it, \
+        or paste an ibc/ denom to find the chains that know it.",
     )
     .await?;
     Ok(())
 }
 
+/// `_IBC` files fetched in parallel while resolving route candidates.
+const ROUTE_PATH_FETCH_CONCURRENCY: usize = 16;
+
+pub async fn find_route(
+    bot: Bot,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    args: String,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let chains: Vec<String> = args
+        .split_whitespace()
+        .map(|chain| chain.to_lowercase())
+        .collect();
+    let [source, destination] = chains.as_slice() else {
+        bot.send_message(
+            msg.chat.id,
+            "Usage: /route <source> <destination> (e.g., /route stride neutron)",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    send_processing_action(&bot, msg.chat.id).await;
+    let status = bot
+        .send_message(
+            msg.chat.id,
+            format!("Searching IBC routes from {source} to {destination}..."),
+        )
+        .await?;
+    let message = match find_ibc_routes(&cache, source, destination).await {
+        Ok(message) => message,
+        Err(e) => format!("❌ Could not search IBC routes:\n{e}"),
+    };
+    edit_status_message(&bot, msg.chat.id, status.id, message, None).await?;
+    Ok(())
+}
+
+/// Enumerates candidate chain sequences on the registry graph, then resolves each hop's
+/// canonical transfer channel from its `_IBC` file; candidates with a missing hop are dropped.
+/// Channels a cached live channel list shows as not open are skipped, but hops on chains
+/// without a cached list are not checked on chain.
+async fn find_ibc_routes(
+    cache: &Arc<RegistryCache>,
+    source: &str,
+    destination: &str,
+) -> anyhow::Result<String> {
+    if source == destination {
+        anyhow::bail!("Source and destination are the same chain");
+    }
+    let graph = cache.ibc_graph().await?;
+    for chain in [source, destination] {
+        if !graph.contains(chain) {
+            anyhow::bail!("{chain} has no IBC paths in the chain registry");
+        }
+    }
+
+    let candidates = graph.candidate_routes(source, destination, &PFM_HUBS, MAX_ROUTE_HOPS);
+    let pairs: HashSet<(String, String)> = candidates
+        .iter()
+        .flat_map(|route| route.windows(2))
+        .map(|pair| (pair[0].clone(), pair[1].clone()))
+        .collect();
+    let closed: HashSet<(String, String)> = pairs
+        .iter()
+        .flat_map(|(from, to)| [from, to])
+        .filter_map(|chain| Some((chain, cache.cached_ibc_channels(chain)?)))
+        .flat_map(|(chain, channels)| {
+            channels
+                .iter()
+                .filter(|channel| channel.port_id == "transfer" && channel.state != "Open")
+                .map(|channel| (chain.clone(), channel.channel_id.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let closed = &closed;
+    let channels: HashMap<(String, String), RegistryTransferChannel> = stream::iter(pairs)
+        .map(|(from, to)| async move {
+            let channel = match cache.get_ibc_path(&from, &to).await {
+                Ok(path) => path.and_then(|path| {
+                    canonical_transfer_channel(&without_closed_channels(&path, closed), &from)
+                }),
+                Err(e) => {
+                    log::warn!("Could not fetch IBC path {}-{}: {}", from, to, e);
+                    None
+                }
+            };
+            channel.map(|channel| ((from, to), channel))
+        })
+        .buffer_unordered(ROUTE_PATH_FETCH_CONCURRENCY)
+        .filter_map(|resolved| async move { resolved })
+        .collect()
+        .await;
+
+    let mut routes: Vec<IbcRoute> = candidates
+        .iter()
+        .filter_map(|route| {
+            let hops = route
+                .windows(2)
+                .map(|pair| {
+                    let channel = channels.get(&(pair[0].clone(), pair[1].clone()))?;
+                    Some(RouteHop {
+                        from: pair[0].clone(),
+                        to: pair[1].clone(),
+                        channel: channel.clone(),
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(IbcRoute { hops })
+        })
+        .collect();
+    rank_ibc_routes(&mut routes);
+
+    Ok(format_ibc_routes(source, destination, &routes))
+}
+
+pub async fn convert_address(
+    bot: Bot,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    address: String,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if address.trim().is_empty() {
+        bot.send_message(
+            msg.chat.id,
+            "Usage: /address <address> (a bech32 account, valoper or valcons address, or a 0x EVM address)",
+        )
+        .await?;
+        return Ok(());
+    }
+    let decoded = match decode_address(&address) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            bot.send_message(msg.chat.id, format!("❌ {e}")).await?;
+            return Ok(());
+        }
+    };
+
+    send_processing_action(&bot, msg.chat.id).await;
+    let status = bot
+        .send_message(msg.chat.id, "Converting address for registry chains...")
+        .await?;
+    match cache.chain_prefixes().await {
+        Ok(chains) => {
+            let message = format_address_conversions(&address, &decoded, &chains);
+            edit_or_send_markdown_result(&bot, msg.chat.id, status.id, message).await?;
+        }
+        Err(e) => {
+            edit_status_message(
+                &bot,
+                msg.chat.id,
+                status.id,
+                format!("❌ Could not load registry chains:\n{e}"),
+                None,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
 pub async fn show_testnets(
     bot: Bot,
     dialogue: MyDialogue,
@@ -392,18 +576,28 @@ async fn edit_or_send_markdown_result(
     chat_id: ChatId,
     status_id: MessageId,
     message: String,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    edit_or_send_markdown_result_with_keyboard(bot, chat_id, status_id, message, None).await
+}
+
+/// Like `edit_or_send_markdown_result`, attaching `keyboard` to the last part.
+async fn edit_or_send_markdown_result_with_keyboard(
+    bot: &Bot,
+    chat_id: ChatId,
+    status_id: MessageId,
+    message: String,
+    keyboard: Option<InlineKeyboardMarkup>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let chunks = split_telegram_message(&message, 3800);
 
     if chunks.len() == 1 {
-        edit_status_message(
-            bot,
-            chat_id,
-            status_id,
-            message,
-            Some(ParseMode::MarkdownV2),
-        )
-        .await?;
+        let request = bot
+            .edit_message_text(chat_id, status_id, message)
+            .parse_mode(ParseMode::MarkdownV2);
+        match keyboard {
+            Some(keyboard) => request.reply_markup(keyboard).await?,
+            None => request.await?,
+        };
         return Ok(());
     }
 
@@ -416,10 +610,15 @@ async fn edit_or_send_markdown_result(
     )
     .await?;
 
-    for chunk in chunks {
-        bot.send_message(chat_id, chunk)
-            .parse_mode(ParseMode::MarkdownV2)
-            .await?;
+    let last = chunks.len() - 1;
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        let request = bot
+            .send_message(chat_id, chunk)
+            .parse_mode(ParseMode::MarkdownV2);
+        match keyboard.clone().filter(|_| index == last) {
+            Some(keyboard) => request.reply_markup(keyboard).await?,
+            None => request.await?,
+        };
     }
 
     Ok(())
@@ -435,48 +634,69 @@ fn is_osmosis_mainnet(chain: &str) -> bool {
     chain.eq_ignore_ascii_case("osmosis")
 }
 
-fn push_osmosis_buttons(buttons: &mut Vec<Vec<InlineKeyboardButton>>) {
-    buttons.push(vec![
-        InlineKeyboardButton::callback("8. LP Incentives", "action:pool_incentives"),
-        InlineKeyboardButton::callback("9. Pool Info", "action:pool_info"),
-    ]);
-    buttons.push(vec![InlineKeyboardButton::callback(
-        "10. Price Info",
-        "action:price_info",
-    )]);
-}
-
-async fn show_chain_menu(
-    bot: &Bot,
-    q: &CallbackQuery,
-    chain: &str,
-) -> Result<MessageId, Box<dyn std::error::Error + Send + Sync>> {
-    let mut buttons = vec![
-        vec![
-            InlineKeyboardButton::callback("1. Chain Info", "action:chain_info"),
-            InlineKeyboardButton::callback("2. Peer Nodes", "action:peer_nodes"),
-        ],
-        vec![
-            InlineKeyboardButton::callback("3. Endpoints", "action:endpoints"),
-            InlineKeyboardButton::callback("4. Block Explorers", "action:explorers"),
-        ],
+/// Chain menu actions in display order. The position in this list is also the
+/// number users can type to pick an action, so buttons and text input agree.
+fn chain_menu_actions(chain: &str, is_mainnet: bool) -> Vec<(&'static str, &'static str)> {
+    let mut actions = vec![
+        ("chain_info", "Chain Info"),
+        ("peer_nodes", "Peer Nodes"),
+        ("endpoints", "Endpoints"),
+        ("explorers", "Block Explorers"),
     ];
 
-    // Add IBC options and balance check for mainnets
-    if !chain.contains("testnet") {
-        buttons.push(vec![
-            InlineKeyboardButton::callback("5. IBC-ID", "action:ibc_id"),
-            InlineKeyboardButton::callback("6. IBC Route Info", "action:ibc_route"),
+    // IBC options and address lookups are only offered for mainnets
+    if is_mainnet {
+        actions.extend([
+            ("ibc_id", "IBC-ID"),
+            ("asset_denom", "Where Is My Asset"),
+            ("ibc_route", "IBC Route Info"),
+            ("transfer_channels", "Transfer Channels"),
+            ("all_channels", "All Channels"),
+            ("pending_packets", "Pending Packets"),
+            ("escrow", "IBC Escrow"),
+            ("rate_limits", "Rate Limits"),
+            ("memo", "Memo Builder"),
+            ("registry_check", "Registry IBC Check"),
+            ("check_balance", "Check Balance"),
+            ("portfolio_scan", "Scan All Chains"),
+            ("account_info", "Account Info"),
+            ("grants", "Authz & Feegrants"),
+            ("chain_params", "Chain Params"),
+        ]);
+    }
+
+    // Registry cards work for any chain listed in the registry
+    actions.extend([("fees", "Fees"), ("software", "Software")]);
+
+    if is_mainnet && is_osmosis_mainnet(chain) {
+        actions.extend([
+            ("pool_incentives", "LP Incentives"),
+            ("pool_info", "Pool Info"),
+            ("price_info", "Price Info"),
         ]);
-        buttons.push(vec![InlineKeyboardButton::callback(
-            "7. Check Balance",
-            "action:check_balance",
-        )]);
-        if is_osmosis_mainnet(chain) {
-            push_osmosis_buttons(&mut buttons);
-        }
     }
 
+    actions
+}
+
+fn chain_menu_keyboard(chain: &str, is_mainnet: bool) -> InlineKeyboardMarkup {
+    let actions = chain_menu_actions(chain, is_mainnet);
+    let mut buttons: Vec<Vec<InlineKeyboardButton>> = actions
+        .chunks(2)
+        .enumerate()
+        .map(|(row, pair)| {
+            pair.iter()
+                .enumerate()
+                .map(|(col, (action, label))| {
+                    InlineKeyboardButton::callback(
+                        format!("{}. {}", row * 2 + col + 1, label),
+                        format!("action:{}", action),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
     // Add installation guide link if available on Polkachu
     if let Some(install_url) = get_polkachu_installation_url(chain) {
         buttons.push(vec![InlineKeyboardButton::url(
@@ -490,7 +710,15 @@ async fn show_chain_menu(
         "back:chains",
     )]);
 
-    let keyboard = InlineKeyboardMarkup::new(buttons);
+    InlineKeyboardMarkup::new(buttons)
+}
+
+async fn show_chain_menu(
+    bot: &Bot,
+    q: &CallbackQuery,
+    chain: &str,
+) -> Result<MessageId, Box<dyn std::error::Error + Send + Sync>> {
+    let keyboard = chain_menu_keyboard(chain, !chain.contains("testnet"));
 
     // Send as a new message instead of editing
     if let Some(Message { chat, .. }) = &q.message {
@@ -529,46 +757,7 @@ async fn send_chain_menu(
     msg: &Message,
     chain: &str,
 ) -> Result<MessageId, Box<dyn std::error::Error + Send + Sync>> {
-    let mut buttons = vec![
-        vec![
-            InlineKeyboardButton::callback("1. Chain Info", "action:chain_info"),
-            InlineKeyboardButton::callback("2. Peer Nodes", "action:peer_nodes"),
-        ],
-        vec![
-            InlineKeyboardButton::callback("3. Endpoints", "action:endpoints"),
-            InlineKeyboardButton::callback("4. Block Explorers", "action:explorers"),
-        ],
-    ];
-
-    // Add IBC options and balance check for mainnets
-    if !chain.contains("testnet") {
-        buttons.push(vec![
-            InlineKeyboardButton::callback("5. IBC-ID", "action:ibc_id"),
-            InlineKeyboardButton::callback("6. IBC Route Info", "action:ibc_route"),
-        ]);
-        buttons.push(vec![InlineKeyboardButton::callback(
-            "7. Check Balance",
-            "action:check_balance",
-        )]);
-        if is_osmosis_mainnet(chain) {
-            push_osmosis_buttons(&mut buttons);
-        }
-    }
-
-    // Add installation guide link if available on Polkachu
-    if let Some(install_url) = get_polkachu_installation_url(chain) {
-        buttons.push(vec![InlineKeyboardButton::url(
-            "Node Installation Guide",
-            install_url.parse().unwrap(),
-        )]);
-    }
-
-    buttons.push(vec![InlineKeyboardButton::callback(
-        "← Back",
-        "back:chains",
-    )]);
-
-    let keyboard = InlineKeyboardMarkup::new(buttons);
+    let keyboard = chain_menu_keyboard(chain, !chain.contains("testnet"));
 
     let sent_msg = bot
         .send_message(
@@ -586,7 +775,7 @@ pub async fn handle_chain_action(
     dialogue: MyDialogue,
     cache: Arc<RegistryCache>,
     q: CallbackQuery,
-    (chain, _message_id): (String, Option<teloxide::types::MessageId>),
+    (chain, menu_message_id): (String, Option<teloxide::types::MessageId>),
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     if let Some(ref data) = q.data {
         match data.as_str() {
@@ -669,6 +858,21 @@ pub async fn handle_chain_action(
                         .await?;
                 }
             }
+            "action:asset_denom" => {
+                // Delete the menu with effect
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    let msg_id = q.message.as_ref().map(|m| m.id);
+                    dialogue
+                        .update(State::AwaitingAssetDenom {
+                            chain,
+                            message_id: msg_id,
+                        })
+                        .await?;
+                    bot.send_message(chat.id, "Enter asset and destination chain (e.g., ATOM osmosis),\nor a full trace path to hash (e.g., transfer/channel-0/uatom):")
+                        .await?;
+                }
+            }
             "action:ibc_route" => {
                 // Delete the menu with effect
                 if let Some(Message { id, chat, .. }) = &q.message {
@@ -699,106 +903,420 @@ pub async fn handle_chain_action(
                         .await?;
                 }
             }
-            "action:pool_incentives" => {
+            "action:portfolio_scan" => {
                 if let Some(Message { id, chat, .. }) = &q.message {
                     delete_message_with_effect(&bot, chat.id, *id).await?;
-                    if is_osmosis_mainnet(&chain) {
-                        let msg_id = q.message.as_ref().map(|m| m.id);
-                        dialogue
-                            .update(State::AwaitingOsmosisPoolIncentives {
-                                chain,
-                                message_id: msg_id,
-                            })
-                            .await?;
-                        bot.send_message(chat.id, "Enter Osmosis pool ID for LP incentives:")
-                            .await?;
-                    } else {
-                        bot.send_message(chat.id, "LP incentives are only available for Osmosis.")
-                            .await?;
-                    }
+                    let msg_id = q.message.as_ref().map(|m| m.id);
+                    dialogue
+                        .update(State::AwaitingPortfolioAddress {
+                            chain,
+                            message_id: msg_id,
+                        })
+                        .await?;
+                    bot.send_message(
+                        chat.id,
+                        "Enter an address to scan balances on every chain with the same coin type:",
+                    )
+                    .await?;
                 }
             }
-            "action:pool_info" => {
+            "action:chain_params" => {
                 if let Some(Message { id, chat, .. }) = &q.message {
                     delete_message_with_effect(&bot, chat.id, *id).await?;
-                    if is_osmosis_mainnet(&chain) {
-                        let msg_id = q.message.as_ref().map(|m| m.id);
-                        dialogue
-                            .update(State::AwaitingOsmosisPoolInfo {
-                                chain,
-                                message_id: msg_id,
-                            })
-                            .await?;
-                        bot.send_message(chat.id, "Enter Osmosis pool ID for pool info:")
-                            .await?;
-                    } else {
-                        bot.send_message(chat.id, "Pool info is only available for Osmosis.")
-                            .await?;
-                    }
+                    send_chain_params(&bot, chat.id, &cache, &chain).await?;
                 }
+                let new_menu_id = show_chain_menu(&bot, &q, &chain).await?;
+                dialogue
+                    .update(State::ChainSelected {
+                        chain: chain.clone(),
+                        message_id: Some(new_menu_id),
+                    })
+                    .await?;
             }
-            "action:price_info" => {
+            "action:transfer_channels" => {
                 if let Some(Message { id, chat, .. }) = &q.message {
                     delete_message_with_effect(&bot, chat.id, *id).await?;
-                    if is_osmosis_mainnet(&chain) {
-                        let msg_id = q.message.as_ref().map(|m| m.id);
-                        dialogue
-                            .update(State::AwaitingOsmosisTokenTicker {
-                                chain,
-                                message_id: msg_id,
-                            })
-                            .await?;
-                        bot.send_message(chat.id, "Enter Osmosis token symbol or base denom:")
-                            .await?;
-                    } else {
-                        bot.send_message(chat.id, "Price info is only available for Osmosis.")
-                            .await?;
-                    }
+                    send_channel_list(&bot, chat.id, &cache, &chain, true).await?;
                 }
+                let new_menu_id = show_chain_menu(&bot, &q, &chain).await?;
+                dialogue
+                    .update(State::ChainSelected {
+                        chain: chain.clone(),
+                        message_id: Some(new_menu_id),
+                    })
+                    .await?;
             }
-            "back:chains" => {
-                // No need to delete - update_chain_selection will edit the existing message
-                let msg_id = q.message.as_ref().map(|m| m.id);
+            "action:all_channels" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    send_channel_list(&bot, chat.id, &cache, &chain, false).await?;
+                }
+                let new_menu_id = show_chain_menu(&bot, &q, &chain).await?;
                 dialogue
-                    .update(State::SelectingChain {
-                        page: 0,
-                        is_testnet: false,
-                        message_id: msg_id,
-                        last_selected_chain: Some(chain.clone()),
+                    .update(State::ChainSelected {
+                        chain: chain.clone(),
+                        message_id: Some(new_menu_id),
                     })
                     .await?;
-                update_chain_selection(&bot, &q, &cache, 0, false, Some(&chain)).await?;
             }
-            _ => {}
-        }
-    }
-
-    bot.answer_callback_query(q.id).await?;
-    Ok(())
-}
-
-async fn show_chain_info(
-    bot: &Bot,
-    q: &CallbackQuery,
-    cache: &Arc<RegistryCache>,
-    chain: &str,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let chain_data = cache.get_chain(chain).await?;
-    let assets_data = cache.get_assets(chain).await?;
-
-    if let (Some(chain_info), Some(assets)) = (chain_data, assets_data) {
-        let base_denom = chain_info
-            .staking
-            .staking_tokens
-            .first()
-            .map(|t| t.denom.as_str())
-            .unwrap_or("Unknown");
-
-        let decimals = assets
-            .assets
-            .first()
-            .and_then(|a| a.denom_units.last())
-            .map(|d| d.exponent.to_string())
+            "action:fees" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    send_fee_card(&bot, chat.id, &cache, &chain).await?;
+                }
+                let new_menu_id = show_chain_menu(&bot, &q, &chain).await?;
+                dialogue
+                    .update(State::ChainSelected {
+                        chain: chain.clone(),
+                        message_id: Some(new_menu_id),
+                    })
+                    .await?;
+            }
+            "action:software" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    send_software_card(&bot, chat.id, &cache, &chain).await?;
+                }
+                let new_menu_id = show_chain_menu(&bot, &q, &chain).await?;
+                dialogue
+                    .update(State::ChainSelected {
+                        chain: chain.clone(),
+                        message_id: Some(new_menu_id),
+                    })
+                    .await?;
+            }
+            "action:account_info" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    let msg_id = q.message.as_ref().map(|m| m.id);
+                    dialogue
+                        .update(State::AwaitingAccountAddress {
+                            chain,
+                            message_id: msg_id,
+                        })
+                        .await?;
+                    bot.send_message(chat.id, "Enter address to inspect account:")
+                        .await?;
+                }
+            }
+            "action:pending_packets" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    let msg_id = q.message.as_ref().map(|m| m.id);
+                    dialogue
+                        .update(State::AwaitingPacketChannel {
+                            chain,
+                            message_id: msg_id,
+                        })
+                        .await?;
+                    bot.send_message(chat.id, "Enter channel to inspect pending packets (e.g., 0, channel-0, or channel-0 transfer):")
+                        .await?;
+                }
+            }
+            "action:escrow" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    let msg_id = q.message.as_ref().map(|m| m.id);
+                    dialogue
+                        .update(State::AwaitingEscrowChannel {
+                            chain,
+                            message_id: msg_id,
+                        })
+                        .await?;
+                    bot.send_message(
+                        chat.id,
+                        "Enter transfer channel to inspect escrow (e.g., 0 or channel-0),\nor \"all\" for every open transfer channel:",
+                    )
+                    .await?;
+                }
+            }
+            "action:rate_limits" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    let msg_id = q.message.as_ref().map(|m| m.id);
+                    dialogue
+                        .update(State::AwaitingRateLimit {
+                            chain,
+                            message_id: msg_id,
+                        })
+                        .await?;
+                    bot.send_message(
+                        chat.id,
+                        "Enter transfer channel to check rate limits (e.g., 0 or channel-0),\noptionally followed by a denom or symbol (e.g., channel-0 uatom):",
+                    )
+                    .await?;
+                }
+            }
+            data if data.starts_with("balances:") => {
+                // Switching follows an address to the chain its prefix belongs to
+                let mut menu_chain = chain.clone();
+                if let Some(Message { chat, .. }) = &q.message {
+                    let (mode, token) = data
+                        .trim_start_matches("balances:")
+                        .split_once(':')
+                        .unwrap_or(("page", ""));
+                    let all_pages = mode == "all";
+                    // A switch starts a fresh first page on the chain the address belongs to
+                    let cursor = if mode == "switch" {
+                        cache.chain_switch(token).map(|switch| BalanceCursor {
+                            chain: switch.chain,
+                            address: switch.address,
+                            key: None,
+                            page: 0,
+                            previous: None,
+                        })
+                    } else {
+                        cache.balance_cursor(token)
+                    };
+                    let Some(cursor) = cursor else {
+                        bot.answer_callback_query(q.id)
+                            .text("This balance view has expired; look the address up again.")
+                            .await?;
+                        return Ok(());
+                    };
+                    if mode == "switch" {
+                        menu_chain = cursor.chain.clone();
+                    }
+                    // The menu below the balances moves under the new page
+                    if let Some(menu_id) = menu_message_id {
+                        let _ = bot.delete_message(chat.id, menu_id).await;
+                    }
+                    send_processing_action(&bot, chat.id).await;
+                    let status = bot
+                        .send_message(
+                            chat.id,
+                            if all_pages {
+                                format!("Fetching every balance page on {}...", cursor.chain)
+                            } else if mode == "switch" {
+                                format!("Checking balances on {}...", cursor.chain)
+                            } else {
+                                format!("Fetching balance page {}...", cursor.page + 1)
+                            },
+                        )
+                        .await?;
+                    if all_pages {
+                        send_balance_summary(&bot, chat.id, status.id, &cache, &cursor).await?;
+                    } else {
+                        send_balance_page(&bot, chat.id, status.id, &cache, cursor).await?;
+                    }
+                }
+                let new_menu_id = show_chain_menu(&bot, &q, &menu_chain).await?;
+                dialogue
+                    .update(State::ChainSelected {
+                        chain: menu_chain,
+                        message_id: Some(new_menu_id),
+                    })
+                    .await?;
+            }
+            data if data.starts_with("validator:") => {
+                // The stored switch carries the owning chain and the operator address
+                let mut menu_chain = chain.clone();
+                if let Some(Message { chat, .. }) = &q.message {
+                    let Some(switch) = cache.chain_switch(data.trim_start_matches("validator:"))
+                    else {
+                        bot.answer_callback_query(q.id)
+                            .text("This lookup has expired; enter the address again.")
+                            .await?;
+                        return Ok(());
+                    };
+                    menu_chain = switch.chain.clone();
+                    if let Some(menu_id) = menu_message_id {
+                        let _ = bot.delete_message(chat.id, menu_id).await;
+                    }
+                    send_processing_action(&bot, chat.id).await;
+                    let status = bot
+                        .send_message(
+                            chat.id,
+                            format!("Looking up the validator on {}...", switch.chain),
+                        )
+                        .await?;
+                    send_validator_info(
+                        &bot,
+                        chat.id,
+                        status.id,
+                        &cache,
+                        &switch.chain,
+                        &switch.address,
+                    )
+                    .await?;
+                }
+                let new_menu_id = show_chain_menu(&bot, &q, &menu_chain).await?;
+                dialogue
+                    .update(State::ChainSelected {
+                        chain: menu_chain,
+                        message_id: Some(new_menu_id),
+                    })
+                    .await?;
+            }
+            data if data.starts_with("rate_limits:") => {
+                let channel_id = data.trim_start_matches("rate_limits:");
+                if let Some(Message { chat, .. }) = &q.message {
+                    // Keep the route message; replace the menu below it with the result
+                    // (it may already be gone if another prompt was opened since)
+                    if let Some(menu_id) = menu_message_id {
+                        let _ = bot.delete_message(chat.id, menu_id).await;
+                    }
+                    send_processing_action(&bot, chat.id).await;
+                    let status = bot
+                        .send_message(chat.id, format!("Checking rate limits on {chain}..."))
+                        .await?;
+                    let message = match inspect_rate_limits(&cache, &chain, channel_id, None).await
+                    {
+                        Ok(message) => message,
+                        Err(e) => format!("❌ Could not read rate limits:\n{e}"),
+                    };
+                    edit_status_message(&bot, chat.id, status.id, message, None).await?;
+                }
+                let new_menu_id = show_chain_menu(&bot, &q, &chain).await?;
+                dialogue
+                    .update(State::ChainSelected {
+                        chain: chain.clone(),
+                        message_id: Some(new_menu_id),
+                    })
+                    .await?;
+            }
+            "action:memo" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    let msg_id = q.message.as_ref().map(|m| m.id);
+                    bot.send_message(chat.id, format!("Build a packet-forward memo from {chain}.\nEnter the chains to pass through, ending with the destination (e.g., osmosis or cosmoshub neutron):"))
+                        .await?;
+                    dialogue
+                        .update(State::BuildingMemo {
+                            chain,
+                            message_id: msg_id,
+                            draft: MemoDraft::default(),
+                        })
+                        .await?;
+                }
+            }
+            "action:registry_check" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    send_registry_check(&bot, chat.id, &cache, &chain).await?;
+                }
+                let new_menu_id = show_chain_menu(&bot, &q, &chain).await?;
+                dialogue
+                    .update(State::ChainSelected {
+                        chain: chain.clone(),
+                        message_id: Some(new_menu_id),
+                    })
+                    .await?;
+            }
+            "action:grants" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    let msg_id = q.message.as_ref().map(|m| m.id);
+                    dialogue
+                        .update(State::AwaitingGrantsAddress {
+                            chain,
+                            message_id: msg_id,
+                        })
+                        .await?;
+                    bot.send_message(chat.id, "Enter address to look up grants:")
+                        .await?;
+                }
+            }
+            "action:pool_incentives" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    if is_osmosis_mainnet(&chain) {
+                        let msg_id = q.message.as_ref().map(|m| m.id);
+                        dialogue
+                            .update(State::AwaitingOsmosisPoolIncentives {
+                                chain,
+                                message_id: msg_id,
+                            })
+                            .await?;
+                        bot.send_message(chat.id, "Enter Osmosis pool ID for LP incentives:")
+                            .await?;
+                    } else {
+                        bot.send_message(chat.id, "LP incentives are only available for Osmosis.")
+                            .await?;
+                    }
+                }
+            }
+            "action:pool_info" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    if is_osmosis_mainnet(&chain) {
+                        let msg_id = q.message.as_ref().map(|m| m.id);
+                        dialogue
+                            .update(State::AwaitingOsmosisPoolInfo {
+                                chain,
+                                message_id: msg_id,
+                            })
+                            .await?;
+                        bot.send_message(chat.id, "Enter Osmosis pool ID for pool info:")
+                            .await?;
+                    } else {
+                        bot.send_message(chat.id, "Pool info is only available for Osmosis.")
+                            .await?;
+                    }
+                }
+            }
+            "action:price_info" => {
+                if let Some(Message { id, chat, .. }) = &q.message {
+                    delete_message_with_effect(&bot, chat.id, *id).await?;
+                    if is_osmosis_mainnet(&chain) {
+                        let msg_id = q.message.as_ref().map(|m| m.id);
+                        dialogue
+                            .update(State::AwaitingOsmosisTokenTicker {
+                                chain,
+                                message_id: msg_id,
+                            })
+                            .await?;
+                        bot.send_message(chat.id, "Enter Osmosis token symbol or base denom:")
+                            .await?;
+                    } else {
+                        bot.send_message(chat.id, "Price info is only available for Osmosis.")
+                            .await?;
+                    }
+                }
+            }
+            "back:chains" => {
+                // No need to delete - update_chain_selection will edit the existing message
+                let msg_id = q.message.as_ref().map(|m| m.id);
+                dialogue
+                    .update(State::SelectingChain {
+                        page: 0,
+                        is_testnet: false,
+                        message_id: msg_id,
+                        last_selected_chain: Some(chain.clone()),
+                    })
+                    .await?;
+                update_chain_selection(&bot, &q, &cache, 0, false, Some(&chain)).await?;
+            }
+            _ => {}
+        }
+    }
+
+    bot.answer_callback_query(q.id).await?;
+    Ok(())
+}
+
+async fn show_chain_info(
+    bot: &Bot,
+    q: &CallbackQuery,
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let chain_data = cache.get_chain(chain).await?;
+    let assets_data = cache.get_assets(chain).await?;
+
+    if let (Some(chain_info), Some(assets)) = (chain_data, assets_data) {
+        let base_denom = chain_info
+            .staking
+            .staking_tokens
+            .first()
+            .map(|t| t.denom.as_str())
+            .unwrap_or("Unknown");
+
+        let decimals = assets
+            .assets
+            .first()
+            .and_then(|a| a.denom_units.last())
+            .map(|d| d.exponent.to_string())
             .unwrap_or_else(|| "Unknown".to_string());
 
         let rpc = first_endpoint_address(&chain_info.apis.rpc);
@@ -1017,83 +1535,367 @@ async fn show_explorers(
     Ok(())
 }
 
-pub async fn handle_ibc_denom(
-    bot: Bot,
-    dialogue: MyDialogue,
-    cache: Arc<RegistryCache>,
-    msg: Message,
-    (chain, _message_id): (String, Option<teloxide::types::MessageId>),
+async fn send_chain_params(
+    bot: &Bot,
+    chat_id: ChatId,
+    cache: &Arc<RegistryCache>,
+    chain: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    if let Some(text) = msg.text() {
-        if let Some(ibc_hash) = text.strip_prefix("ibc/") {
-            send_processing_action(&bot, msg.chat.id).await;
-            let status = bot
-                .send_message(msg.chat.id, format!("Resolving IBC denom on {chain}..."))
+    send_processing_action(bot, chat_id).await;
+    let status = bot
+        .send_message(chat_id, format!("Fetching chain parameters on {chain}..."))
+        .await?;
+
+    if let Some(chain_info) = cache.get_chain(chain).await? {
+        match query_chain_params_grpc_first(&chain_info.apis.grpc).await {
+            Ok(params) => {
+                edit_or_send_markdown_result(
+                    bot,
+                    chat_id,
+                    status.id,
+                    format_chain_params(chain, &params, &chain_denom_units(cache, chain).await),
+                )
+                .await?;
+            }
+            Err(e) => {
+                edit_status_message(
+                    bot,
+                    chat_id,
+                    status.id,
+                    format!("❌ Could not fetch chain parameters:\n{e}"),
+                    None,
+                )
                 .await?;
+            }
+        }
+    } else {
+        edit_status_message(bot, chat_id, status.id, "Chain not found".to_string(), None).await?;
+    }
 
-            if let Some(chain_info) = cache.get_chain(&chain).await? {
-                match query_ibc_denom_grpc_first(&chain_info.apis.grpc, ibc_hash).await {
-                    Ok(trace) => {
-                        let mut message = if !trace.path.is_empty() {
-                            format!(
-                                "✅ IBC Denom Trace Found:\n\nPath: {}\nBase Denomination: {}",
-                                trace.path, trace.base_denom
-                            )
-                        } else {
-                            format!(
-                                "✅ IBC Denom Trace Found:\n\nBase Denomination: {}",
-                                trace.base_denom
-                            )
-                        };
-
-                        // Try to extract channel from path and fetch route info
-                        if !trace.path.is_empty() {
-                            if let Some(channel) = extract_channel_from_path(&trace.path) {
-                                match query_ibc_channel_info_grpc_first(
-                                    &chain_info.apis.grpc,
-                                    &channel,
-                                    "transfer",
-                                )
-                                .await
-                                {
-                                    Ok(info) => {
-                                        message.push_str(&format!(
-                                            "\n\n📍 Source Chain: {}\nvia {}",
-                                            info.counterparty_chain_id, channel
-                                        ));
-                                    }
-                                    Err(e) => {
-                                        log::warn!(
-                                            "Could not fetch channel info for {}: {}",
-                                            channel,
-                                            e
-                                        );
-                                    }
-                                }
-                            }
-                        }
+    Ok(())
+}
 
-                        edit_status_message(&bot, msg.chat.id, status.id, message, None).await?;
-                    }
+/// Chains queried at once while confirming where a global denom is known.
+const GLOBAL_DENOM_CHECK_CONCURRENCY: usize = 8;
+
+/// Maps counterparty chain IDs to registry names, skipping IDs the registry does not know.
+async fn resolve_chain_names(
+    cache: &Arc<RegistryCache>,
+    chain_ids: HashSet<String>,
+) -> HashMap<String, String> {
+    let index = match cache.chain_id_index().await {
+        Ok(index) => index,
+        Err(e) => {
+            log::warn!("Could not build the chain ID index: {}", e);
+            return HashMap::new();
+        }
+    };
+
+    chain_ids
+        .into_iter()
+        .filter_map(|chain_id| {
+            let name = index.get(&chain_id)?.clone();
+            Some((chain_id, name))
+        })
+        .collect()
+}
+
+async fn send_channel_list(
+    bot: &Bot,
+    chat_id: ChatId,
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+    transfer_only: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    send_processing_action(bot, chat_id).await;
+    let status = bot
+        .send_message(chat_id, format!("Listing IBC channels on {chain}..."))
+        .await?;
+
+    let Some(chain_info) = cache.get_chain(chain).await? else {
+        edit_status_message(bot, chat_id, status.id, "Chain not found".to_string(), None).await?;
+        return Ok(());
+    };
+
+    match query_ibc_channels_grpc_first(&chain_info.apis.grpc).await {
+        Ok(mut channels) => {
+            cache.store_ibc_channels(chain, channels.clone());
+            let chain_names = resolve_chain_names(
+                cache,
+                channels
+                    .iter()
+                    .filter(|channel| !transfer_only || channel.is_open_transfer())
+                    .filter_map(|channel| channel.counterparty_chain_id.clone())
+                    .collect(),
+            )
+            .await;
+            for channel in &mut channels {
+                channel.counterparty_chain_name = channel
+                    .counterparty_chain_id
+                    .as_ref()
+                    .and_then(|chain_id| chain_names.get(chain_id))
+                    .cloned();
+            }
+
+            edit_or_send_markdown_result(
+                bot,
+                chat_id,
+                status.id,
+                format_ibc_channels(chain, &channels, transfer_only),
+            )
+            .await?;
+        }
+        Err(e) => {
+            edit_status_message(
+                bot,
+                chat_id,
+                status.id,
+                format!("❌ Could not list IBC channels:\n{e}"),
+                None,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Channel ends queried in parallel by the registry consistency check.
+const CONSISTENCY_CHECK_CONCURRENCY: usize = 8;
+
+async fn send_registry_check(
+    bot: &Bot,
+    chat_id: ChatId,
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    send_processing_action(bot, chat_id).await;
+    let status = bot
+        .send_message(
+            chat_id,
+            format!("Checking registry IBC entries for {chain} against both chains..."),
+        )
+        .await?;
+
+    match check_registry_channels(cache, chain).await {
+        Ok(report) => {
+            let chunks = split_telegram_message(&format_consistency_report(chain, &report), 3800);
+            let mut chunks = chunks.into_iter();
+            if let Some(first) = chunks.next() {
+                edit_status_message(bot, chat_id, status.id, first, None).await?;
+            }
+            for chunk in chunks {
+                bot.send_message(chat_id, chunk).await?;
+            }
+        }
+        Err(e) => {
+            edit_status_message(
+                bot,
+                chat_id,
+                status.id,
+                format!("❌ Could not check registry IBC entries:\n{e}"),
+                None,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Walks every `_IBC` file for `chain` and queries each listed channel from both ends.
+async fn check_registry_channels(
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+) -> anyhow::Result<ConsistencyReport> {
+    let graph = cache.ibc_graph().await?;
+    let counterparties = graph.neighbours(chain);
+    if counterparties.is_empty() {
+        anyhow::bail!("{chain} has no IBC paths in the chain registry");
+    }
+
+    let chains: HashMap<String, ChainInfo> =
+        stream::iter(std::iter::once(chain.to_string()).chain(counterparties.iter().cloned()))
+            .map(|name| async move {
+                match cache.get_chain(&name).await {
+                    Ok(chain_info) => chain_info.map(|chain_info| (name, chain_info)),
                     Err(e) => {
-                        let error_message = format!(
-                            "❌ Could not resolve IBC denom:\n{}\n\nThis denom might not exist on {} or the chain's APIs might be unavailable.",
-                            e, chain
-                        );
-                        edit_status_message(&bot, msg.chat.id, status.id, error_message, None)
-                            .await?;
+                        log::warn!("Could not fetch chain info for {}: {}", name, e);
+                        None
                     }
                 }
-            } else {
-                edit_status_message(
-                    &bot,
-                    msg.chat.id,
-                    status.id,
-                    "Chain not found".to_string(),
-                    None,
+            })
+            .buffer_unordered(ROUTE_PATH_FETCH_CONCURRENCY)
+            .filter_map(|resolved| async move { resolved })
+            .collect()
+            .await;
+    let chain_ids: HashMap<String, String> = chains
+        .iter()
+        .map(|(name, chain_info)| (name.clone(), chain_info.chain_id.clone()))
+        .collect();
+    let paths: Vec<(String, Option<IBCPath>)> = stream::iter(counterparties)
+        .map(|counterparty| async move {
+            let path = cache
+                .get_ibc_path(chain, &counterparty)
+                .await
+                .unwrap_or_else(|e| {
+                    log::warn!("Could not fetch IBC path {}-{}: {}", chain, counterparty, e);
+                    None
+                });
+            (counterparty, path)
+        })
+        .buffer_unordered(ROUTE_PATH_FETCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut report = ConsistencyReport::default();
+    let mut pairs = Vec::new();
+    for (counterparty, path) in paths {
+        match path {
+            Some(path) => pairs.extend(RegistryChannelEnd::pairs_from_path(
+                &path, chain, &chain_ids,
+            )),
+            None => report.unreadable_paths.push(counterparty),
+        }
+    }
+    report.unreadable_paths.sort();
+
+    let chains = &chains;
+    report.checks = stream::iter(pairs)
+        .map(|(local, remote)| async move {
+            let mut issues = Vec::new();
+            for (expected, counterparty) in [(&local, &remote), (&remote, &local)] {
+                let Some(chain_info) = chains.get(&expected.chain_name) else {
+                    issues.push(format!(
+                        "{}: not in the chain registry",
+                        expected.chain_name
+                    ));
+                    continue;
+                };
+                match query_ibc_channel_info_grpc_first(
+                    &chain_info.apis.grpc,
+                    &expected.channel_id,
+                    &expected.port_id,
                 )
-                .await?;
+                .await
+                {
+                    Ok(info) => {
+                        issues.extend(channel_end_mismatches(expected, counterparty, &info))
+                    }
+                    Err(e) => issues.push(format!(
+                        "{}: could not query {}/{}: {e}",
+                        expected.chain_name, expected.port_id, expected.channel_id
+                    )),
+                }
+            }
+            ChannelConsistency {
+                local,
+                remote,
+                issues,
+            }
+        })
+        .buffer_unordered(CONSISTENCY_CHECK_CONCURRENCY)
+        .collect()
+        .await;
+    report.checks.sort_by(|a, b| {
+        (&a.remote.chain_name, &a.local.channel_id)
+            .cmp(&(&b.remote.chain_name, &b.local.channel_id))
+    });
+
+    Ok(report)
+}
+
+async fn send_fee_card(
+    bot: &Bot,
+    chat_id: ChatId,
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    send_processing_action(bot, chat_id).await;
+    let status = bot
+        .send_message(chat_id, format!("Fetching fee info on {chain}..."))
+        .await?;
+
+    if let Some(chain_info) = cache.get_chain(chain).await? {
+        let assets = match cache.get_assets(chain).await {
+            Ok(assets) => assets,
+            Err(e) => {
+                log::warn!("Could not fetch asset metadata for {}: {}", chain, e);
+                None
             }
+        };
+        let fee_denom = chain_info
+            .fees
+            .fee_tokens
+            .first()
+            .map(|token| token.denom.as_str());
+        let base_fee = query_live_base_fee_grpc_first(&chain_info.apis.grpc, fee_denom).await;
+
+        let message = format_fee_card(
+            chain,
+            &chain_info.fees.fee_tokens,
+            assets.as_ref(),
+            base_fee.as_ref(),
+        );
+        edit_or_send_markdown_result(bot, chat_id, status.id, message).await?;
+    } else {
+        edit_status_message(bot, chat_id, status.id, "Chain not found".to_string(), None).await?;
+    }
+
+    Ok(())
+}
+
+async fn send_software_card(
+    bot: &Bot,
+    chat_id: ChatId,
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    send_processing_action(bot, chat_id).await;
+    let status = bot
+        .send_message(chat_id, format!("Fetching software info for {chain}..."))
+        .await?;
+
+    match cache.get_chain_json(chain).await {
+        Ok(Some(chain_json)) => {
+            let software = parse_chain_software(&chain_json);
+            edit_or_send_markdown_result(
+                bot,
+                chat_id,
+                status.id,
+                format_software_card(chain, &software),
+            )
+            .await?;
+        }
+        Ok(None) => {
+            edit_status_message(bot, chat_id, status.id, "Chain not found".to_string(), None)
+                .await?;
+        }
+        Err(e) => {
+            edit_status_message(
+                bot,
+                chat_id,
+                status.id,
+                format!("❌ Could not fetch software info:\n{e}"),
+                None,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn handle_ibc_denom(
+    bot: Bot,
+    dialogue: MyDialogue,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    (chain, _message_id): (String, Option<teloxide::types::MessageId>),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(text) = msg.text() {
+        if let Some(ibc_hash) = text.strip_prefix("ibc/") {
+            send_ibc_denom_lookup(&bot, msg.chat.id, &cache, &chain, ibc_hash).await?;
         } else {
             bot.send_message(
                 msg.chat.id,
@@ -1118,10 +1920,1801 @@ pub async fn handle_ibc_denom(
             })
             .await?;
     }
-    Ok(())
+    Ok(())
+}
+
+pub async fn handle_asset_denom(
+    bot: Bot,
+    dialogue: MyDialogue,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    (chain, _message_id): (String, Option<teloxide::types::MessageId>),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(text) = msg.text() {
+        let parts: Vec<&str> = text.split_whitespace().collect();
+        match parts.as_slice() {
+            [full_path] if !split_trace_path(full_path).0.is_empty() => {
+                bot.send_message(msg.chat.id, format_raw_denom_hash(full_path))
+                    .await?;
+            }
+            [asset, destination] => {
+                send_processing_action(&bot, msg.chat.id).await;
+                let status = bot
+                    .send_message(
+                        msg.chat.id,
+                        format!("Computing {asset} denom on {destination}..."),
+                    )
+                    .await?;
+                let message =
+                    match resolve_asset_denom(&cache, &chain, asset, &destination.to_lowercase())
+                        .await
+                    {
+                        Ok(lookup) => format_asset_denom_lookup(&lookup),
+                        Err(e) => format!("❌ Could not compute the denom:\n{e}"),
+                    };
+                edit_status_message(&bot, msg.chat.id, status.id, message, None).await?;
+            }
+            _ => {
+                bot.send_message(
+                    msg.chat.id,
+                    "Please enter an asset and a destination chain (e.g., ATOM osmosis), or a full trace path (e.g., transfer/channel-0/uatom)",
+                )
+                .await?;
+            }
+        }
+
+        let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+        dialogue
+            .update(State::ChainSelected {
+                chain: chain.clone(),
+                message_id: Some(new_menu_id),
+            })
+            .await?;
+    } else {
+        dialogue
+            .update(State::ChainSelected {
+                chain,
+                message_id: None,
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+/// Works out the denom `asset` from `source_chain` gets on `destination_chain` over the
+/// registry's canonical channel, then checks the destination has seen it.
+async fn resolve_asset_denom(
+    cache: &Arc<RegistryCache>,
+    source_chain: &str,
+    asset: &str,
+    destination_chain: &str,
+) -> anyhow::Result<AssetDenomLookup> {
+    let source_info = cache
+        .get_chain(source_chain)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Chain not found"))?;
+    let (source_denom, asset_label) = match cache.get_assets(source_chain).await? {
+        Some(assets) => match find_registry_asset(&assets, asset) {
+            Some(found) => (found.base.clone(), Some(found.symbol.clone())),
+            None => (asset.to_string(), None),
+        },
+        None => (asset.to_string(), None),
+    };
+
+    let path = cache
+        .get_ibc_path(source_chain, destination_chain)
+        .await?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "The chain registry has no IBC path between {} and {}",
+                source_chain,
+                destination_chain
+            )
+        })?;
+    let channel = canonical_transfer_channel(&path, source_chain).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No active transfer channel between {} and {} in the chain registry",
+            source_chain,
+            destination_chain
+        )
+    })?;
+
+    let source_trace = match source_denom.strip_prefix("ibc/") {
+        Some(hash) => Some(query_ibc_denom_grpc_first(&source_info.apis.grpc, hash).await?),
+        None => None,
+    };
+
+    let mut lookup = AssetDenomLookup::new(
+        source_chain,
+        destination_chain,
+        asset_label,
+        &source_denom,
+        channel,
+        source_trace.as_ref(),
+    );
+    lookup.verification = match cache.get_chain(destination_chain).await {
+        Ok(Some(destination_info)) => {
+            verify_destination_denom(&destination_info.apis.grpc, &lookup.destination_denom).await
+        }
+        _ => DenomVerification::Unchecked(format!("{destination_chain} is not in the registry")),
+    };
+    Ok(lookup)
+}
+
+/// The chain across `chain`'s `port_id/channel_id` according to the registry `_IBC` files,
+/// without querying the chain.
+async fn registry_counterparty(
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+    port_id: &str,
+    channel_id: &str,
+) -> Option<String> {
+    match cache.channel_counterparties(chain).await {
+        Ok(index) => index
+            .get(&(port_id.to_string(), channel_id.to_string()))
+            .cloned(),
+        Err(e) => {
+            log::warn!("Could not index registry channels for {}: {}", chain, e);
+            None
+        }
+    }
+}
+
+/// Follows a voucher's trace hop by hop, naming each counterparty from the registry `_IBC`
+/// paths and falling back to a live channel query for channels the registry does not list.
+async fn walk_denom_route(
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+    hops: Vec<IbcDenomHop>,
+    base_denom: &str,
+) -> DenomRoute {
+    let total_hops = hops.len();
+    let mut route = DenomRoute {
+        chain: chain.to_string(),
+        base_denom: base_denom.to_string(),
+        hops: Vec::new(),
+        unresolved_hops: 0,
+    };
+    let mut current_chain = Some(chain.to_string());
+
+    for IbcDenomHop {
+        port_id,
+        channel_id,
+    } in hops
+    {
+        let Some(chain_name) = current_chain.take() else {
+            break;
+        };
+        let chain_info = match cache.get_chain(&chain_name).await {
+            Ok(Some(chain_info)) => chain_info,
+            _ => break,
+        };
+
+        let mut hop = DenomRouteHop {
+            chain: chain_name,
+            port_id,
+            channel_id,
+            counterparty_chain_id: None,
+            counterparty_chain_name: None,
+        };
+        if let Some(counterparty) =
+            registry_counterparty(cache, &hop.chain, &hop.port_id, &hop.channel_id).await
+        {
+            hop.counterparty_chain_id = match cache.get_chain(&counterparty).await {
+                Ok(Some(info)) if !info.chain_id.is_empty() => Some(info.chain_id),
+                _ => None,
+            };
+            hop.counterparty_chain_name = Some(counterparty);
+            current_chain = hop.counterparty_chain_name.clone();
+            route.hops.push(hop);
+            continue;
+        }
+
+        // Not in the registry: ask the chain which client the channel's connection uses.
+        match query_ibc_channel_info_grpc_first(
+            &chain_info.apis.grpc,
+            &hop.channel_id,
+            &hop.port_id,
+        )
+        .await
+        {
+            Ok(info) => {
+                if let Some(chain_id) = &info.counterparty_chain_id {
+                    hop.counterparty_chain_name = cache.chain_name_for_chain_id(chain_id).await;
+                }
+                hop.counterparty_chain_id = info.counterparty_chain_id;
+                current_chain = hop.counterparty_chain_name.clone();
+            }
+            Err(e) => {
+                log::warn!(
+                    "Could not follow {}/{} on {}: {}",
+                    hop.port_id,
+                    hop.channel_id,
+                    hop.chain,
+                    e
+                );
+            }
+        }
+        let resolved = current_chain.is_some();
+        route.hops.push(hop);
+        if !resolved {
+            break;
+        }
+    }
+
+    route.unresolved_hops = total_hops - route.hops.len();
+    route
+}
+
+async fn send_ibc_denom_lookup(
+    bot: &Bot,
+    chat_id: ChatId,
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+    ibc_hash: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    send_processing_action(bot, chat_id).await;
+    let status = bot
+        .send_message(chat_id, format!("Resolving IBC denom on {chain}..."))
+        .await?;
+
+    let Some(chain_info) = cache.get_chain(chain).await? else {
+        edit_status_message(bot, chat_id, status.id, "Chain not found".to_string(), None).await?;
+        return Ok(());
+    };
+
+    match query_ibc_denom_grpc_first(&chain_info.apis.grpc, ibc_hash).await {
+        Ok(trace) => {
+            let mut message = format!(
+                "✅ IBC Denom Trace Found:\n\nBase Denomination: {}",
+                trace.base_denom
+            );
+
+            if !trace.hops.is_empty() {
+                message.push_str(&format!("\nPath: {}\nHops:", trace.path()));
+                for (index, hop) in trace.hops.iter().enumerate() {
+                    message.push_str(&format!(
+                        "\n{}. {}/{}",
+                        index + 1,
+                        hop.port_id,
+                        hop.channel_id
+                    ));
+                }
+
+                let route =
+                    walk_denom_route(cache, chain, trace.hops.clone(), &trace.base_denom).await;
+                let check = match cache.get_assetlist_json(chain).await {
+                    Ok(Some(assetlist)) => Some(check_registry_trace(
+                        &assetlist,
+                        &trace.full_path(),
+                        route.origin_chain(),
+                    )),
+                    Ok(None) => None,
+                    Err(e) => {
+                        log::warn!("Could not fetch asset list for {}: {}", chain, e);
+                        None
+                    }
+                };
+                message.push_str(&format!(
+                    "\n\n{}",
+                    format_denom_route(&route, check.as_ref())
+                ));
+            }
+
+            edit_status_message(bot, chat_id, status.id, message, None).await?;
+        }
+        Err(e) => {
+            let error_message = format!(
+                "❌ Could not resolve IBC denom:\n{}\n\nThis denom might not exist on {} or the chain's APIs might be unavailable.",
+                e, chain
+            );
+            edit_status_message(bot, chat_id, status.id, error_message, None).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Origin chains walked per balance page for IBC assets this chain's asset list lacks.
+const MAX_ORIGIN_UNIT_LOOKUPS: usize = 8;
+/// Registry candidates confirmed on-chain per global lookup; the rest are listed unconfirmed.
+const MAX_GLOBAL_DENOM_CHECKS: usize = 12;
+
+/// Finds every chain whose registry asset list carries `ibc_denom`, then asks the best
+/// candidates for the trace in parallel.
+async fn send_global_ibc_denom_lookup(
+    bot: &Bot,
+    chat_id: ChatId,
+    cache: &Arc<RegistryCache>,
+    ibc_denom: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let hash = ibc_denom[4..].to_uppercase();
+    let ibc_denom = format!("ibc/{hash}");
+
+    send_processing_action(bot, chat_id).await;
+    let status = bot
+        .send_message(
+            chat_id,
+            "No chain selected, searching every registry asset list for this denom...",
+        )
+        .await?;
+
+    let index = match cache.ibc_denom_index().await {
+        Ok(index) => index,
+        Err(e) => {
+            let message = format!("❌ Could not load registry asset lists:\n{e}");
+            edit_status_message(bot, chat_id, status.id, message, None).await?;
+            return Ok(());
+        }
+    };
+
+    let mut candidates = index.get(&ibc_denom).cloned().unwrap_or_default();
+    // Shortest recorded paths first: those chains are the likeliest to hold the denom
+    candidates.sort_by_key(|asset| {
+        (
+            asset
+                .path
+                .as_deref()
+                .map(|path| split_trace_path(path).0.len())
+                .unwrap_or(usize::MAX),
+            asset.chain.clone(),
+        )
+    });
+    // A chain may list the hash twice, with and without a trace; keep its shortest entry
+    let mut seen = HashSet::new();
+    candidates.retain(|asset| seen.insert(asset.chain.clone()));
+    let unchecked = candidates.split_off(candidates.len().min(MAX_GLOBAL_DENOM_CHECKS));
+
+    let mut matches: Vec<GlobalDenomMatch> = stream::iter(candidates)
+        .map(|asset| {
+            let hash = hash.clone();
+            async move {
+                let trace = match cache.get_chain(&asset.chain).await {
+                    Ok(Some(chain_info)) => {
+                        query_ibc_denom_grpc_first(&chain_info.apis.grpc, &hash)
+                            .await
+                            .map_err(|e| {
+                                log::warn!("Could not confirm {} on {}: {}", hash, asset.chain, e)
+                            })
+                            .ok()
+                    }
+                    _ => None,
+                };
+                GlobalDenomMatch { asset, trace }
+            }
+        })
+        .buffer_unordered(GLOBAL_DENOM_CHECK_CONCURRENCY)
+        .collect()
+        .await;
+    matches.sort_by(|a, b| a.asset.chain.cmp(&b.asset.chain));
+    matches.extend(
+        unchecked
+            .into_iter()
+            .map(|asset| GlobalDenomMatch { asset, trace: None }),
+    );
+
+    let message = format_global_denom_matches(&ibc_denom, &matches);
+    edit_status_message(bot, chat_id, status.id, message, None).await?;
+    Ok(())
+}
+
+pub async fn handle_ibc_channel(
+    bot: Bot,
+    dialogue: MyDialogue,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    (chain, _message_id): (String, Option<MessageId>),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(text) = msg.text() {
+        // Parse the input - could be "23", "channel-23", or "channel-23 transfer"
+        let parts: Vec<&str> = text.split_whitespace().collect();
+        let channel_input = parts.first().unwrap_or(&"");
+        let port_id = parts.get(1).unwrap_or(&"transfer");
+
+        let channel_id = format_channel_input(channel_input);
+
+        // Validate channel format
+        if !channel_id.starts_with("channel-") {
+            bot.send_message(
+                msg.chat.id,
+                "Please enter a valid channel (e.g., 0, channel-0)",
+            )
+            .await?;
+            let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+            dialogue
+                .update(State::ChainSelected {
+                    chain: chain.clone(),
+                    message_id: Some(new_menu_id),
+                })
+                .await?;
+            return Ok(());
+        }
+
+        send_processing_action(&bot, msg.chat.id).await;
+        let status = bot
+            .send_message(msg.chat.id, format!("Looking up IBC route on {chain}..."))
+            .await?;
+
+        if let Some(chain_info) = cache.get_chain(&chain).await? {
+            match query_ibc_channel_info_grpc_first(&chain_info.apis.grpc, &channel_id, port_id)
+                .await
+            {
+                Ok(info) => {
+                    let message = format!(
+                        "✅ IBC Route Information\n\n\
+                        Source Chain: {}\n\
+                        Destination Chain: {}\n\n\
+                        Channel Details:\n\
+                        • Channel: {}\n\
+                        • Port: {}\n\
+                        • State: {}\n\
+                        • Client ID: {}\n\
+                        • Connection: {}\n\n\
+                        Counterparty Details:\n\
+                        • Channel: {}\n\
+                        • Client ID: {}\n\
+                        • Connection: {}\n\n\
+                        Light Client ({}, {}):\n{}",
+                        info.chain_id,
+                        info.client_kind.counterparty_label(&info.chain_id),
+                        info.channel_id,
+                        port_id,
+                        info.channel_state,
+                        info.client_id,
+                        info.connection_id,
+                        info.counterparty_channel_id,
+                        info.counterparty_client_id,
+                        info.counterparty_connection_id,
+                        info.client_id,
+                        info.client_kind.client_type(),
+                        format_light_client_status(
+                            &info.client_status,
+                            chrono::Utc::now().timestamp()
+                        )
+                    );
+                    if *port_id == "transfer" {
+                        let keyboard =
+                            InlineKeyboardMarkup::new(vec![vec![InlineKeyboardButton::callback(
+                                "⏱ Rate Limits",
+                                format!("rate_limits:{}", info.channel_id),
+                            )]]);
+                        bot.edit_message_text(msg.chat.id, status.id, message)
+                            .reply_markup(keyboard)
+                            .await?;
+                    } else {
+                        edit_status_message(&bot, msg.chat.id, status.id, message, None).await?;
+                    }
+                }
+                Err(e) => {
+                    let error_message = format!(
+                        "❌ Could not fetch IBC route info:\n{}\n\n\
+                        Make sure the channel exists on {}.",
+                        e, chain
+                    );
+                    edit_status_message(&bot, msg.chat.id, status.id, error_message, None).await?;
+                }
+            }
+        } else {
+            edit_status_message(
+                &bot,
+                msg.chat.id,
+                status.id,
+                "Chain not found".to_string(),
+                None,
+            )
+            .await?;
+        }
+
+        // Show the menu after showing IBC route info
+        let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+        dialogue
+            .update(State::ChainSelected {
+                chain: chain.clone(),
+                message_id: Some(new_menu_id),
+            })
+            .await?;
+    } else {
+        dialogue
+            .update(State::ChainSelected {
+                chain,
+                message_id: None,
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+pub async fn handle_packet_channel(
+    bot: Bot,
+    dialogue: MyDialogue,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    (chain, _message_id): (String, Option<MessageId>),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(text) = msg.text() {
+        let parts: Vec<&str> = text.split_whitespace().collect();
+        let channel_id = format_channel_input(parts.first().unwrap_or(&""));
+        let port_id = parts.get(1).unwrap_or(&"transfer");
+
+        if !channel_id.starts_with("channel-") {
+            bot.send_message(
+                msg.chat.id,
+                "Please enter a valid channel (e.g., 0, channel-0)",
+            )
+            .await?;
+        } else {
+            send_processing_action(&bot, msg.chat.id).await;
+            let status = bot
+                .send_message(
+                    msg.chat.id,
+                    format!("Inspecting packets on {chain} {port_id}/{channel_id}..."),
+                )
+                .await?;
+
+            let message = match inspect_pending_packets(&cache, &chain, port_id, &channel_id).await
+            {
+                Ok(message) => message,
+                Err(e) => format!(
+                    "❌ Could not inspect pending packets:\n{}\n\n\
+                    Make sure the channel exists on {}.",
+                    e, chain
+                ),
+            };
+            edit_status_message(&bot, msg.chat.id, status.id, message, None).await?;
+        }
+
+        let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+        dialogue
+            .update(State::ChainSelected {
+                chain: chain.clone(),
+                message_id: Some(new_menu_id),
+            })
+            .await?;
+    } else {
+        dialogue
+            .update(State::ChainSelected {
+                chain,
+                message_id: None,
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+pub async fn handle_escrow_channel(
+    bot: Bot,
+    dialogue: MyDialogue,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    (chain, _message_id): (String, Option<MessageId>),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(text) = msg.text() {
+        let input = text.trim();
+        let channel_id = if input.eq_ignore_ascii_case("all") {
+            None
+        } else {
+            Some(format_channel_input(input))
+        };
+
+        if channel_id
+            .as_ref()
+            .is_some_and(|channel_id| !channel_id.starts_with("channel-"))
+        {
+            bot.send_message(
+                msg.chat.id,
+                "Please enter a valid channel (e.g., 0, channel-0) or \"all\"",
+            )
+            .await?;
+        } else {
+            send_processing_action(&bot, msg.chat.id).await;
+            let status = bot
+                .send_message(
+                    msg.chat.id,
+                    format!("Reading escrow balances on {chain}..."),
+                )
+                .await?;
+            let message = match inspect_escrow(&cache, &chain, channel_id.as_deref()).await {
+                Ok(message) => message,
+                Err(e) => format!("❌ Could not read escrow balances:\n{e}"),
+            };
+            let mut chunks = split_telegram_message(&message, 3800).into_iter();
+            if let Some(first) = chunks.next() {
+                edit_status_message(&bot, msg.chat.id, status.id, first, None).await?;
+            }
+            for chunk in chunks {
+                bot.send_message(msg.chat.id, chunk).await?;
+            }
+        }
+
+        let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+        dialogue
+            .update(State::ChainSelected {
+                chain: chain.clone(),
+                message_id: Some(new_menu_id),
+            })
+            .await?;
+    } else {
+        dialogue
+            .update(State::ChainSelected {
+                chain,
+                message_id: None,
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+/// Reads escrow for one transfer channel, or every open one when `channel_id` is `None`,
+/// naming counterparties and assets from the registry.
+async fn inspect_escrow(
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+    channel_id: Option<&str>,
+) -> anyhow::Result<String> {
+    let chain_info = cache
+        .get_chain(chain)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Chain not found"))?;
+
+    let channels: Vec<(String, Option<String>)> = match channel_id {
+        Some(channel_id) => {
+            let info =
+                query_ibc_channel_info_grpc_first(&chain_info.apis.grpc, channel_id, "transfer")
+                    .await?;
+            vec![(channel_id.to_string(), info.counterparty_chain_id)]
+        }
+        None => query_ibc_channels_grpc_first(&chain_info.apis.grpc)
+            .await?
+            .into_iter()
+            .filter(IbcChannelSummary::is_open_transfer)
+            .map(|channel| (channel.channel_id, channel.counterparty_chain_id))
+            .collect(),
+    };
+    let chain_names = resolve_chain_names(
+        cache,
+        channels
+            .iter()
+            .filter_map(|(_, chain_id)| chain_id.clone())
+            .collect(),
+    )
+    .await;
+    let channels: Vec<(String, String, Option<String>)> = channels
+        .into_iter()
+        .map(|(channel_id, chain_id)| {
+            let counterparty =
+                chain_id.map(|chain_id| chain_names.get(&chain_id).cloned().unwrap_or(chain_id));
+            ("transfer".to_string(), channel_id, counterparty)
+        })
+        .collect();
+
+    let report = query_channel_escrows_grpc_first(
+        &chain_info.apis.grpc,
+        &chain_info.bech32_prefix,
+        &channels,
+    )
+    .await?;
+
+    let assets = cache.get_assets(chain).await.unwrap_or_else(|e| {
+        log::warn!("Could not fetch asset metadata for {}: {}", chain, e);
+        None
+    });
+    let labels: HashMap<String, String> = report
+        .escrows
+        .iter()
+        .flat_map(|escrow| &escrow.balances)
+        .filter_map(|balance| {
+            asset_label_for_denom(assets.as_ref(), &balance.denom)
+                .map(|label| (balance.denom.clone(), label))
+        })
+        .collect();
+
+    Ok(format_escrow_report(
+        chain,
+        &report,
+        &labels,
+        &DenomUnits::from_assets(assets.as_ref()),
+    ))
+}
+
+pub async fn handle_memo_builder(
+    bot: Bot,
+    dialogue: MyDialogue,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    (chain, message_id, draft): (String, Option<MessageId>, MemoDraft),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Some(text) = msg.text() else {
+        dialogue
+            .update(State::ChainSelected {
+                chain,
+                message_id: None,
+            })
+            .await?;
+        return Ok(());
+    };
+
+    match advance_memo_draft(&cache, &chain, draft, text.trim()).await {
+        Ok(MemoProgress::Prompt(draft, prompt)) => {
+            bot.send_message(msg.chat.id, prompt).await?;
+            dialogue
+                .update(State::BuildingMemo {
+                    chain,
+                    message_id,
+                    draft,
+                })
+                .await?;
+            return Ok(());
+        }
+        Ok(MemoProgress::Done(message)) => {
+            for chunk in split_telegram_message(&message, 4000) {
+                bot.send_message(msg.chat.id, chunk).await?;
+            }
+        }
+        Err(e) => {
+            bot.send_message(msg.chat.id, format!("❌ {e}\nMemo builder cancelled."))
+                .await?;
+        }
+    }
+
+    let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+    dialogue
+        .update(State::ChainSelected {
+            chain: chain.clone(),
+            message_id: Some(new_menu_id),
+        })
+        .await?;
+    Ok(())
+}
+
+enum MemoProgress {
+    Prompt(MemoDraft, String),
+    Done(String),
+}
+
+/// Applies one answer to the draft and returns either the next prompt or the finished memo.
+/// Input that cannot be used re-asks the same step; registry failures end the dialogue.
+async fn advance_memo_draft(
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+    mut draft: MemoDraft,
+    input: &str,
+) -> anyhow::Result<MemoProgress> {
+    let chain_info = cache
+        .get_chain(chain)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Chain not found"))?;
+
+    match draft.step {
+        MemoStep::Route => {
+            let route: Vec<String> = std::iter::once(chain.to_string())
+                .chain(input.split_whitespace().map(|hop| hop.to_lowercase()))
+                .collect();
+            if route.len() < 2 {
+                return Ok(MemoProgress::Prompt(
+                    draft,
+                    "Enter at least the destination chain:".to_string(),
+                ));
+            }
+            if let Some(hub) = route[1..route.len() - 1]
+                .iter()
+                .find(|hub| !PFM_HUBS.contains(&hub.as_str()))
+            {
+                return Ok(MemoProgress::Prompt(
+                    draft,
+                    format!(
+                        "{hub} is not a known packet-forward hub ({}). Enter the route again:",
+                        PFM_HUBS.join(", ")
+                    ),
+                ));
+            }
+
+            let mut hops = Vec::new();
+            for pair in route.windows(2) {
+                let channel = cache
+                    .get_ibc_path(&pair[0], &pair[1])
+                    .await?
+                    .and_then(|path| canonical_transfer_channel(&path, &pair[0]));
+                let Some(channel) = channel else {
+                    return Ok(MemoProgress::Prompt(
+                        draft,
+                        format!(
+                            "The registry has no transfer channel from {} to {}. Enter the route again:",
+                            pair[0], pair[1]
+                        ),
+                    ));
+                };
+                hops.push(RouteHop {
+                    from: pair[0].clone(),
+                    to: pair[1].clone(),
+                    channel,
+                });
+            }
+            draft.hops = hops;
+            draft.step = MemoStep::Asset;
+            Ok(MemoProgress::Prompt(
+                draft,
+                format!("Enter amount and asset to send from {chain} (e.g., 1.5 ATOM, or 1500000 uatom in base units):"),
+            ))
+        }
+        MemoStep::Asset => {
+            let parts: Vec<&str> = input.split_whitespace().collect();
+            let [amount, asset] = parts.as_slice() else {
+                return Ok(MemoProgress::Prompt(
+                    draft,
+                    "Enter an amount followed by an asset (e.g., 1.5 ATOM):".to_string(),
+                ));
+            };
+            let assets = cache.get_assets(chain).await.unwrap_or_else(|e| {
+                log::warn!("Could not fetch asset metadata for {}: {}", chain, e);
+                None
+            });
+            let registry_asset = assets
+                .as_ref()
+                .and_then(|assets| find_registry_asset(assets, asset));
+            let (denom, base_amount) = match registry_asset {
+                Some(found) if found.base != *asset => {
+                    let exponent = display_unit_for_denom(assets.as_ref(), &found.base)
+                        .map(|(_, exponent)| exponent)
+                        .unwrap_or(0);
+                    (found.base.clone(), to_base_units(amount, exponent))
+                }
+                _ => (asset.to_string(), to_base_units(amount, 0)),
+            };
+            let Some(base_amount) = base_amount else {
+                return Ok(MemoProgress::Prompt(
+                    draft,
+                    format!(
+                        "{amount} is not a valid amount for {asset}. Enter amount and asset again:"
+                    ),
+                ));
+            };
+
+            draft.amount = base_amount;
+            draft.denom = denom;
+            draft.step = MemoStep::Receiver;
+            let destination = draft.destination().unwrap_or_default().to_string();
+            Ok(MemoProgress::Prompt(
+                draft,
+                format!("Enter the final receiver address on {destination}:"),
+            ))
+        }
+        MemoStep::Receiver => {
+            let destination = draft.destination().unwrap_or_default().to_string();
+            let prefix = cache
+                .get_chain(&destination)
+                .await?
+                .map(|info| info.bech32_prefix)
+                .unwrap_or_default();
+            if !prefix.is_empty() && !input.starts_with(&format!("{prefix}1")) {
+                return Ok(MemoProgress::Prompt(
+                    draft,
+                    format!("Enter an address starting with {prefix}1 for {destination}:"),
+                ));
+            }
+
+            draft.receiver = input.to_string();
+            if destination == "osmosis" {
+                draft.step = MemoStep::Swap;
+                return Ok(MemoProgress::Prompt(
+                    draft,
+                    "Optional swap on Osmosis: enter <output asset> [slippage %] (e.g., OSMO 2), or \"skip\":"
+                        .to_string(),
+                ));
+            }
+            Ok(MemoProgress::Done(format_forward_memo(
+                &chain_info,
+                &draft,
+                None,
+            )?))
+        }
+        MemoStep::Swap => {
+            if input.eq_ignore_ascii_case("skip") {
+                return Ok(MemoProgress::Done(format_forward_memo(
+                    &chain_info,
+                    &draft,
+                    None,
+                )?));
+            }
+            let parts: Vec<&str> = input.split_whitespace().collect();
+            let (output_asset, slippage) = match parts.as_slice() {
+                [output_asset] => (*output_asset, "5"),
+                [output_asset, slippage] => (*output_asset, *slippage),
+                _ => {
+                    return Ok(MemoProgress::Prompt(
+                        draft,
+                        "Enter <output asset> [slippage %], or \"skip\":".to_string(),
+                    ))
+                }
+            };
+            let slippage = slippage.trim_end_matches('%');
+            if slippage
+                .parse::<f64>()
+                .map_or(true, |slippage| !(0.0..=100.0).contains(&slippage))
+            {
+                return Ok(MemoProgress::Prompt(
+                    draft,
+                    "Slippage must be a percentage between 0 and 100. Enter the swap again, or \"skip\":"
+                        .to_string(),
+                ));
+            }
+            let osmosis_assets = cache.get_assets("osmosis").await.unwrap_or_else(|e| {
+                log::warn!("Could not fetch asset metadata for osmosis: {}", e);
+                None
+            });
+            let Some(output) = osmosis_assets
+                .as_ref()
+                .and_then(|assets| find_registry_asset(assets, output_asset))
+            else {
+                return Ok(MemoProgress::Prompt(
+                    draft,
+                    format!(
+                        "{output_asset} is not in the Osmosis asset list. Enter a listed symbol or denom, or \"skip\":"
+                    ),
+                ));
+            };
+
+            let swap = OsmosisSwap {
+                contract: OSMOSIS_CROSSCHAIN_SWAPS_CONTRACT.to_string(),
+                output_denom: output.base.clone(),
+                slippage_percent: slippage.to_string(),
+            };
+            Ok(MemoProgress::Done(format_forward_memo(
+                &chain_info,
+                &draft,
+                Some(&swap),
+            )?))
+        }
+    }
+}
+
+pub async fn handle_rate_limit(
+    bot: Bot,
+    dialogue: MyDialogue,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    (chain, _message_id): (String, Option<MessageId>),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(text) = msg.text() {
+        let parts: Vec<&str> = text.split_whitespace().collect();
+        let channel_id = format_channel_input(parts.first().unwrap_or(&""));
+
+        if !channel_id.starts_with("channel-") {
+            bot.send_message(
+                msg.chat.id,
+                "Please enter a valid channel (e.g., 0, channel-0), optionally followed by a denom",
+            )
+            .await?;
+        } else {
+            send_processing_action(&bot, msg.chat.id).await;
+            let status = bot
+                .send_message(msg.chat.id, format!("Checking rate limits on {chain}..."))
+                .await?;
+            let message =
+                match inspect_rate_limits(&cache, &chain, &channel_id, parts.get(1).copied()).await
+                {
+                    Ok(message) => message,
+                    Err(e) => format!("❌ Could not read rate limits:\n{e}"),
+                };
+            edit_status_message(&bot, msg.chat.id, status.id, message, None).await?;
+        }
+
+        let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+        dialogue
+            .update(State::ChainSelected {
+                chain: chain.clone(),
+                message_id: Some(new_menu_id),
+            })
+            .await?;
+    } else {
+        dialogue
+            .update(State::ChainSelected {
+                chain,
+                message_id: None,
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+/// Looks up rate limits on a channel; a registry symbol is accepted in place of the denom.
+async fn inspect_rate_limits(
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+    channel_id: &str,
+    denom_input: Option<&str>,
+) -> anyhow::Result<String> {
+    let chain_info = cache
+        .get_chain(chain)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Chain not found"))?;
+    let assets = cache.get_assets(chain).await.unwrap_or_else(|e| {
+        log::warn!("Could not fetch asset metadata for {}: {}", chain, e);
+        None
+    });
+
+    let denom = denom_input.map(|input| {
+        assets
+            .as_ref()
+            .and_then(|assets| find_registry_asset(assets, input))
+            .map(|asset| asset.base.clone())
+            .unwrap_or_else(|| input.to_string())
+    });
+    let lookup =
+        query_rate_limits_grpc_first(&chain_info.apis.grpc, channel_id, denom.as_deref()).await?;
+
+    let labels: HashMap<String, String> = match &lookup {
+        RateLimitLookup::Limits { limits, .. } => limits
+            .iter()
+            .filter_map(|limit| {
+                asset_label_for_denom(assets.as_ref(), &limit.denom)
+                    .map(|label| (limit.denom.clone(), label))
+            })
+            .collect(),
+        RateLimitLookup::NotSupported => HashMap::new(),
+    };
+
+    Ok(format_rate_limits(
+        chain,
+        channel_id,
+        denom.as_deref(),
+        &lookup,
+        &labels,
+        &DenomUnits::from_assets(assets.as_ref()),
+        chrono::Utc::now().timestamp(),
+    ))
+}
+
+/// Resolves both ends of the channel, then checks packets in each direction.
+async fn inspect_pending_packets(
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+    port_id: &str,
+    channel_id: &str,
+) -> anyhow::Result<String> {
+    let chain_info = cache
+        .get_chain(chain)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Chain not found"))?;
+    let channel_info =
+        query_ibc_channel_info_grpc_first(&chain_info.apis.grpc, channel_id, port_id).await?;
+    let local_endpoint = find_healthy_grpc_endpoint(&chain_info.apis.grpc).await;
+
+    let counterparty_name = match &channel_info.counterparty_chain_id {
+        Some(chain_id) => cache.chain_name_for_chain_id(chain_id).await,
+        None => None,
+    };
+    let counterparty_endpoint = match &counterparty_name {
+        Some(name) => match cache.get_chain(name).await {
+            Ok(Some(counterparty_info)) => {
+                find_healthy_grpc_endpoint(&counterparty_info.apis.grpc).await
+            }
+            _ => None,
+        },
+        None => None,
+    };
+    let counterparty_label = counterparty_name.unwrap_or_else(|| {
+        channel_info
+            .client_kind
+            .counterparty_label(&channel_info.chain_id)
+    });
+
+    let report = query_pending_packets_grpc(
+        &ChannelEnd {
+            chain,
+            grpc_endpoint: local_endpoint.as_deref(),
+            port_id,
+            channel_id,
+        },
+        &ChannelEnd {
+            chain: &counterparty_label,
+            grpc_endpoint: counterparty_endpoint.as_deref(),
+            port_id: &channel_info.counterparty_port_id,
+            channel_id: &channel_info.counterparty_channel_id,
+        },
+    )
+    .await?;
+
+    Ok(format_pending_packets(
+        chain,
+        &report,
+        chrono::Utc::now().timestamp(),
+    ))
+}
+
+/// Display units from the chain's asset list; empty when the list cannot be fetched.
+async fn chain_denom_units(cache: &RegistryCache, chain: &str) -> DenomUnits {
+    match cache.get_assets(chain).await {
+        Ok(assets) => DenomUnits::from_assets(assets.as_ref()),
+        Err(e) => {
+            log::warn!("Could not fetch asset metadata for {}: {}", chain, e);
+            DenomUnits::default()
+        }
+    }
+}
+
+/// Asset list entries for traced IBC denoms missing from this chain's asset list, read from
+/// the asset list of the chain each trace originates on.
+async fn origin_assets(
+    cache: &Arc<RegistryCache>,
+    chain: &str,
+    traces: Vec<(String, IbcDenomTrace)>,
+) -> HashMap<String, Asset> {
+    stream::iter(traces.into_iter().take(MAX_ORIGIN_UNIT_LOOKUPS))
+        .map(|(denom, trace)| async move {
+            let route = walk_denom_route(cache, chain, trace.hops, &trace.base_denom).await;
+            let origin = route.origin_chain()?;
+            let assets = cache.get_assets(origin).await.ok()??;
+            let asset = assets
+                .assets
+                .into_iter()
+                .find(|asset| asset.base == trace.base_denom)?;
+            Some((denom, asset))
+        })
+        .buffer_unordered(MAX_ORIGIN_UNIT_LOOKUPS)
+        .filter_map(|resolved| async move { resolved })
+        .collect()
+        .await
+}
+
+/// The asset list entry whose base denom is `denom`.
+fn listed_asset<'a>(assets: Option<&'a AssetList>, denom: &str) -> Option<&'a Asset> {
+    assets?.assets.iter().find(|asset| asset.base == denom)
+}
+
+fn asset_label_for_denom(assets: Option<&AssetList>, denom: &str) -> Option<String> {
+    let asset = assets?.assets.iter().find(|asset| {
+        asset.base == denom
+            || asset
+                .denom_units
+                .iter()
+                .any(|denom_unit| denom_unit.denom == denom)
+    })?;
+
+    [&asset.symbol, &asset.name, &asset.display]
+        .into_iter()
+        .find(|label| !label.is_empty())
+        .cloned()
+}
+
+/// Balances of the CW20 tokens in the chain's asset list; empty when it lists none or the
+/// queries fail, so bank balances still show.
+async fn cw20_balances(
+    cache: &RegistryCache,
+    chain: &str,
+    chain_info: &ChainInfo,
+    address: &str,
+) -> Vec<Balance> {
+    let tokens = match cache.get_assetlist_json(chain).await {
+        Ok(Some(assetlist)) => registry_cw20_tokens(&assetlist),
+        Ok(None) => return Vec::new(),
+        Err(e) => {
+            log::warn!("Could not fetch asset list for {}: {}", chain, e);
+            return Vec::new();
+        }
+    };
+    if tokens.is_empty() {
+        return Vec::new();
+    }
+
+    query_cw20_balances_grpc_first(&chain_info.apis.grpc, &tokens, address)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Could not fetch CW20 balances on {}: {}", chain, e);
+            Vec::new()
+        })
+}
+
+/// Shows one page of balances on the status message, with Prev/More buttons backed by
+/// cursors in the cache and a button to summarize every page.
+async fn send_balance_page(
+    bot: &Bot,
+    chat_id: ChatId,
+    status_id: MessageId,
+    cache: &Arc<RegistryCache>,
+    cursor: BalanceCursor,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let chain = cursor.chain.as_str();
+    let address = cursor.address.as_str();
+    let Some(chain_info) = cache.get_chain(chain).await? else {
+        edit_status_message(bot, chat_id, status_id, "Chain not found".to_string(), None).await?;
+        return Ok(());
+    };
+    let assets_data = match cache.get_assets(chain).await {
+        Ok(assets) => assets,
+        Err(e) => {
+            log::warn!("Could not fetch asset metadata for {}: {}", chain, e);
+            None
+        }
+    };
+
+    // CW20 tokens are not paged with bank balances, so they all go on the first page
+    let cw20_balances = if cursor.page == 0 {
+        cw20_balances(cache, chain, &chain_info, address).await
+    } else {
+        Vec::new()
+    };
+
+    match query_balances_grpc_first(&chain_info.apis.grpc, address, cursor.key.as_deref())
+        .await
+        .map(|(mut balances, next_key)| {
+            balances.extend(cw20_balances);
+            (balances, next_key)
+        }) {
+        Ok((balances, _)) if balances.is_empty() && cursor.page == 0 => {
+            let message = format!(
+                "No balances found for address:\n`{}`\n\n\
+                The address might be empty or invalid for {}\\.",
+                escape_markdown(address),
+                escape_markdown(chain)
+            );
+            edit_or_send_markdown_result(bot, chat_id, status_id, message).await?;
+        }
+        Ok((balances, next_key)) => {
+            let mut wallet_balances = Vec::with_capacity(balances.len());
+            let mut listed_assets = HashMap::new();
+            for balance in balances {
+                let ibc_trace = if let Some(ibc_hash) = balance.denom.strip_prefix("ibc/") {
+                    match query_ibc_denom_grpc_first(&chain_info.apis.grpc, ibc_hash).await {
+                        Ok(trace) => Some(trace),
+                        Err(e) => {
+                            log::warn!("Could not resolve IBC denom {}: {}", balance.denom, e);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                let asset_label = asset_label_for_denom(assets_data.as_ref(), &balance.denom);
+                if let Some(asset) = listed_asset(assets_data.as_ref(), &balance.denom) {
+                    listed_assets.insert(balance.denom.clone(), asset.clone());
+                }
+
+                wallet_balances.push(WalletBalance {
+                    balance,
+                    ibc_trace,
+                    asset_label,
+                    display: None,
+                    usd_price: None,
+                });
+            }
+
+            let unresolved: Vec<(String, IbcDenomTrace)> = wallet_balances
+                .iter()
+                .filter(|wallet_balance| !listed_assets.contains_key(&wallet_balance.balance.denom))
+                .filter_map(|wallet_balance| {
+                    Some((
+                        wallet_balance.balance.denom.clone(),
+                        wallet_balance.ibc_trace.clone()?,
+                    ))
+                })
+                .collect();
+            listed_assets.extend(origin_assets(cache, chain, unresolved).await);
+
+            let price_queries: Vec<PriceQuery> = listed_assets
+                .iter()
+                .filter_map(|(denom, asset)| PriceQuery::from_asset(denom, asset))
+                .collect();
+            let prices = cache.usd_prices(chain, &price_queries).await;
+            for wallet_balance in &mut wallet_balances {
+                let denom = &wallet_balance.balance.denom;
+                wallet_balance.display = listed_assets.get(denom).and_then(asset_display_unit);
+                wallet_balance.usd_price = prices.get(denom).copied();
+            }
+
+            let message = format_wallet_balances(
+                address,
+                chain,
+                &wallet_balances,
+                cursor.page,
+                next_key.is_some(),
+            );
+            let keyboard = balance_page_keyboard(cache, &cursor, next_key);
+            edit_or_send_markdown_result_with_keyboard(bot, chat_id, status_id, message, keyboard)
+                .await?;
+        }
+        Err(e) => {
+            let error_message = format!(
+                "❌ Could not fetch balances:\n{}\n\n\
+                The address might be invalid or the chain's APIs might be unavailable\\.",
+                escape_markdown(&e.to_string())
+            );
+            edit_or_send_markdown_result(bot, chat_id, status_id, error_message).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Buttons for a balance page; single-page results get none.
+fn balance_page_keyboard(
+    cache: &RegistryCache,
+    cursor: &BalanceCursor,
+    next_key: Option<String>,
+) -> Option<InlineKeyboardMarkup> {
+    if cursor.page == 0 && next_key.is_none() {
+        return None;
+    }
+
+    let token = cache.store_balance_cursor(cursor.clone());
+    let mut navigation = Vec::new();
+    if let Some(previous) = &cursor.previous {
+        navigation.push(InlineKeyboardButton::callback(
+            "◀ Prev",
+            format!("balances:page:{previous}"),
+        ));
+    }
+    if let Some(next_key) = next_key {
+        let next = cache.store_balance_cursor(BalanceCursor {
+            key: Some(next_key),
+            page: cursor.page + 1,
+            previous: Some(token.clone()),
+            ..cursor.clone()
+        });
+        navigation.push(InlineKeyboardButton::callback(
+            "More ▶",
+            format!("balances:page:{next}"),
+        ));
+    }
+
+    Some(InlineKeyboardMarkup::new(vec![
+        navigation,
+        vec![InlineKeyboardButton::callback(
+            "Summarize all pages",
+            format!("balances:all:{token}"),
+        )],
+    ]))
+}
+
+/// Walks every balance page for the cursor's address and summarizes them.
+async fn send_balance_summary(
+    bot: &Bot,
+    chat_id: ChatId,
+    status_id: MessageId,
+    cache: &Arc<RegistryCache>,
+    cursor: &BalanceCursor,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let chain = cursor.chain.as_str();
+    let Some(chain_info) = cache.get_chain(chain).await? else {
+        edit_status_message(bot, chat_id, status_id, "Chain not found".to_string(), None).await?;
+        return Ok(());
+    };
+
+    let mut balances = Vec::new();
+    let mut key = None;
+    let mut complete = false;
+    for _ in 0..MAX_BALANCE_PAGES {
+        match query_balances_grpc_first(&chain_info.apis.grpc, &cursor.address, key.as_deref())
+            .await
+        {
+            Ok((page, next_key)) => {
+                balances.extend(page);
+                key = next_key;
+                if key.is_none() {
+                    complete = true;
+                    break;
+                }
+            }
+            Err(e) => {
+                edit_status_message(
+                    bot,
+                    chat_id,
+                    status_id,
+                    format!(
+                        "❌ Could not fetch balances after {} denoms:\n{e}",
+                        balances.len()
+                    ),
+                    None,
+                )
+                .await?;
+                return Ok(());
+            }
+        }
+    }
+
+    let assets = cache.get_assets(chain).await.unwrap_or_else(|e| {
+        log::warn!("Could not fetch asset metadata for {}: {}", chain, e);
+        None
+    });
+    let labels: HashMap<String, String> = balances
+        .iter()
+        .filter_map(|balance| {
+            asset_label_for_denom(assets.as_ref(), &balance.denom)
+                .map(|label| (balance.denom.clone(), label))
+        })
+        .collect();
+    let price_queries: Vec<PriceQuery> = balances
+        .iter()
+        .filter_map(|balance| {
+            PriceQuery::from_asset(
+                &balance.denom,
+                listed_asset(assets.as_ref(), &balance.denom)?,
+            )
+        })
+        .collect();
+    let prices = cache.usd_prices(chain, &price_queries).await;
+
+    edit_or_send_markdown_result(
+        bot,
+        chat_id,
+        status_id,
+        format_balance_summary(
+            &cursor.address,
+            chain,
+            &balances,
+            &labels,
+            &DenomUnits::from_assets(assets.as_ref()),
+            &prices,
+            complete,
+        ),
+    )
+    .await
+}
+
+/// Chains with an EVM JSON-RPC endpoint or the Ethereum coin type take `0x` addresses.
+fn is_evm_chain(chain_info: &ChainInfo) -> bool {
+    !chain_info.apis.evm_http_jsonrpc.is_empty() || chain_info.slip44 == ETHERMINT_SLIP44
+}
+
+/// Maximum "Check on <chain>" buttons offered for an address from another chain.
+const MAX_SWITCH_CHAIN_BUTTONS: usize = 3;
+
+/// Explains that the address belongs elsewhere, with buttons that switch to each registry
+/// chain using its prefix and check the balance, or the validator for `valoper` addresses.
+async fn send_other_chain_address(
+    bot: &Bot,
+    chat_id: ChatId,
+    cache: &RegistryCache,
+    chain: &str,
+    address: &str,
+    prefix: &str,
+    kind: AddressKind,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let owners: Vec<String> = match cache.chain_prefixes().await {
+        Ok(prefixes) => prefixes
+            .iter()
+            .filter(|owner| owner.bech32_prefix == prefix)
+            .map(|owner| owner.chain_name.clone())
+            .collect(),
+        Err(e) => {
+            log::warn!("Could not load registry prefixes: {}", e);
+            Vec::new()
+        }
+    };
+
+    if owners.is_empty() {
+        bot.send_message(
+            chat_id,
+            format!("❌ The prefix {prefix} is not used by {chain} or any other registry chain."),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let buttons: Vec<Vec<InlineKeyboardButton>> = owners
+        .iter()
+        .take(MAX_SWITCH_CHAIN_BUTTONS)
+        .map(|owner| {
+            let token = cache.store_chain_switch(ChainSwitch {
+                chain: owner.clone(),
+                address: address.to_lowercase(),
+            });
+            let data = match kind {
+                AddressKind::Validator => format!("validator:{token}"),
+                _ => format!("balances:switch:{token}"),
+            };
+            vec![InlineKeyboardButton::callback(
+                format!("Check on {owner}"),
+                data,
+            )]
+        })
+        .collect();
+    bot.send_message(
+        chat_id,
+        format!(
+            "⚠️ This is a {} {}address, not {chain}. Switch chains to check it there, or use /address to convert it.",
+            owners.join(" / "),
+            if kind == AddressKind::Validator { "validator " } else { "" }
+        ),
+    )
+    .reply_markup(InlineKeyboardMarkup::new(buttons))
+    .await?;
+    Ok(())
+}
+
+async fn send_validator_info(
+    bot: &Bot,
+    chat_id: ChatId,
+    status_id: MessageId,
+    cache: &RegistryCache,
+    chain: &str,
+    operator_address: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Some(chain_info) = cache.get_chain(chain).await? else {
+        edit_status_message(bot, chat_id, status_id, "Chain not found".to_string(), None).await?;
+        return Ok(());
+    };
+
+    match query_validator_grpc_first(&chain_info.apis.grpc, operator_address).await {
+        Ok(validator) => {
+            let units = chain_denom_units(cache, chain).await;
+            let message = format_validator_info(chain, &validator, &units);
+            edit_or_send_markdown_result(bot, chat_id, status_id, message).await?;
+        }
+        Err(e) => {
+            edit_status_message(
+                bot,
+                chat_id,
+                status_id,
+                format!("❌ Could not fetch validator info:\n{e}"),
+                None,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_wallet_address(
+    bot: Bot,
+    dialogue: MyDialogue,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    (chain, _message_id): (String, Option<MessageId>),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(text) = msg.text() {
+        let check = match cache.get_chain(&chain).await? {
+            Some(chain_info) => check_wallet_address(
+                text,
+                &chain,
+                &chain_info.bech32_prefix,
+                is_evm_chain(&chain_info),
+            ),
+            None => WalletAddressCheck::Invalid(format!("{chain} is not in the chain registry")),
+        };
+
+        match check {
+            WalletAddressCheck::Account(address) => {
+                send_processing_action(&bot, msg.chat.id).await;
+                let status = bot
+                    .send_message(msg.chat.id, format!("Checking balances on {chain}..."))
+                    .await?;
+                let cursor = BalanceCursor {
+                    chain: chain.clone(),
+                    address,
+                    key: None,
+                    page: 0,
+                    previous: None,
+                };
+                send_balance_page(&bot, msg.chat.id, status.id, &cache, cursor).await?;
+            }
+            WalletAddressCheck::Validator(operator_address) => {
+                send_processing_action(&bot, msg.chat.id).await;
+                let status = bot
+                    .send_message(
+                        msg.chat.id,
+                        format!("That is a validator address; looking it up on {chain}..."),
+                    )
+                    .await?;
+                send_validator_info(
+                    &bot,
+                    msg.chat.id,
+                    status.id,
+                    &cache,
+                    &chain,
+                    &operator_address,
+                )
+                .await?;
+            }
+            WalletAddressCheck::OtherPrefix { prefix, kind } => {
+                send_other_chain_address(
+                    &bot,
+                    msg.chat.id,
+                    &cache,
+                    &chain,
+                    text.trim(),
+                    &prefix,
+                    kind,
+                )
+                .await?;
+            }
+            WalletAddressCheck::Invalid(reason) => {
+                bot.send_message(msg.chat.id, format!("❌ Invalid address: {reason}"))
+                    .await?;
+            }
+        }
+
+        // Show the menu after showing balance info
+        let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+        dialogue
+            .update(State::ChainSelected {
+                chain: chain.clone(),
+                message_id: Some(new_menu_id),
+            })
+            .await?;
+    } else {
+        dialogue
+            .update(State::ChainSelected {
+                chain,
+                message_id: None,
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+/// Chains whose balances are queried at once during a portfolio scan.
+const PORTFOLIO_SCAN_CONCURRENCY: usize = 8;
+/// Balance pages read per chain; large accounts are flagged as incomplete instead.
+const MAX_PORTFOLIO_PAGES: usize = 3;
+
+pub async fn handle_portfolio_address(
+    bot: Bot,
+    dialogue: MyDialogue,
+    cache: Arc<RegistryCache>,
+    msg: Message,
+    (chain, _message_id): (String, Option<MessageId>),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(text) = msg.text() {
+        let address = text.trim();
+        send_processing_action(&bot, msg.chat.id).await;
+        let status = bot
+            .send_message(
+                msg.chat.id,
+                "Scanning balances on every chain with the same coin type...",
+            )
+            .await?;
+        match scan_portfolio(&cache, address).await {
+            Ok(scan) => {
+                let message = format_portfolio_scan(address, &scan);
+                edit_or_send_markdown_result(&bot, msg.chat.id, status.id, message).await?;
+            }
+            Err(e) => {
+                edit_status_message(
+                    &bot,
+                    msg.chat.id,
+                    status.id,
+                    format!("❌ Could not scan chains:\n{e}"),
+                    None,
+                )
+                .await?;
+            }
+        }
+
+        let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+        dialogue
+            .update(State::ChainSelected {
+                chain: chain.clone(),
+                message_id: Some(new_menu_id),
+            })
+            .await?;
+    } else {
+        dialogue
+            .update(State::ChainSelected {
+                chain,
+                message_id: None,
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+/// Derives the address on every mainnet sharing the key's coin type and reads each one's
+/// balances, a bounded number of chains at a time.
+async fn scan_portfolio(cache: &Arc<RegistryCache>, input: &str) -> anyhow::Result<PortfolioScan> {
+    let decoded = decode_address(input)?;
+    if decoded.kind != AddressKind::Account {
+        anyhow::bail!("Enter an account address, not a validator address");
+    }
+    let prefixes = cache.chain_prefixes().await?;
+    let slip44 = source_slip44(&decoded, &prefixes).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Prefix {} is not used by any registry chain",
+            decoded.prefix.as_deref().unwrap_or_default()
+        )
+    })?;
+    let targets: Vec<ChainAddressPrefix> = prefixes
+        .iter()
+        .filter(|chain| chain.slip44 == slip44)
+        .cloned()
+        .collect();
+    let scanned = targets.len();
+
+    let results: Vec<(String, anyhow::Result<Option<ChainPortfolio>>)> = stream::iter(targets)
+        .map(|target| {
+            let decoded = decoded.clone();
+            async move {
+                let result = match decoded.encode(&target.bech32_prefix) {
+                    Ok(address) => scan_chain_balances(cache, &target.chain_name, address).await,
+                    Err(e) => Err(e),
+                };
+                (target.chain_name, result)
+            }
+        })
+        .buffer_unordered(PORTFOLIO_SCAN_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut scan = PortfolioScan {
+        slip44,
+        scanned,
+        chains: Vec::new(),
+        failed_chains: Vec::new(),
+    };
+    for (chain, result) in results {
+        match result {
+            Ok(Some(portfolio)) => scan.chains.push(portfolio),
+            Ok(None) => {}
+            Err(e) => {
+                log::warn!("Portfolio scan failed on {}: {}", chain, e);
+                scan.failed_chains.push(chain);
+            }
+        }
+    }
+    Ok(scan)
 }
 
-pub async fn handle_ibc_channel(
+/// Balances for one chain, labelled and priced from its asset list; `None` when empty.
+async fn scan_chain_balances(
+    cache: &RegistryCache,
+    chain: &str,
+    address: String,
+) -> anyhow::Result<Option<ChainPortfolio>> {
+    let chain_info = cache
+        .get_chain(chain)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("{chain} is not in the chain registry"))?;
+
+    let mut balances = Vec::new();
+    let mut key = None;
+    let mut complete = false;
+    for _ in 0..MAX_PORTFOLIO_PAGES {
+        let (page, next_key) =
+            query_balances_grpc_first(&chain_info.apis.grpc, &address, key.as_deref()).await?;
+        balances.extend(page);
+        key = next_key;
+        if key.is_none() {
+            complete = true;
+            break;
+        }
+    }
+    if balances.is_empty() {
+        return Ok(None);
+    }
+
+    let assets = cache.get_assets(chain).await.unwrap_or_else(|e| {
+        log::warn!("Could not fetch asset metadata for {}: {}", chain, e);
+        None
+    });
+    let price_queries: Vec<PriceQuery> = balances
+        .iter()
+        .filter_map(|balance| {
+            PriceQuery::from_asset(
+                &balance.denom,
+                listed_asset(assets.as_ref(), &balance.denom)?,
+            )
+        })
+        .collect();
+    let prices = cache.usd_prices(chain, &price_queries).await;
+    let balances = balances
+        .into_iter()
+        .map(|balance| WalletBalance {
+            ibc_trace: None,
+            asset_label: asset_label_for_denom(assets.as_ref(), &balance.denom),
+            display: listed_asset(assets.as_ref(), &balance.denom).and_then(asset_display_unit),
+            usd_price: prices.get(&balance.denom).copied(),
+            balance,
+        })
+        .collect();
+
+    Ok(Some(ChainPortfolio {
+        chain: chain.to_string(),
+        address,
+        balances,
+        complete,
+    }))
+}
+
+pub async fn handle_account_address(
     bot: Bot,
     dialogue: MyDialogue,
     cache: Arc<RegistryCache>,
@@ -1129,20 +3722,12 @@ pub async fn handle_ibc_channel(
     (chain, _message_id): (String, Option<MessageId>),
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     if let Some(text) = msg.text() {
-        // Parse the input - could be "23", "channel-23", or "channel-23 transfer"
-        let parts: Vec<&str> = text.split_whitespace().collect();
-        let channel_input = parts.first().unwrap_or(&"");
-        let port_id = parts.get(1).unwrap_or(&"transfer");
-
-        let channel_id = format_channel_input(channel_input);
+        let address = text.trim();
 
-        // Validate channel format
-        if !channel_id.starts_with("channel-") {
-            bot.send_message(
-                msg.chat.id,
-                "Please enter a valid channel (e.g., 0, channel-0)",
-            )
-            .await?;
+        // Basic validation
+        if address.is_empty() || address.len() < 10 || address.len() > 100 {
+            bot.send_message(msg.chat.id, "Please enter a valid account address.")
+                .await?;
             let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
             dialogue
                 .update(State::ChainSelected {
@@ -1155,46 +3740,29 @@ pub async fn handle_ibc_channel(
 
         send_processing_action(&bot, msg.chat.id).await;
         let status = bot
-            .send_message(msg.chat.id, format!("Looking up IBC route on {chain}..."))
+            .send_message(msg.chat.id, format!("Inspecting account on {chain}..."))
             .await?;
 
         if let Some(chain_info) = cache.get_chain(&chain).await? {
-            match query_ibc_channel_info_grpc_first(&chain_info.apis.grpc, &channel_id, port_id)
-                .await
-            {
-                Ok(info) => {
-                    let message = format!(
-                        "✅ IBC Route Information\n\n\
-                        Source Chain: {}\n\
-                        Destination Chain: {}\n\n\
-                        Channel Details:\n\
-                        • Channel: {}\n\
-                        • Port: {}\n\
-                        • Client ID: {}\n\
-                        • Connection: {}\n\n\
-                        Counterparty Details:\n\
-                        • Channel: {}\n\
-                        • Client ID: {}\n\
-                        • Connection: {}",
-                        info.chain_id,
-                        info.counterparty_chain_id,
-                        info.channel_id,
-                        port_id,
-                        info.client_id,
-                        info.connection_id,
-                        info.counterparty_channel_id,
-                        info.counterparty_client_id,
-                        info.counterparty_connection_id
+            match query_account_grpc_first(&chain_info.apis.grpc, address).await {
+                Ok(account) => {
+                    let units = chain_denom_units(&cache, &chain).await;
+                    let message = format_account_info(
+                        &chain,
+                        &account,
+                        &units,
+                        chrono::Utc::now().timestamp(),
                     );
-                    edit_status_message(&bot, msg.chat.id, status.id, message, None).await?;
+                    edit_or_send_markdown_result(&bot, msg.chat.id, status.id, message).await?;
                 }
                 Err(e) => {
                     let error_message = format!(
-                        "❌ Could not fetch IBC route info:\n{}\n\n\
-                        Make sure the channel exists on {}.",
-                        e, chain
+                        "❌ Could not fetch account:\n{}\n\n\
+                        The account may not exist yet \\(no incoming transfers\\) or the chain's APIs might be unavailable\\.",
+                        escape_markdown(&e.to_string())
                     );
-                    edit_status_message(&bot, msg.chat.id, status.id, error_message, None).await?;
+                    edit_or_send_markdown_result(&bot, msg.chat.id, status.id, error_message)
+                        .await?;
                 }
             }
         } else {
@@ -1208,7 +3776,6 @@ pub async fn handle_ibc_channel(
             .await?;
         }
 
-        // Show the menu after showing IBC route info
         let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
         dialogue
             .update(State::ChainSelected {
@@ -1227,22 +3794,7 @@ pub async fn handle_ibc_channel(
     Ok(())
 }
 
-fn asset_label_for_denom(assets: Option<&AssetList>, denom: &str) -> Option<String> {
-    let asset = assets?.assets.iter().find(|asset| {
-        asset.base == denom
-            || asset
-                .denom_units
-                .iter()
-                .any(|denom_unit| denom_unit.denom == denom)
-    })?;
-
-    [&asset.symbol, &asset.name, &asset.display]
-        .into_iter()
-        .find(|label| !label.is_empty())
-        .cloned()
-}
-
-pub async fn handle_wallet_address(
+pub async fn handle_grants_address(
     bot: Bot,
     dialogue: MyDialogue,
     cache: Arc<RegistryCache>,
@@ -1254,7 +3806,7 @@ pub async fn handle_wallet_address(
 
         // Basic validation
         if address.is_empty() || address.len() < 10 || address.len() > 100 {
-            bot.send_message(msg.chat.id, "Please enter a valid wallet address.")
+            bot.send_message(msg.chat.id, "Please enter a valid account address.")
                 .await?;
             let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
             dialogue
@@ -1268,73 +3820,25 @@ pub async fn handle_wallet_address(
 
         send_processing_action(&bot, msg.chat.id).await;
         let status = bot
-            .send_message(msg.chat.id, format!("Checking balances on {chain}..."))
+            .send_message(msg.chat.id, format!("Looking up grants on {chain}..."))
             .await?;
 
         if let Some(chain_info) = cache.get_chain(&chain).await? {
-            let assets_data = match cache.get_assets(&chain).await {
-                Ok(assets) => assets,
-                Err(e) => {
-                    log::warn!("Could not fetch asset metadata for {}: {}", chain, e);
-                    None
-                }
-            };
-
-            match query_balances_grpc_first(&chain_info.apis.grpc, address, None).await {
-                Ok((balances, next_key)) => {
-                    if balances.is_empty() {
-                        let message = format!(
-                            "No balances found for address:\n`{}`\n\n\
-                            The address might be empty or invalid for {}\\.",
-                            escape_markdown(address),
-                            escape_markdown(&chain)
-                        );
-                        edit_or_send_markdown_result(&bot, msg.chat.id, status.id, message).await?;
-                    } else {
-                        let mut wallet_balances = Vec::with_capacity(balances.len());
-                        for balance in balances {
-                            let ibc_trace = if let Some(ibc_hash) =
-                                balance.denom.strip_prefix("ibc/")
-                            {
-                                match query_ibc_denom_grpc_first(&chain_info.apis.grpc, ibc_hash)
-                                    .await
-                                {
-                                    Ok(trace) => Some(trace),
-                                    Err(e) => {
-                                        log::warn!(
-                                            "Could not resolve IBC denom {}: {}",
-                                            balance.denom,
-                                            e
-                                        );
-                                        None
-                                    }
-                                }
-                            } else {
-                                None
-                            };
-                            let asset_label =
-                                asset_label_for_denom(assets_data.as_ref(), &balance.denom);
-
-                            wallet_balances.push(WalletBalance {
-                                balance,
-                                ibc_trace,
-                                asset_label,
-                            });
-                        }
-
-                        let message = format_wallet_balances(
-                            address,
-                            &chain,
-                            &wallet_balances,
-                            next_key.is_some(),
-                        );
-                        edit_or_send_markdown_result(&bot, msg.chat.id, status.id, message).await?;
-                    }
+            match query_address_grants_grpc_first(&chain_info.apis.grpc, address).await {
+                Ok(grants) => {
+                    let message = format_address_grants(
+                        &chain,
+                        address,
+                        &grants,
+                        &chain_denom_units(&cache, &chain).await,
+                        chrono::Utc::now().timestamp(),
+                    );
+                    edit_or_send_markdown_result(&bot, msg.chat.id, status.id, message).await?;
                 }
                 Err(e) => {
                     let error_message = format!(
-                        "❌ Could not fetch balances:\n{}\n\n\
-                        The address might be invalid or the chain's APIs might be unavailable\\.",
+                        "❌ Could not fetch grants:\n{}\n\n\
+                        The chain's APIs might be unavailable\\.",
                         escape_markdown(&e.to_string())
                     );
                     edit_or_send_markdown_result(&bot, msg.chat.id, status.id, error_message)
@@ -1352,7 +3856,6 @@ pub async fn handle_wallet_address(
             .await?;
         }
 
-        // Show the menu after showing balance info
         let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
         dialogue
             .update(State::ChainSelected {
@@ -1411,11 +3914,12 @@ pub async fn handle_osmosis_pool_incentives(
         if let Some(chain_info) = cache.get_chain(&chain).await? {
             match query_osmosis_pool_incentives(&chain_info.apis.grpc, &pool_id).await {
                 Ok(incentives) => {
+                    let units = chain_denom_units(&cache, &chain).await;
                     edit_status_message(
                         &bot,
                         msg.chat.id,
                         status.id,
-                        format_osmosis_pool_incentives(&pool_id, &incentives),
+                        format_osmosis_pool_incentives(&pool_id, &incentives, &units),
                         None,
                     )
                     .await?;
@@ -1484,11 +3988,12 @@ pub async fn handle_osmosis_pool_info(
         if let Some(chain_info) = cache.get_chain(&chain).await? {
             match query_osmosis_pool_info(&chain_info.apis.grpc, &pool_id).await {
                 Ok(pool) => {
+                    let units = chain_denom_units(&cache, &chain).await;
                     edit_status_message(
                         &bot,
                         msg.chat.id,
                         status.id,
-                        format_osmosis_pool_info(&pool_id, &pool),
+                        format_osmosis_pool_info(&pool_id, &pool, &units),
                         None,
                     )
                     .await?;
@@ -1609,25 +4114,10 @@ pub async fn handle_text(
             let state = dialogue.get().await?.unwrap_or_default();
             match state {
                 State::ChainSelected { chain, message_id } => {
-                    let mut actions = if !chain.contains("testnet") {
-                        vec![
-                            "chain_info",
-                            "peer_nodes",
-                            "endpoints",
-                            "explorers",
-                            "ibc_id",
-                            "ibc_route",
-                            "check_balance",
-                        ]
-                    } else {
-                        vec!["chain_info", "peer_nodes", "endpoints", "explorers"]
-                    };
-                    if is_osmosis_mainnet(&chain) {
-                        actions.extend(["pool_incentives", "pool_info", "price_info"]);
-                    }
+                    let actions = chain_menu_actions(&chain, !chain.contains("testnet"));
 
                     if num > 0 && num <= actions.len() {
-                        let action = actions[num - 1];
+                        let (action, _) = actions[num - 1];
 
                         // Delete the previous menu if it exists
                         if let Some(menu_id) = message_id {
@@ -1906,6 +4396,19 @@ pub async fn handle_text(
                                 )
                                 .await?;
                             }
+                            "asset_denom" => {
+                                dialogue
+                                    .update(State::AwaitingAssetDenom {
+                                        chain,
+                                        message_id: Some(msg.id),
+                                    })
+                                    .await?;
+                                bot.send_message(
+                                    msg.chat.id,
+                                    "Enter asset and destination chain (e.g., ATOM osmosis),\nor a full trace path to hash (e.g., transfer/channel-0/uatom):",
+                                )
+                                .await?;
+                            }
                             "ibc_route" => {
                                 dialogue
                                     .update(State::AwaitingIbcChannel {
@@ -1928,6 +4431,146 @@ pub async fn handle_text(
                                 )
                                 .await?;
                             }
+                            "portfolio_scan" => {
+                                dialogue
+                                    .update(State::AwaitingPortfolioAddress {
+                                        chain,
+                                        message_id: Some(msg.id),
+                                    })
+                                    .await?;
+                                bot.send_message(
+                                    msg.chat.id,
+                                    "Enter an address to scan balances on every chain with the same coin type:",
+                                )
+                                .await?;
+                            }
+                            "chain_params" => {
+                                send_chain_params(&bot, msg.chat.id, &cache, &chain).await?;
+                                let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+                                dialogue
+                                    .update(State::ChainSelected {
+                                        chain: chain.clone(),
+                                        message_id: Some(new_menu_id),
+                                    })
+                                    .await?;
+                            }
+                            "transfer_channels" => {
+                                send_channel_list(&bot, msg.chat.id, &cache, &chain, true).await?;
+                                let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+                                dialogue
+                                    .update(State::ChainSelected {
+                                        chain: chain.clone(),
+                                        message_id: Some(new_menu_id),
+                                    })
+                                    .await?;
+                            }
+                            "all_channels" => {
+                                send_channel_list(&bot, msg.chat.id, &cache, &chain, false).await?;
+                                let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+                                dialogue
+                                    .update(State::ChainSelected {
+                                        chain: chain.clone(),
+                                        message_id: Some(new_menu_id),
+                                    })
+                                    .await?;
+                            }
+                            "fees" => {
+                                send_fee_card(&bot, msg.chat.id, &cache, &chain).await?;
+                                let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+                                dialogue
+                                    .update(State::ChainSelected {
+                                        chain: chain.clone(),
+                                        message_id: Some(new_menu_id),
+                                    })
+                                    .await?;
+                            }
+                            "software" => {
+                                send_software_card(&bot, msg.chat.id, &cache, &chain).await?;
+                                let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+                                dialogue
+                                    .update(State::ChainSelected {
+                                        chain: chain.clone(),
+                                        message_id: Some(new_menu_id),
+                                    })
+                                    .await?;
+                            }
+                            "account_info" => {
+                                dialogue
+                                    .update(State::AwaitingAccountAddress {
+                                        chain,
+                                        message_id: Some(msg.id),
+                                    })
+                                    .await?;
+                                bot.send_message(msg.chat.id, "Enter address to inspect account:")
+                                    .await?;
+                            }
+                            "pending_packets" => {
+                                dialogue
+                                    .update(State::AwaitingPacketChannel {
+                                        chain,
+                                        message_id: Some(msg.id),
+                                    })
+                                    .await?;
+                                bot.send_message(msg.chat.id, "Enter channel to inspect pending packets (e.g., 0, channel-0, or channel-0 transfer):")
+                                    .await?;
+                            }
+                            "escrow" => {
+                                dialogue
+                                    .update(State::AwaitingEscrowChannel {
+                                        chain,
+                                        message_id: Some(msg.id),
+                                    })
+                                    .await?;
+                                bot.send_message(
+                                    msg.chat.id,
+                                    "Enter transfer channel to inspect escrow (e.g., 0 or channel-0),\nor \"all\" for every open transfer channel:",
+                                )
+                                .await?;
+                            }
+                            "rate_limits" => {
+                                dialogue
+                                    .update(State::AwaitingRateLimit {
+                                        chain,
+                                        message_id: Some(msg.id),
+                                    })
+                                    .await?;
+                                bot.send_message(
+                                    msg.chat.id,
+                                    "Enter transfer channel to check rate limits (e.g., 0 or channel-0),\noptionally followed by a denom or symbol (e.g., channel-0 uatom):",
+                                )
+                                .await?;
+                            }
+                            "memo" => {
+                                bot.send_message(msg.chat.id, format!("Build a packet-forward memo from {chain}.\nEnter the chains to pass through, ending with the destination (e.g., osmosis or cosmoshub neutron):"))
+                                    .await?;
+                                dialogue
+                                    .update(State::BuildingMemo {
+                                        chain,
+                                        message_id: Some(msg.id),
+                                        draft: MemoDraft::default(),
+                                    })
+                                    .await?;
+                            }
+                            "registry_check" => {
+                                send_registry_check(&bot, msg.chat.id, &cache, &chain).await?;
+                                let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
+                                dialogue
+                                    .update(State::ChainSelected {
+                                        chain: chain.clone(),
+                                        message_id: Some(new_menu_id),
+                                    })
+                                    .await?;
+                            }
+                            "grants" => {
+                                dialogue
+                                    .update(State::AwaitingGrantsAddress {
+                                        chain,
+                                        message_id: Some(msg.id),
+                                    })
+                                    .await?;
+                                bot.send_message(msg.chat.id, "Enter address to look up grants:")
+                                    .await?;
+                            }
                             "pool_incentives" => {
                                 dialogue
                                     .update(State::AwaitingOsmosisPoolIncentives {
@@ -1975,11 +4618,7 @@ pub async fn handle_text(
                     }
                 }
                 _ => {
-                    bot.send_message(
-                        msg.chat.id,
-                        "No chain selected. Please select a chain first using /start",
-                    )
-                    .await?;
+                    send_global_ibc_denom_lookup(&bot, msg.chat.id, &cache, text.trim()).await?;
                 }
             }
             return Ok(());
@@ -1993,80 +4632,7 @@ pub async fn handle_text(
                 State::ChainSelected { chain, .. } | State::AwaitingIbcDenom { chain, .. } => {
                     let ibc_hash = &text[4..]; // Remove "ibc/" prefix
 
-                    send_processing_action(&bot, msg.chat.id).await;
-                    let status = bot
-                        .send_message(msg.chat.id, format!("Resolving IBC denom on {chain}..."))
-                        .await?;
-
-                    if let Some(chain_info) = cache.get_chain(&chain).await? {
-                        match query_ibc_denom_grpc_first(&chain_info.apis.grpc, ibc_hash).await {
-                            Ok(trace) => {
-                                let mut message = if !trace.path.is_empty() {
-                                    format!(
-                                        "✅ IBC Denom Trace Found:\n\nPath: {}\nBase Denomination: {}",
-                                        trace.path, trace.base_denom
-                                    )
-                                } else {
-                                    format!(
-                                        "✅ IBC Denom Trace Found:\n\nBase Denomination: {}",
-                                        trace.base_denom
-                                    )
-                                };
-
-                                if !trace.path.is_empty() {
-                                    if let Some(channel) = extract_channel_from_path(&trace.path) {
-                                        match query_ibc_channel_info_grpc_first(
-                                            &chain_info.apis.grpc,
-                                            &channel,
-                                            "transfer",
-                                        )
-                                        .await
-                                        {
-                                            Ok(info) => {
-                                                message.push_str(&format!(
-                                                    "\n\n📍 Source Chain: {}\nvia {}",
-                                                    info.counterparty_chain_id, channel
-                                                ));
-                                            }
-                                            Err(e) => {
-                                                log::warn!(
-                                                    "Could not fetch channel info for {}: {}",
-                                                    channel,
-                                                    e
-                                                );
-                                            }
-                                        }
-                                    }
-                                }
-
-                                edit_status_message(&bot, msg.chat.id, status.id, message, None)
-                                    .await?;
-                            }
-                            Err(e) => {
-                                let error_message = format!(
-                                    "❌ Could not resolve IBC denom:\n{}\n\nThis denom might not exist on {} or the chain's APIs might be unavailable.",
-                                    e, chain
-                                );
-                                edit_status_message(
-                                    &bot,
-                                    msg.chat.id,
-                                    status.id,
-                                    error_message,
-                                    None,
-                                )
-                                .await?;
-                            }
-                        }
-                    } else {
-                        edit_status_message(
-                            &bot,
-                            msg.chat.id,
-                            status.id,
-                            "Chain not found".to_string(),
-                            None,
-                        )
-                        .await?;
-                    }
+                    send_ibc_denom_lookup(&bot, msg.chat.id, &cache, &chain, ibc_hash).await?;
                     // Show menu again after IBC lookup
                     let new_menu_id = send_chain_menu(&bot, &msg, &chain).await?;
                     dialogue
@@ -2103,48 +4669,10 @@ pub async fn handle_text(
             }
 
             // Show chain menu inline
-            let mut buttons = vec![
-                vec![
-                    InlineKeyboardButton::callback("1. Chain Info", "action:chain_info"),
-                    InlineKeyboardButton::callback("2. Peer Nodes", "action:peer_nodes"),
-                ],
-                vec![
-                    InlineKeyboardButton::callback("3. Endpoints", "action:endpoints"),
-                    InlineKeyboardButton::callback("4. Block Explorers", "action:explorers"),
-                ],
-            ];
-
-            // Add IBC options and balance check for mainnets
             // Check if it's a testnet by seeing if it's in the testnets list
             let testnets = cache.list_testnets().await.unwrap_or_default();
-            if !testnets.iter().any(|t| t.to_lowercase() == text_lower) {
-                buttons.push(vec![
-                    InlineKeyboardButton::callback("5. IBC-ID", "action:ibc_id"),
-                    InlineKeyboardButton::callback("6. IBC Route Info", "action:ibc_route"),
-                ]);
-                buttons.push(vec![InlineKeyboardButton::callback(
-                    "7. Check Balance",
-                    "action:check_balance",
-                )]);
-                if is_osmosis_mainnet(&text_lower) {
-                    push_osmosis_buttons(&mut buttons);
-                }
-            }
-
-            // Add installation guide link if available on Polkachu
-            if let Some(install_url) = get_polkachu_installation_url(&text_lower) {
-                buttons.push(vec![InlineKeyboardButton::url(
-                    "Node Installation Guide",
-                    install_url.parse().unwrap(),
-                )]);
-            }
-
-            buttons.push(vec![InlineKeyboardButton::callback(
-                "← Back",
-                "back:chains",
-            )]);
-
-            let keyboard = InlineKeyboardMarkup::new(buttons);
+            let is_mainnet = !testnets.iter().any(|t| t.to_lowercase() == text_lower);
+            let keyboard = chain_menu_keyboard(&text_lower, is_mainnet);
 
             let sent_msg = bot
                 .send_message(
@@ -2,15 +2,52 @@
 mod unit_tests {
     use crate::{
         bot::{MyDialogue, State},
+        cache::RegistryCache,
         utils::{
-            first_endpoint_address, format_channel_input, format_osmosis_pool_incentives,
-            format_osmosis_pool_info, format_osmosis_token_price, format_wallet_balances,
-            prioritize_grpc_endpoints, Balance, IbcDenomTrace, OsmosisGaugeIncentive,
-            OsmosisPoolAsset, OsmosisPoolIncentives, OsmosisPoolInfo, OsmosisTokenPrice,
-            WalletBalance,
+            build_forward_memo, canonical_transfer_channel, channel_end_mismatches,
+            check_registry_trace, check_wallet_address, decode_address, destination_trace_path,
+            fetch_usd_prices, first_endpoint_address, format_account_info,
+            format_address_conversions, format_address_grants, format_asset_denom_lookup,
+            format_balance_summary, format_chain_params, format_channel_input,
+            format_consistency_report, format_denom_route, format_display_amount,
+            format_escrow_report, format_fee_card, format_global_denom_matches,
+            format_ibc_channels, format_ibc_routes, format_legacy_dec, format_light_client_status,
+            format_osmosis_pool_incentives, format_osmosis_pool_info, format_osmosis_token_price,
+            format_pending_packets, format_portfolio_scan, format_rate_limits,
+            format_raw_denom_hash, format_software_card, format_usd, format_validator_info,
+            format_wallet_balances, human_duration, ibc_denom_hash, ics20_escrow_address,
+            light_client_kind, parse_chain_software, parse_osmosis_rate_limits,
+            parse_rate_limit_key, prioritize_grpc_endpoints, rank_ibc_routes,
+            registry_channel_counterparties, registry_cw20_tokens, registry_ibc_denoms,
+            source_slip44, split_trace_path, to_base_units, validate_forward_memo,
+            vesting_amounts_at, without_closed_channels, AccountInfo, AddressGrants, AddressKind,
+            AssetDenomLookup, AuthzGrantInfo, Balance, BalanceCursor, ChainAddressPrefix,
+            ChainParams, ChainParamsSection, ChainPortfolio, ChainSwitch, ChannelConsistency,
+            ChannelEscrow, ConsistencyReport, DecodedAddress, DenomRoute, DenomRouteHop,
+            DenomUnits, DenomVerification, DetailValue, DisplayUnit, EscrowReport,
+            FeeAllowanceInfo, FixedPrices, GlobalDenomMatch, IbcChannelInfo, IbcChannelSummary,
+            IbcDenomHop, IbcDenomTrace, IbcGraph, IbcRoute, LightClientKind, LightClientStatus,
+            LiveBaseFee, OsmosisGaugeIncentive, OsmosisPoolAsset, OsmosisPoolIncentives,
+            OsmosisPoolInfo, OsmosisSwap, OsmosisTokenPrice, PacketFlow, PendingPacketReport,
+            PortfolioScan, PriceQuery, PriceSource, RateLimitLookup, RateLimitSource,
+            RegistryChannelEnd, RegistryCw20Token, RegistryIbcAsset, RegistryTraceCheck,
+            RegistryTransferChannel, RouteHop, UsdPrices, ValidatorInfo, VestingPeriod,
+            VestingSchedule, WalletAddressCheck, WalletBalance, MAX_CHANNELS_SHOWN, MAX_ROUTE_HOPS,
+            OSMOSIS_CROSSCHAIN_SWAPS_CONTRACT, PFM_HUBS,
         },
     };
-    use cosmos_chain_registry::chain;
+    use cosmos_chain_registry::{
+        assets::{Asset, DenomUnit},
+        chain,
+        paths::{Chain1, Chain2, Channel, ChannelChain1, ChannelChain2, Tags},
+        AssetList, IBCPath,
+    };
+    use ibc_proto::ibc::lightclients::{
+        tendermint::v1::ClientState as TendermintClientState,
+        wasm::v1::ClientState as WasmClientState,
+    };
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
     use teloxide::{
         dispatching::dialogue::InMemStorage,
         types::{ChatId, InlineKeyboardButton, MessageId},
@@ -343,7 +380,7 @@ mod unit_tests {
             current_tick_liquidity: None,
         };
 
-        let formatted = format_osmosis_pool_info("1", &pool);
+        let formatted = format_osmosis_pool_info("1", &pool, &DenomUnits::default());
 
         assert!(formatted.contains("Osmosis Pool 1"));
         assert!(formatted.contains("Type: Balancer"));
@@ -386,7 +423,7 @@ mod unit_tests {
             cl_records: vec![],
         };
 
-        let formatted = format_osmosis_pool_incentives("1", &incentives);
+        let formatted = format_osmosis_pool_incentives("1", &incentives, &DenomUnits::default());
 
         assert!(formatted.contains("Gauge 1"));
         assert!(formatted.contains("1,000,000 uosmo"));
@@ -449,11 +486,13 @@ mod unit_tests {
                         .to_string(),
                     amount: "10000000".to_string(),
                 },
-                ibc_trace: Some(IbcDenomTrace {
-                    path: "transfer/channel-123/transfer/channel-42".to_string(),
-                    base_denom: "loki".to_string(),
-                }),
+                ibc_trace: Some(IbcDenomTrace::from_path(
+                    "transfer/channel-123/transfer/channel-42",
+                    "loki",
+                )),
                 asset_label: Some("LOKI".to_string()),
+                display: None,
+                usd_price: None,
             },
             WalletBalance {
                 balance: Balance {
@@ -462,11 +501,13 @@ mod unit_tests {
                 },
                 ibc_trace: None,
                 asset_label: Some("SPICE".to_string()),
+                display: None,
+                usd_price: None,
             },
         ];
 
         let formatted =
-            format_wallet_balances("osmo1wev8ptzj27aueu0abc", "osmosis", &balances, true);
+            format_wallet_balances("osmo1wev8ptzj27aueu0abc", "osmosis", &balances, 0, true);
 
         assert!(formatted.contains("*Balances for* `osmo1wev8ptzj27aueu0...`"));
         assert!(formatted.contains("*LOKI*"));
@@ -474,13 +515,1652 @@ mod unit_tests {
         assert!(formatted.contains(
             "IBC Denom: `ibc/23B7FFE8D1673E1EBF05AB02000E23E6077967B79547A3733B60AE4ED62C4D32`"
         ));
-        assert!(formatted.contains("IBC Path: `transfer/channel-123/transfer/channel-42`"));
+        assert!(
+            formatted.contains("IBC Path: `transfer/channel-123/transfer/channel-42` \\(2 hops\\)")
+        );
         assert!(formatted.contains("Base Denom: `loki`"));
         assert!(formatted.contains("*SPICE*"));
         assert!(formatted.contains("Amount: `999,999`"));
         assert!(formatted
             .contains("Denom: `factory/osmo1n6asrjy9754q8y9jsxqf557zmsv3s3xa5m9eg5/uspice`"));
         assert_eq!(formatted.matches("\\-\\-\\-").count(), 1);
-        assert!(formatted.contains("_Showing first 100 assets; more balances are available\\._"));
+        assert!(formatted.contains("_Page 1; more balances are available\\._"));
+
+        let last =
+            format_wallet_balances("osmo1wev8ptzj27aueu0abc", "osmosis", &balances, 2, false);
+        assert!(last.ends_with("_Page 3, the last page\\._"));
+    }
+
+    #[test]
+    fn test_balance_cursors_and_summary() {
+        let cache = RegistryCache::new(5);
+        let cursor = BalanceCursor {
+            chain: "osmosis".to_string(),
+            address: "osmo1abc".to_string(),
+            key: Some("a2V5".to_string()),
+            page: 1,
+            previous: None,
+        };
+        let first = cache.store_balance_cursor(cursor.clone());
+        let second = cache.store_balance_cursor(cursor.clone());
+        assert_ne!(first, second);
+        assert_eq!(cache.balance_cursor(&first), Some(cursor));
+        assert_eq!(cache.balance_cursor("missing"), None);
+
+        let switch = ChainSwitch {
+            chain: "cosmoshub".to_string(),
+            address: "cosmosvaloper1abc".to_string(),
+        };
+        let token = cache.store_chain_switch(switch.clone());
+        assert_eq!(cache.chain_switch(&token), Some(switch));
+        assert_eq!(cache.balance_cursor(&token), None);
+
+        let balance = |denom: &str, amount: &str| Balance {
+            denom: denom.to_string(),
+            amount: amount.to_string(),
+        };
+        let balances = vec![
+            balance("uosmo", "1500000"),
+            balance("ibc/27394FB0", "42"),
+            balance("ibc/UNKNOWN", "7"),
+            balance("factory/osmo1x/uspice", "9"),
+        ];
+        let labels = HashMap::from([
+            ("uosmo".to_string(), "OSMO".to_string()),
+            ("ibc/27394FB0".to_string(), "ATOM".to_string()),
+        ]);
+        let units = six_decimal_units("uosmo", "OSMO");
+        let prices =
+            UsdPrices::from([("uosmo".to_string(), 0.5), ("ibc/UNKNOWN".to_string(), 3.0)]);
+        let summary = format_balance_summary(
+            "osmo1abc", "osmosis", &balances, &labels, &units, &prices, false,
+        );
+        assert!(summary.contains("4 denoms: 2 IBC, 1 token factory, 1 other"));
+        assert!(summary.contains("_Stopped after 50 pages; the account holds more\\._"));
+        assert!(summary.contains(
+            "*Value of fetched balances:* `$0.75`\n_3 assets unpriced and not counted\\._"
+        ));
+        assert!(summary.contains("*Registry assets*\n• ATOM: `42`\n• OSMO: `1.5 OSMO (1500000)`"));
+        assert!(summary.ends_with("2 denoms are not in the osmosis asset list"));
+    }
+
+    #[test]
+    fn test_vesting_amounts_and_account_formatting() {
+        let uatom = |amount: &str| Balance {
+            denom: "uatom".to_string(),
+            amount: amount.to_string(),
+        };
+
+        let continuous = VestingSchedule {
+            kind: "Continuous".to_string(),
+            start_time: Some(1_000),
+            end_time: 2_000,
+            original_vesting: vec![uatom("1000000")],
+            delegated_free: vec![],
+            delegated_vesting: vec![uatom("400000")],
+            periods: vec![],
+        };
+        let (vested, unvested) = vesting_amounts_at(&continuous, 1_250);
+        assert_eq!(vested, vec![uatom("250000")]);
+        assert_eq!(unvested, vec![uatom("750000")]);
+
+        let periodic = VestingSchedule {
+            kind: "Periodic".to_string(),
+            start_time: Some(1_000),
+            end_time: 3_000,
+            original_vesting: vec![uatom("300")],
+            delegated_free: vec![],
+            delegated_vesting: vec![],
+            periods: vec![
+                VestingPeriod {
+                    start_time: 1_000,
+                    end_time: 2_000,
+                    amount: vec![uatom("100")],
+                },
+                VestingPeriod {
+                    start_time: 2_000,
+                    end_time: 3_000,
+                    amount: vec![uatom("200")],
+                },
+            ],
+        };
+        let (vested, unvested) = vesting_amounts_at(&periodic, 2_500);
+        assert_eq!(vested, vec![uatom("100")]);
+        assert_eq!(unvested, vec![uatom("200")]);
+
+        let account = AccountInfo {
+            account_type: "Periodic Vesting".to_string(),
+            address: "cosmos1vesting".to_string(),
+            account_number: 42,
+            sequence: 7,
+            pub_key_type: Some("cosmos.crypto.secp256k1.PubKey".to_string()),
+            module_name: None,
+            module_permissions: vec![],
+            vesting: Some(periodic),
+        };
+        let formatted = format_account_info("cosmoshub", &account, &DenomUnits::default(), 2_500);
+
+        assert!(formatted.contains("Account Number: `42`"));
+        assert!(formatted.contains("Sequence: `7`"));
+        assert!(formatted.contains("Pubkey: `cosmos.crypto.secp256k1.PubKey`"));
+        assert!(formatted.contains("*Vesting Schedule \\(Periodic\\)*"));
+        assert!(formatted.contains("Vested now: `100 uatom`"));
+        assert!(formatted.contains("Still locked: `200 uatom`"));
+        assert!(formatted.contains("✅ 1\\."));
+        assert!(formatted.contains("🔒 2\\."));
+    }
+
+    #[test]
+    fn test_chain_params_formatting() {
+        assert_eq!(format_legacy_dec("50000000000000000"), "0.05");
+        assert_eq!(format_legacy_dec("0.334000000000000000"), "0.334");
+        assert_eq!(format_legacy_dec("1000000000000000000"), "1");
+        assert_eq!(format_legacy_dec("0"), "0");
+        assert_eq!(human_duration(1_814_400), "21d");
+        assert_eq!(human_duration(129_600), "1d 12h");
+        assert_eq!(human_duration(600), "10m");
+
+        let params = ChainParams {
+            sections: vec![ChainParamsSection {
+                module: "Staking".to_string(),
+                entries: vec![
+                    ("Unbonding Time".to_string(), "21d".to_string().into()),
+                    ("Min Commission".to_string(), "0.05".to_string().into()),
+                    (
+                        "Min Deposit".to_string(),
+                        DetailValue::Coins(vec![Balance {
+                            denom: "uatom".to_string(),
+                            amount: "250000000".to_string(),
+                        }]),
+                    ),
+                ],
+            }],
+            unavailable: vec!["Consensus".to_string()],
+            failed: vec!["Governance".to_string()],
+        };
+        let formatted =
+            format_chain_params("cosmoshub", &params, &six_decimal_units("uatom", "ATOM"));
+
+        assert!(formatted.contains("**>*Staking*\n>Unbonding Time: `21d`"));
+        assert!(formatted.contains(">Min Commission: `0.05`\n"));
+        assert!(formatted.contains(">Min Deposit: `250 ATOM (250000000)`||"));
+        assert!(formatted.contains("_Not exposed by this chain: Consensus_"));
+        assert!(formatted.contains("_Could not be queried: Governance_"));
+    }
+
+    #[test]
+    fn test_fee_card_formatting() {
+        let fee_tokens = vec![chain::FeeToken {
+            denom: "uatom".to_string(),
+            fixed_min_gas_price: 0.005,
+            low_gas_price: 0.01,
+            average_gas_price: 0.025,
+            high_gas_price: 0.03,
+        }];
+        let assets = AssetList {
+            chain_name: "cosmoshub".to_string(),
+            assets: vec![Asset {
+                base: "uatom".to_string(),
+                display: "atom".to_string(),
+                symbol: "ATOM".to_string(),
+                denom_units: vec![
+                    DenomUnit {
+                        denom: "uatom".to_string(),
+                        exponent: 0,
+                    },
+                    DenomUnit {
+                        denom: "atom".to_string(),
+                        exponent: 6,
+                    },
+                ],
+                ..Default::default()
+            }],
+        };
+
+        let formatted = format_fee_card("cosmoshub", &fee_tokens, Some(&assets), None);
+        assert!(formatted.contains("*ATOM*\nDenom: `uatom`"));
+        assert!(formatted.contains("Average: `0.025uatom`"));
+        assert!(formatted.contains("Typical send \\(~100,000 gas\\): `0.0025 ATOM`"));
+        assert!(formatted.contains("registry prices apply"));
+
+        let base_fee = LiveBaseFee {
+            source: "feemarket".to_string(),
+            denom: Some("uatom".to_string()),
+            price: "0.005".to_string(),
+        };
+        let formatted = format_fee_card("cosmoshub", &fee_tokens, Some(&assets), Some(&base_fee));
+        assert!(formatted.contains("*Live Base Fee* \\(feemarket\\)\n`0.005uatom`"));
+        assert!(formatted.contains("Typical send at base fee: `0.0005 ATOM`"));
+    }
+
+    #[test]
+    fn test_software_card_parsing_and_formatting() {
+        let chain_json = serde_json::json!({
+            "codebase": {
+                "git_repo": "https://github.com/cosmos/gaia",
+                "recommended_version": "v19.2.0",
+                "compatible_versions": ["v19.1.0", "v19.2.0"],
+                "binaries": {
+                    "linux/amd64": "https://github.com/cosmos/gaia/releases/download/v19.2.0/gaiad-v19.2.0-linux-amd64"
+                },
+                "sdk": { "type": "cosmos", "version": "0.50.9" },
+                "tendermint_version": "0.38.11",
+                "genesis": { "genesis_url": "https://example.com/genesis.json" }
+            },
+            "versions": [
+                { "name": "v18", "tag": "v18.1.0", "height": 21835200, "proposal": 924 },
+                { "name": "v19", "recommended_version": "v19.2.0", "height": "22433500" }
+            ]
+        });
+
+        let software = parse_chain_software(&chain_json);
+        assert_eq!(software.compatible_versions.len(), 2);
+        assert_eq!(
+            software.libraries,
+            vec![
+                ("Cosmos SDK".to_string(), "0.50.9 (cosmos)".to_string()),
+                ("Consensus".to_string(), "0.38.11".to_string()),
+            ]
+        );
+        assert_eq!(software.versions[1].height, Some(22433500));
+        assert_eq!(software.versions[1].tag.as_deref(), Some("v19.2.0"));
+
+        let formatted = format_software_card("cosmoshub", &software);
+        assert!(formatted.contains("Recommended: `v19.2.0`"));
+        assert!(formatted.contains("Compatible: `v19.1.0`, `v19.2.0`"));
+        assert!(formatted.contains("linux/amd64: https://github\\.com"));
+        let newest = formatted.find("*v19* at height 22,433,500").unwrap();
+        let oldest = formatted
+            .find("*v18* at height 21,835,200 \\- `v18.1.0` \\(prop 924\\)")
+            .unwrap();
+        assert!(newest < oldest);
+        assert!(formatted.ends_with("||"));
+    }
+
+    #[test]
+    fn test_address_grants_formatting() {
+        let grants = AddressGrants {
+            granted_by_address: vec![AuthzGrantInfo {
+                granter: "cosmos1granter".to_string(),
+                grantee: "cosmos1restakebot".to_string(),
+                authorization: "Stake".to_string(),
+                msg_type: Some("/cosmos.staking.v1beta1.MsgDelegate".to_string()),
+                details: vec![("Max tokens".to_string(), "unlimited".to_string().into())],
+                expiration: Some(1_000 + 3 * 86_400),
+            }],
+            granted_to_address: Vec::new(),
+            allowances_given: Vec::new(),
+            allowances_received: vec![FeeAllowanceInfo {
+                granter: "cosmos1sponsor".to_string(),
+                grantee: "cosmos1granter".to_string(),
+                allowance: "Basic".to_string(),
+                details: vec![(
+                    "Spend limit".to_string(),
+                    DetailValue::Coins(vec![Balance {
+                        denom: "uatom".to_string(),
+                        amount: "1000000".to_string(),
+                    }]),
+                )],
+                expiration: Some(500),
+            }],
+            unavailable: vec!["feegrant (as granter)".to_string()],
+            truncated: vec!["authz (as granter)".to_string()],
+        };
+
+        let formatted = format_address_grants(
+            "cosmoshub",
+            "cosmos1granter",
+            &grants,
+            &six_decimal_units("uatom", "ATOM"),
+            1_000,
+        );
+        assert!(formatted.contains("**>*Authz Granted To Others* \\(1\\)\n>`cosmos1restakebot`"));
+        assert!(formatted.contains(">  Stake: `/cosmos.staking.v1beta1.MsgDelegate`"));
+        assert!(formatted.contains("\\(in 3d\\)"));
+        assert!(formatted.contains("*Authz Received*\nnone"));
+        assert!(formatted.contains(">`cosmos1sponsor`\n>  Basic, expired 1970"));
+        assert!(formatted.contains(">  Spend limit: `1 ATOM (1000000)`||"));
+        assert!(formatted.contains("_Not exposed by this chain: feegrant \\(as granter\\)_"));
+        assert!(
+            formatted.contains("_Only the first 500 entries shown for: authz \\(as granter\\)_")
+        );
+    }
+
+    #[test]
+    fn test_ibc_channel_list_formatting() {
+        let open_transfer = IbcChannelSummary {
+            port_id: "transfer".to_string(),
+            channel_id: "channel-0".to_string(),
+            state: "Open".to_string(),
+            ordering: "Unordered".to_string(),
+            version: "ics20-1".to_string(),
+            connection_id: "connection-0".to_string(),
+            client_id: Some("07-tendermint-0".to_string()),
+            counterparty_port_id: "transfer".to_string(),
+            counterparty_channel_id: "channel-141".to_string(),
+            counterparty_chain_id: Some("osmosis-1".to_string()),
+            counterparty_chain_name: Some("osmosis".to_string()),
+        };
+        let closed_ica = IbcChannelSummary {
+            port_id: "icahost".to_string(),
+            channel_id: "channel-7".to_string(),
+            state: "Closed".to_string(),
+            ordering: "Ordered".to_string(),
+            counterparty_chain_name: None,
+            ..open_transfer.clone()
+        };
+        let channels = vec![open_transfer, closed_ica];
+
+        let transfer_only = format_ibc_channels("cosmoshub", &channels, true);
+        assert!(transfer_only.contains("1 of 2 channels"));
+        assert!(transfer_only
+            .contains("`transfer/channel-0` → *osmosis* \\(osmosis\\-1\\) `transfer/channel-141`"));
+        assert!(transfer_only.contains("Open · Unordered · `ics20-1`"));
+        assert!(!transfer_only.contains("icahost"));
+
+        let all = format_ibc_channels("cosmoshub", &channels, false);
+        assert!(all.contains("2 channels, 1 open"));
+        assert!(all.contains("`icahost/channel-7` → osmosis\\-1"));
+        assert!(all.contains("Closed · Ordered"));
+        assert!(!all.contains("more channels"));
+
+        let many: Vec<IbcChannelSummary> = (0..MAX_CHANNELS_SHOWN + 5)
+            .map(|i| IbcChannelSummary {
+                channel_id: format!("channel-{i}"),
+                ..channels[0].clone()
+            })
+            .collect();
+        let capped = format_ibc_channels("cosmoshub", &many, true);
+        assert!(capped.contains(&format!("`transfer/channel-{}`", MAX_CHANNELS_SHOWN - 1)));
+        assert!(!capped.contains(&format!("`transfer/channel-{}`", MAX_CHANNELS_SHOWN)));
+        assert!(capped.ends_with("… 5 more channels"));
+    }
+
+    #[test]
+    fn test_light_client_status_expiry() {
+        let now = 10 * 86_400;
+        let mut status = LightClientStatus {
+            status: Some("Active".to_string()),
+            trusting_period: Some(14 * 86_400),
+            unbonding_period: Some(21 * 86_400),
+            latest_height: Some("1-12345".to_string()),
+            frozen: false,
+            last_update: Some(now - 3_600),
+        };
+        assert_eq!(status.expires_in(now), Some(14 * 86_400 - 3_600));
+        let formatted = format_light_client_status(&status, now);
+        assert!(formatted.contains("• Status: ✅ Active"));
+        assert!(formatted.contains("• Latest Height: 1-12345"));
+        assert!(formatted.contains("• Trusting Period: 14d"));
+        assert!(formatted.contains("(1h ago)"));
+        assert!(formatted.contains("• Expires In: ~13d 23h"));
+
+        status.last_update = Some(now - 14 * 86_400 + 7_200);
+        let formatted = format_light_client_status(&status, now);
+        assert!(formatted.contains("• Status: ⚠️ Active"));
+        assert!(formatted.contains("• Expires In: 2h, update the client soon"));
+
+        status.status = None;
+        status.last_update = Some(now - 15 * 86_400);
+        let formatted = format_light_client_status(&status, now);
+        assert!(formatted.contains("• Status: ❌ Expired"));
+        assert!(formatted.contains("expired 1d ago"));
+    }
+
+    #[test]
+    fn test_multi_hop_denom_route() {
+        let (hops, base_denom) =
+            split_trace_path("transfer/channel-141/transfer/channel-0/factory/osmo1abc/ufoo");
+        assert_eq!(
+            hops,
+            vec![
+                IbcDenomHop {
+                    port_id: "transfer".to_string(),
+                    channel_id: "channel-141".to_string(),
+                },
+                IbcDenomHop {
+                    port_id: "transfer".to_string(),
+                    channel_id: "channel-0".to_string(),
+                },
+            ]
+        );
+        assert_eq!(base_denom, "factory/osmo1abc/ufoo");
+
+        let trace = IbcDenomTrace { hops, base_denom };
+        assert_eq!(trace.path(), "transfer/channel-141/transfer/channel-0");
+        assert_eq!(
+            trace.full_path(),
+            "transfer/channel-141/transfer/channel-0/factory/osmo1abc/ufoo"
+        );
+        assert_eq!(
+            IbcDenomTrace::from_path(
+                "transfer/channel-141/transfer/channel-0",
+                "factory/osmo1abc/ufoo"
+            ),
+            trace
+        );
+        assert_eq!(IbcDenomTrace::from_path("", "uosmo").full_path(), "uosmo");
+
+        let assetlist = serde_json::json!({
+            "assets": [{
+                "base": "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+                "traces": [{
+                    "type": "ibc",
+                    "counterparty": { "chain_name": "cosmoshub", "base_denom": "uatom" },
+                    "chain": { "path": "transfer/channel-0/uatom" }
+                }]
+            }]
+        });
+        assert_eq!(
+            check_registry_trace(&assetlist, "transfer/channel-0/uatom", Some("cosmoshub")),
+            RegistryTraceCheck::Canonical
+        );
+        assert_eq!(
+            check_registry_trace(
+                &assetlist,
+                "transfer/channel-750/transfer/channel-536/uatom",
+                Some("cosmoshub")
+            ),
+            RegistryTraceCheck::NonCanonical {
+                ibc_denom: "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+                    .to_string(),
+                path: "transfer/channel-0/uatom".to_string(),
+            }
+        );
+        assert_eq!(
+            check_registry_trace(&assetlist, "transfer/channel-0/uosmo", Some("osmosis")),
+            RegistryTraceCheck::NotListed
+        );
+
+        let route = DenomRoute {
+            chain: "osmosis".to_string(),
+            base_denom: "uatom".to_string(),
+            hops: vec![
+                DenomRouteHop {
+                    chain: "osmosis".to_string(),
+                    port_id: "transfer".to_string(),
+                    channel_id: "channel-750".to_string(),
+                    counterparty_chain_id: Some("noble-1".to_string()),
+                    counterparty_chain_name: Some("noble".to_string()),
+                },
+                DenomRouteHop {
+                    chain: "noble".to_string(),
+                    port_id: "transfer".to_string(),
+                    channel_id: "channel-536".to_string(),
+                    counterparty_chain_id: Some("cosmoshub-4".to_string()),
+                    counterparty_chain_name: Some("cosmoshub".to_string()),
+                },
+            ],
+            unresolved_hops: 0,
+        };
+        assert_eq!(route.origin_chain(), Some("cosmoshub"));
+        let formatted = format_denom_route(
+            &route,
+            Some(&RegistryTraceCheck::NonCanonical {
+                ibc_denom: "ibc/27394".to_string(),
+                path: "transfer/channel-0/uatom".to_string(),
+            }),
+        );
+        assert!(formatted.starts_with(
+            "🧭 Route (2 hops):\nosmosis ← channel-750 ← noble ← channel-536 ← cosmoshub (native uatom)"
+        ));
+        assert!(formatted.contains("⚠️ Non-canonical path"));
+
+        let partial = DenomRoute {
+            hops: route.hops[..1].to_vec(),
+            unresolved_hops: 1,
+            ..route
+        };
+        assert_eq!(partial.origin_chain(), None);
+        assert!(format_denom_route(&partial, None)
+            .contains("← noble ← … (1 more hop could not be resolved)"));
+    }
+
+    #[test]
+    fn test_pending_packet_formatting() {
+        let now = 1_700_000_000;
+        let mut report = PendingPacketReport {
+            port_id: "transfer".to_string(),
+            channel_id: "channel-141".to_string(),
+            counterparty_port_id: "transfer".to_string(),
+            counterparty_channel_id: "channel-0".to_string(),
+            outgoing: PacketFlow {
+                source: "cosmoshub".to_string(),
+                destination: "osmosis".to_string(),
+                next_sequence_send: Some(120),
+                next_sequence_recv: Some(118),
+                next_sequence_ack: Some(1),
+                destination_checked: true,
+                ..Default::default()
+            },
+            incoming: PacketFlow {
+                source: "osmosis".to_string(),
+                destination: "cosmoshub".to_string(),
+                next_sequence_send: Some(50),
+                next_sequence_recv: Some(50),
+                destination_checked: true,
+                ..Default::default()
+            },
+        };
+        let idle = format_pending_packets("cosmoshub", &report, now);
+        assert!(idle.contains("Counterparty: osmosis transfer/channel-0"));
+        assert!(idle.contains("✅ No packets in flight"));
+        assert!(idle.contains("• Next sequence send: 120 | ack: 1 | next recv on osmosis: 118"));
+        assert!(
+            idle.contains("• Next sequence send: 50 | ack: unknown | next recv on cosmoshub: 50")
+        );
+        assert!(idle.contains("• No packets awaiting acknowledgement"));
+
+        report.outgoing.commitments = (107..=119).collect();
+        report.outgoing.unreceived_packets = vec![118, 119];
+        report.outgoing.unreceived_acks = vec![117];
+        report.outgoing.oldest_sent_at = Some(now - 7_200);
+        let stuck = format_pending_packets("cosmoshub", &report, now);
+        assert!(stuck.contains("⚠️ 13 packet(s) in flight, oldest is 2h old"));
+        assert!(stuck.contains(
+            "• Awaiting ack: 13 (107, 108, 109, 110, 111, 112, 113, 114, 115, 116, … 3 more)"
+        ));
+        assert!(stuck.contains("• Not received on osmosis: 2 (118, 119)"));
+        assert!(stuck.contains("• Acks not relayed back: 1 (117)"));
+        assert!(stuck.contains("• Oldest pending: #107 sent"));
+
+        report.outgoing.oldest_sent_at = None;
+        report.outgoing.destination_checked = false;
+        let partial = format_pending_packets("cosmoshub", &report, now);
+        assert!(partial.contains("ℹ️ 13 packet(s) in flight"));
+        assert!(partial.contains("• Could not query osmosis, receive and ack status unknown"));
+        assert!(partial.contains("• Oldest pending: #107 (send time unavailable)"));
+    }
+
+    #[test]
+    fn test_asset_denom_computation() {
+        assert_eq!(
+            ibc_denom_hash("transfer/channel-0/uatom"),
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+
+        let channel = |a: &str, b: &str, port: &str, preferred: bool, status: &str| Channel {
+            chain_1: ChannelChain1 {
+                channel_id: a.to_string(),
+                port_id: port.to_string(),
+            },
+            chain_2: ChannelChain2 {
+                channel_id: b.to_string(),
+                port_id: port.to_string(),
+            },
+            tags: Some(Tags {
+                preferred,
+                status: Some(status.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let path = IBCPath {
+            chain_1: Chain1 {
+                chain_name: "cosmoshub".to_string(),
+                ..Default::default()
+            },
+            chain_2: Chain2 {
+                chain_name: "osmosis".to_string(),
+                ..Default::default()
+            },
+            channels: vec![
+                channel("channel-1", "channel-1", "transfer", true, "killed"),
+                channel("channel-9", "channel-9", "icahost", false, "live"),
+                channel("channel-999", "channel-5", "transfer", false, "live"),
+                channel("channel-141", "channel-0", "transfer", true, "live"),
+            ],
+            ..Default::default()
+        };
+
+        let outbound = canonical_transfer_channel(&path, "cosmoshub").unwrap();
+        assert_eq!(outbound.source_channel, "channel-141");
+        assert_eq!(outbound.destination_channel, "channel-0");
+        let lookup = AssetDenomLookup::new(
+            "cosmoshub",
+            "osmosis",
+            Some("ATOM".to_string()),
+            "uatom",
+            outbound,
+            None,
+        );
+        assert_eq!(lookup.full_path, "transfer/channel-0/uatom");
+        assert_eq!(
+            lookup.destination_denom,
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+        let formatted = format_asset_denom_lookup(&AssetDenomLookup {
+            verification: DenomVerification::Supply("1234567".to_string()),
+            ..lookup
+        });
+        assert!(formatted.starts_with("🔎 ATOM from cosmoshub on osmosis"));
+        assert!(formatted
+            .contains("Channel: cosmoshub channel-141 → osmosis transfer/channel-0 (preferred)"));
+        assert!(formatted.contains("✅ Confirmed on osmosis via bank supply (1,234,567)"));
+
+        let inbound = canonical_transfer_channel(&path, "osmosis").unwrap();
+        assert_eq!(inbound.destination_channel, "channel-141");
+        let atom_on_osmosis = IbcDenomTrace::from_path("transfer/channel-0", "uatom");
+        assert_eq!(
+            destination_trace_path(&inbound, "ibc/27394F", Some(&atom_on_osmosis)),
+            "uatom"
+        );
+        let via_noble = IbcDenomTrace::from_path("transfer/channel-750", "uusdc");
+        let lookup = AssetDenomLookup::new(
+            "osmosis",
+            "cosmoshub",
+            None,
+            "ibc/498A",
+            inbound,
+            Some(&via_noble),
+        );
+        assert_eq!(
+            lookup.full_path,
+            "transfer/channel-141/transfer/channel-750/uusdc"
+        );
+        assert!(lookup.destination_denom.starts_with("ibc/"));
+
+        assert!(canonical_transfer_channel(&path, "juno").is_none());
+        let closed = HashSet::from([("osmosis".to_string(), "channel-0".to_string())]);
+        let open_only = without_closed_channels(&path, &closed);
+        assert_eq!(open_only.channels.len(), 3);
+        assert_eq!(
+            canonical_transfer_channel(&open_only, "cosmoshub")
+                .unwrap()
+                .source_channel,
+            "channel-999"
+        );
+        let cosmoshub_ends: HashMap<(String, String), String> =
+            registry_channel_counterparties(&path, "cosmoshub")
+                .into_iter()
+                .collect();
+        assert_eq!(
+            cosmoshub_ends
+                .get(&("transfer".to_string(), "channel-141".to_string()))
+                .map(String::as_str),
+            Some("osmosis")
+        );
+        assert!(!cosmoshub_ends.contains_key(&("transfer".to_string(), "channel-0".to_string())));
+        assert!(
+            registry_channel_counterparties(&path, "osmosis").contains(&(
+                ("icahost".to_string(), "channel-9".to_string()),
+                "cosmoshub".to_string()
+            ))
+        );
+        assert!(registry_channel_counterparties(&path, "juno").is_empty());
+        assert!(format_raw_denom_hash("transfer/channel-0/uatom").contains(
+            "IBC Denom: ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        ));
+    }
+
+    #[test]
+    fn test_global_ibc_denom_index_and_formatting() {
+        let assetlist = serde_json::json!({
+            "assets": [
+                { "base": "uosmo", "symbol": "OSMO" },
+                {
+                    "base": "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+                    "symbol": "ATOM",
+                    "traces": [{
+                        "type": "ibc",
+                        "counterparty": { "chain_name": "cosmoshub", "base_denom": "uatom" },
+                        "chain": { "path": "transfer/channel-0/uatom" }
+                    }]
+                },
+                { "base": "ibc/ABCDEF", "symbol": "OLD" }
+            ]
+        });
+        let denoms = registry_ibc_denoms("osmosis", &assetlist);
+        assert_eq!(denoms.len(), 2);
+        assert_eq!(
+            denoms[0].0,
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+        assert_eq!(
+            denoms[0].1.path.as_deref(),
+            Some("transfer/channel-0/uatom")
+        );
+        assert_eq!(denoms[1].0, "ibc/ABCDEF");
+        assert_eq!(denoms[1].1.path, None);
+
+        let matches = vec![
+            GlobalDenomMatch {
+                asset: denoms[0].1.clone(),
+                trace: Some(IbcDenomTrace::from_path("transfer/channel-0", "uatom")),
+            },
+            GlobalDenomMatch {
+                asset: RegistryIbcAsset {
+                    chain: "neutron".to_string(),
+                    symbol: "ATOM".to_string(),
+                    path: Some("transfer/channel-0/uatom".to_string()),
+                },
+                trace: None,
+            },
+        ];
+        let formatted = format_global_denom_matches(&denoms[0].0, &matches);
+        assert!(formatted.contains("Found on 1 chain\n"));
+        assert!(
+            formatted.contains("✅ osmosis (ATOM)\nPath: transfer/channel-0\nBase Denom: uatom")
+        );
+        assert!(formatted.contains("• neutron (ATOM): transfer/channel-0/uatom"));
+        assert!(format_global_denom_matches("ibc/FFFF", &[]).contains("No registry asset list"));
+    }
+
+    #[test]
+    fn test_light_client_kind_decoding() {
+        use prost::Message as _;
+        use tendermint_proto::google::protobuf::Any;
+
+        let tendermint = TendermintClientState {
+            chain_id: "cosmoshub-4".to_string(),
+            trusting_period: Some(tendermint_proto::google::protobuf::Duration {
+                seconds: 14 * 86_400,
+                nanos: 0,
+            }),
+            ..Default::default()
+        };
+        let kind = light_client_kind(&Any {
+            type_url: "/ibc.lightclients.tendermint.v1.ClientState".to_string(),
+            value: tendermint.encode_to_vec(),
+        });
+        assert_eq!(kind.client_type(), "07-tendermint");
+        assert_eq!(kind.counterparty_chain_id(), Some("cosmoshub-4"));
+
+        let wasm = |data: Vec<u8>| Any {
+            type_url: "/ibc.lightclients.wasm.v1.ClientState".to_string(),
+            value: WasmClientState {
+                data,
+                checksum: vec![0xab; 32],
+                latest_height: None,
+            }
+            .encode_to_vec(),
+        };
+        let wrapped = light_client_kind(&wasm(tendermint.encode_to_vec()));
+        assert_eq!(wrapped.client_type(), "08-wasm");
+        assert_eq!(wrapped.counterparty_chain_id(), Some("cosmoshub-4"));
+        assert_eq!(
+            wrapped.counterparty_label("osmosis-1"),
+            "cosmoshub-4 (wasm-wrapped tendermint)"
+        );
+
+        let ethereum = light_client_kind(&wasm(b"\x0a\x20not a tendermint state".to_vec()));
+        assert_eq!(ethereum.counterparty_chain_id(), None);
+        assert_eq!(
+            ethereum.counterparty_label("osmosis-1"),
+            "wasm client abababababababab"
+        );
+
+        let localhost = light_client_kind(&Any {
+            type_url: "/ibc.lightclients.localhost.v2.ClientState".to_string(),
+            value: Vec::new(),
+        });
+        assert_eq!(localhost.client_type(), "09-localhost");
+        assert_eq!(
+            localhost.counterparty_label("osmosis-1"),
+            "osmosis-1 (localhost)"
+        );
+
+        let unknown = light_client_kind(&Any {
+            type_url: "/ibc.lightclients.example.v1.ClientState".to_string(),
+            value: Vec::new(),
+        });
+        assert_eq!(
+            unknown.client_type(),
+            "ibc.lightclients.example.v1.ClientState"
+        );
+        assert_eq!(
+            unknown,
+            LightClientKind::Unknown {
+                type_url: "/ibc.lightclients.example.v1.ClientState".to_string()
+            }
+        );
+
+        for (type_url, value) in [
+            (
+                "/ibc.lightclients.tendermint.v1.ClientState",
+                TendermintClientState::default().encode_to_vec(),
+            ),
+            (
+                "/ibc.lightclients.solomachine.v3.ClientState",
+                vec![0xff, 0xff, 0xff],
+            ),
+        ] {
+            assert_eq!(
+                light_client_kind(&Any {
+                    type_url: type_url.to_string(),
+                    value,
+                }),
+                LightClientKind::Unknown {
+                    type_url: type_url.to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_escrow_address_and_report_formatting() {
+        assert_eq!(
+            ics20_escrow_address("cosmos", "transfer", "channel-141").unwrap(),
+            "cosmos1x54ltnyg88k0ejmk8ytwrhd3ltm84xehrnlslf"
+        );
+
+        let escrow =
+            |channel_id: &str, counterparty: &str, balances: &[(&str, &str)]| ChannelEscrow {
+                port_id: "transfer".to_string(),
+                channel_id: channel_id.to_string(),
+                counterparty: Some(counterparty.to_string()),
+                address: ics20_escrow_address("cosmos", "transfer", channel_id).unwrap(),
+                balances: balances
+                    .iter()
+                    .map(|(denom, amount)| Balance {
+                        denom: denom.to_string(),
+                        amount: amount.to_string(),
+                    })
+                    .collect(),
+            };
+        let report = EscrowReport {
+            escrows: vec![
+                escrow(
+                    "channel-141",
+                    "osmosis",
+                    &[("uatom", "750000"), ("ibc/ABC", "5")],
+                ),
+                escrow("channel-207", "neutron", &[("uatom", "250000")]),
+            ],
+            total_escrow: HashMap::from([("uatom".to_string(), "1000000".to_string())]),
+            failed_channels: 1,
+        };
+        let labels = HashMap::from([("uatom".to_string(), "ATOM".to_string())]);
+        let units = six_decimal_units("uatom", "ATOM");
+        let formatted = format_escrow_report("cosmoshub", &report, &labels, &units);
+        assert!(formatted.contains("2 transfer channels scanned (1 could not be queried)"));
+        assert!(formatted.contains(
+            "ATOM (uatom)\nEscrowed: 1 ATOM (1000000) over 2 channels | chain-wide total: 1 ATOM (1000000)"
+        ));
+        assert!(formatted.contains("• channel-141 → osmosis: 0.75 ATOM (75.0%)"));
+        assert!(formatted.contains("• channel-207 → neutron: 0.25 ATOM (25.0%)"));
+        assert!(formatted.find("ATOM (uatom)").unwrap() < formatted.find("ibc/ABC").unwrap());
+
+        let single = EscrowReport {
+            escrows: vec![escrow("channel-141", "osmosis", &[])],
+            ..Default::default()
+        };
+        let formatted = format_escrow_report(
+            "cosmoshub",
+            &single,
+            &HashMap::new(),
+            &DenomUnits::default(),
+        );
+        assert!(formatted.contains(
+            "transfer/channel-141 → osmosis\nEscrow Address: cosmos1x54ltnyg88k0ejmk8ytwrhd3ltm84xehrnlslf"
+        ));
+        assert!(formatted.ends_with("Nothing is escrowed."));
+    }
+
+    #[test]
+    fn test_rate_limit_parsing_and_formatting() {
+        let mut key = vec![0x00, 0x04];
+        key.extend_from_slice(b"flow");
+        key.extend_from_slice(&[0x00, 0x0b]);
+        key.extend_from_slice(b"channel-208uatom");
+        assert_eq!(
+            parse_rate_limit_key(&key),
+            Some(("channel-208".to_string(), "uatom".to_string()))
+        );
+        key[5] = b'X';
+        assert_eq!(parse_rate_limit_key(&key), None);
+        assert_eq!(parse_rate_limit_key(&[0x00, 0x04, b'f']), None);
+
+        let value = serde_json::json!([{
+            "quota": {
+                "name": "weekly",
+                "max_percentage_send": 30,
+                "max_percentage_recv": 30,
+                "duration": 604800,
+                "channel_value": "10000000"
+            },
+            "flow": {
+                "inflow": "500000",
+                "outflow": "1500000",
+                "period_end": "1700086400000000000"
+            }
+        }]);
+        let limits = parse_osmosis_rate_limits("channel-208", "uatom", &value);
+        assert_eq!(limits.len(), 1);
+        assert_eq!(limits[0].name.as_deref(), Some("weekly"));
+        assert_eq!(limits[0].max_percent_send, "30");
+        assert_eq!(limits[0].duration_secs, 604_800);
+        assert_eq!(limits[0].period_end, Some(1_700_086_400));
+
+        let lookup = RateLimitLookup::Limits {
+            source: RateLimitSource::OsmosisContract("osmo1contract".to_string()),
+            limits,
+        };
+        let labels = HashMap::from([("uatom".to_string(), "ATOM".to_string())]);
+        let formatted = format_rate_limits(
+            "osmosis",
+            "channel-208",
+            None,
+            &lookup,
+            &labels,
+            &DenomUnits::default(),
+            1_700_000_000,
+        );
+        assert!(formatted.contains("Source: ibc-rate-limit contract osmo1contract"));
+        assert!(formatted.contains("ATOM (uatom) on channel-208 [weekly] (7d window)"));
+        assert!(formatted
+            .contains("• Send: 1,000,000 of 3,000,000 allowed (30% of channel value, 33.3% used)"));
+        assert!(formatted
+            .contains("• Receive: 0 of 3,000,000 allowed (30% of channel value, 0.0% used)"));
+        assert!(formatted.contains("• Resets: 2023-11-15T22:13:20+00:00 (in 1d)"));
+        let expired = format_rate_limits(
+            "osmosis",
+            "channel-208",
+            None,
+            &lookup,
+            &labels,
+            &DenomUnits::default(),
+            1_700_090_000,
+        );
+        assert!(expired.contains(
+            "• Window expired 2023-11-15T22:13:20+00:00 (1h ago); the flow above is from that period"
+        ));
+        assert!(!expired.contains("Resets:"));
+
+        let empty = RateLimitLookup::Limits {
+            source: RateLimitSource::Module("ratelimit.v1".to_string()),
+            limits: Vec::new(),
+        };
+        assert!(format_rate_limits(
+            "stride",
+            "channel-5",
+            Some("ustrd"),
+            &empty,
+            &labels,
+            &DenomUnits::default(),
+            0
+        )
+        .ends_with("No rate limit configured for channel-5 / ustrd."));
+        assert!(format_rate_limits(
+            "cosmoshub",
+            "channel-0",
+            None,
+            &RateLimitLookup::NotSupported,
+            &labels,
+            &DenomUnits::default(),
+            0
+        )
+        .contains("runs no IBC rate limiter"));
+    }
+
+    #[test]
+    fn test_ibc_route_finding_and_ranking() {
+        let to_strings =
+            |items: &[&str]| -> Vec<String> { items.iter().map(|item| item.to_string()).collect() };
+        let chains = to_strings(&[
+            "stride",
+            "osmosis",
+            "cosmoshub",
+            "neutron",
+            "terra-classic",
+            "akash",
+        ]);
+        let path_names = to_strings(&[
+            "cosmoshub-stride",
+            "osmosis-stride",
+            "cosmoshub-neutron",
+            "neutron-osmosis",
+            "akash-stride",
+            "akash-neutron",
+            "osmosis-terra-classic",
+            "not-a-chain",
+        ]);
+        let graph = IbcGraph::from_path_names(&path_names, &chains);
+        assert!(graph.connected("terra-classic", "osmosis"));
+        assert!(!graph.contains("not"));
+
+        // akash is not a PFM hub, so stride → akash → neutron is not a candidate
+        let mut candidates = graph.candidate_routes("stride", "neutron", &PFM_HUBS, MAX_ROUTE_HOPS);
+        candidates.sort();
+        assert_eq!(
+            candidates,
+            vec![
+                to_strings(&["stride", "cosmoshub", "neutron"]),
+                to_strings(&["stride", "osmosis", "neutron"]),
+            ]
+        );
+        let to_akash = graph.candidate_routes("stride", "akash", &PFM_HUBS, MAX_ROUTE_HOPS);
+        assert_eq!(to_akash.len(), 3);
+        assert!(to_akash.contains(&to_strings(&["stride", "akash"])));
+        assert!(to_akash.contains(&to_strings(&["stride", "osmosis", "neutron", "akash"])));
+
+        let hop =
+            |from: &str, to: &str, source: &str, destination: &str, preferred: bool| RouteHop {
+                from: from.to_string(),
+                to: to.to_string(),
+                channel: RegistryTransferChannel {
+                    source_channel: source.to_string(),
+                    destination_port: "transfer".to_string(),
+                    destination_channel: destination.to_string(),
+                    preferred,
+                    status: Some("live".to_string()),
+                },
+            };
+        let mut routes = vec![
+            IbcRoute {
+                hops: vec![
+                    hop("stride", "cosmoshub", "channel-0", "channel-391", false),
+                    hop("cosmoshub", "neutron", "channel-569", "channel-1", false),
+                ],
+            },
+            IbcRoute {
+                hops: vec![
+                    hop("stride", "osmosis", "channel-5", "channel-326", true),
+                    hop("osmosis", "neutron", "channel-874", "channel-10", true),
+                ],
+            },
+        ];
+        rank_ibc_routes(&mut routes);
+        assert_eq!(routes[0].hops[0].to, "osmosis");
+
+        let formatted = format_ibc_routes("stride", "neutron", &routes);
+        assert!(formatted.contains("2 routes found"));
+        assert!(formatted.contains("1. stride → osmosis → neutron (2 hops, via PFM)"));
+        assert!(formatted.contains("• stride channel-5 → osmosis channel-326 ⭐ preferred"));
+        assert!(formatted.contains("• cosmoshub channel-569 → neutron channel-1"));
+        assert!(format_ibc_routes("stride", "akash", &[]).contains("No route found within 3 hops"));
+    }
+
+    #[test]
+    fn test_forward_memo_building() {
+        assert_eq!(to_base_units("1.5", 6), Some("1500000".to_string()));
+        assert_eq!(to_base_units("42", 0), Some("42".to_string()));
+        assert_eq!(to_base_units("0.0000001", 6), None);
+        assert_eq!(to_base_units("1.5", 0), None);
+        assert_eq!(to_base_units("abc", 6), None);
+        assert_eq!(to_base_units("0", 6), None);
+
+        let hop = |from: &str, to: &str, source: &str| RouteHop {
+            from: from.to_string(),
+            to: to.to_string(),
+            channel: RegistryTransferChannel {
+                source_channel: source.to_string(),
+                destination_port: "transfer".to_string(),
+                destination_channel: "channel-0".to_string(),
+                preferred: true,
+                status: None,
+            },
+        };
+        let hops = vec![
+            hop("stride", "osmosis", "channel-5"),
+            hop("osmosis", "cosmoshub", "channel-0"),
+            hop("cosmoshub", "neutron", "channel-569"),
+        ];
+
+        let (first_receiver, memo) = build_forward_memo(&hops, "neutron1receiver", None);
+        let memo = memo.expect("multi-hop transfers carry a memo");
+        assert_eq!(first_receiver, "pfm");
+        assert_eq!(memo["forward"]["receiver"], "pfm");
+        assert_eq!(memo["forward"]["channel"], "channel-0");
+        assert_eq!(memo["forward"]["next"]["forward"]["channel"], "channel-569");
+        assert_eq!(
+            memo["forward"]["next"]["forward"]["receiver"],
+            "neutron1receiver"
+        );
+        assert!(memo["forward"]["next"]["forward"].get("next").is_none());
+        assert!(validate_forward_memo(&memo).is_ok());
+
+        let direct = build_forward_memo(&hops[..1], "osmo1receiver", None);
+        assert_eq!(direct, ("osmo1receiver".to_string(), None));
+
+        let swap = OsmosisSwap {
+            contract: OSMOSIS_CROSSCHAIN_SWAPS_CONTRACT.to_string(),
+            output_denom: "uosmo".to_string(),
+            slippage_percent: "5".to_string(),
+        };
+        let (first_receiver, memo) = build_forward_memo(&hops[..1], "osmo1receiver", Some(&swap));
+        let memo = memo.expect("swaps carry a wasm memo");
+        assert_eq!(first_receiver, OSMOSIS_CROSSCHAIN_SWAPS_CONTRACT);
+        assert_eq!(memo["wasm"]["contract"], OSMOSIS_CROSSCHAIN_SWAPS_CONTRACT);
+        assert_eq!(
+            memo["wasm"]["msg"]["osmosis_swap"]["receiver"],
+            "osmo1receiver"
+        );
+        assert!(validate_forward_memo(&memo).is_ok());
+
+        assert!(validate_forward_memo(
+            &serde_json::json!({"forward": {"receiver": "pfm", "port": "transfer", "channel": "7"}})
+        )
+        .is_err());
+        assert!(validate_forward_memo(&serde_json::json!({"note": "hi"})).is_err());
+    }
+
+    #[test]
+    fn test_registry_channel_consistency() {
+        let path = IBCPath {
+            chain_1: Chain1 {
+                chain_name: "cosmoshub".to_string(),
+                client_id: "07-tendermint-259".to_string(),
+                connection_id: "connection-257".to_string(),
+            },
+            chain_2: Chain2 {
+                chain_name: "osmosis".to_string(),
+                client_id: "07-tendermint-1".to_string(),
+                connection_id: "connection-1".to_string(),
+            },
+            channels: vec![Channel {
+                chain_1: ChannelChain1 {
+                    channel_id: "channel-141".to_string(),
+                    port_id: "transfer".to_string(),
+                },
+                chain_2: ChannelChain2 {
+                    channel_id: "channel-0".to_string(),
+                    port_id: "transfer".to_string(),
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let chain_ids = HashMap::from([
+            ("osmosis".to_string(), "osmosis-1".to_string()),
+            ("cosmoshub".to_string(), "cosmoshub-4".to_string()),
+        ]);
+        let pairs = RegistryChannelEnd::pairs_from_path(&path, "osmosis", &chain_ids);
+        assert_eq!(pairs.len(), 1);
+        let (local, remote) = &pairs[0];
+        assert_eq!(local.chain_name, "osmosis");
+        assert_eq!(local.channel_id, "channel-0");
+        assert_eq!(local.chain_id.as_deref(), Some("osmosis-1"));
+        assert_eq!(remote.client_id, "07-tendermint-259");
+
+        let mut info = IbcChannelInfo {
+            chain_id: "osmosis-1".to_string(),
+            counterparty_chain_id: Some("cosmoshub-4".to_string()),
+            client_kind: LightClientKind::Tendermint {
+                chain_id: "cosmoshub-4".to_string(),
+            },
+            client_id: "07-tendermint-1".to_string(),
+            connection_id: "connection-1".to_string(),
+            counterparty_client_id: "07-tendermint-259".to_string(),
+            counterparty_connection_id: "connection-257".to_string(),
+            channel_id: "channel-0".to_string(),
+            counterparty_channel_id: "channel-141".to_string(),
+            counterparty_port_id: "transfer".to_string(),
+            channel_state: "Open".to_string(),
+            connection_state: "Open".to_string(),
+            client_status: LightClientStatus::default(),
+        };
+        assert!(channel_end_mismatches(local, remote, &info).is_empty());
+
+        info.counterparty_channel_id = "channel-142".to_string();
+        info.channel_state = "Closed".to_string();
+        info.counterparty_chain_id = Some("cosmoshub-3".to_string());
+        let issues = channel_end_mismatches(local, remote, &info);
+        assert_eq!(
+            issues,
+            vec![
+                "osmosis: counterparty channel is channel-142 on-chain, registry says channel-141",
+                "osmosis: channel channel-0 is Closed",
+                "osmosis: client 07-tendermint-1 tracks cosmoshub-3, but cosmoshub is cosmoshub-4 in the registry",
+            ]
+        );
+
+        let report = ConsistencyReport {
+            checks: vec![
+                ChannelConsistency {
+                    local: local.clone(),
+                    remote: remote.clone(),
+                    issues,
+                },
+                ChannelConsistency {
+                    local: remote.clone(),
+                    remote: local.clone(),
+                    issues: Vec::new(),
+                },
+            ],
+            unreadable_paths: vec!["juno".to_string()],
+        };
+        let formatted = format_consistency_report("osmosis", &report);
+        assert!(
+            formatted.contains("2 channels on 2 paths checked: 1 consistent, 1 with mismatches")
+        );
+        assert!(formatted.contains("Unreadable _IBC files: juno"));
+        assert!(formatted
+            .contains("❌ osmosis ↔ cosmoshub (transfer/channel-0 ↔ transfer/channel-141)\n• osmosis: counterparty channel"));
+    }
+
+    /// Display units for a single base denom with the usual six-decimal exponent.
+    fn six_decimal_units(base: &str, symbol: &str) -> DenomUnits {
+        let assets = AssetList {
+            assets: vec![Asset {
+                base: base.to_string(),
+                display: symbol.to_lowercase(),
+                symbol: symbol.to_string(),
+                denom_units: vec![
+                    DenomUnit {
+                        denom: base.to_string(),
+                        exponent: 0,
+                    },
+                    DenomUnit {
+                        denom: symbol.to_lowercase(),
+                        exponent: 6,
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        DenomUnits::from_assets(Some(&assets))
+    }
+
+    #[test]
+    fn test_display_unit_amounts() {
+        assert_eq!(format_display_amount("1500000", 6), "1.5");
+        assert_eq!(format_display_amount("1234567890000", 6), "1,234,567.89");
+        assert_eq!(format_display_amount("42", 6), "0.000042");
+        assert_eq!(format_display_amount("1000000000000000000", 18), "1");
+        assert_eq!(format_display_amount("0", 6), "0");
+        assert_eq!(format_display_amount("12345", 0), "12,345");
+        assert_eq!(format_display_amount("not-a-number", 6), "not-a-number");
+
+        let assets = AssetList {
+            assets: vec![Asset {
+                base: "uatom".to_string(),
+                display: "atom".to_string(),
+                symbol: "ATOM".to_string(),
+                denom_units: vec![
+                    DenomUnit {
+                        denom: "uatom".to_string(),
+                        exponent: 0,
+                    },
+                    DenomUnit {
+                        denom: "atom".to_string(),
+                        exponent: 6,
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let units = DenomUnits::from_assets(Some(&assets));
+        assert_eq!(
+            units.get("uatom"),
+            Some(&DisplayUnit {
+                symbol: "ATOM".to_string(),
+                exponent: 6
+            })
+        );
+        assert_eq!(units.format("2500000", "uatom"), "2.5 ATOM (2500000)");
+        assert_eq!(units.format_short("2500000", "uatom"), "2.5 ATOM");
+        assert_eq!(units.format("2500000", "uosmo"), "2,500,000");
+        assert_eq!(units.format_coin("2500000", "uosmo"), "2,500,000 uosmo");
+
+        let balances = vec![WalletBalance {
+            balance: Balance {
+                denom: "uatom".to_string(),
+                amount: "2500000".to_string(),
+            },
+            ibc_trace: None,
+            asset_label: Some("ATOM".to_string()),
+            display: units.get("uatom").cloned(),
+            usd_price: None,
+        }];
+        let formatted = format_wallet_balances("cosmos1abc", "cosmoshub", &balances, 0, false);
+        assert!(formatted.contains("*ATOM*\nAmount: `2.5 ATOM`\nBase Amount: `2500000`"));
+    }
+
+    #[tokio::test]
+    async fn test_usd_valuation() {
+        let query = |denom: &str| PriceQuery {
+            denom: denom.to_string(),
+            coingecko_id: None,
+        };
+        let queries = vec![query("uatom"), query("uosmo"), query("ufoo")];
+        let primary = FixedPrices(HashMap::from([("uatom".to_string(), 10.0)]));
+        let fallback = FixedPrices(HashMap::from([
+            ("uatom".to_string(), 99.0),
+            ("uosmo".to_string(), 0.5),
+        ]));
+        let sources: Vec<Arc<dyn PriceSource>> = vec![Arc::new(primary), Arc::new(fallback)];
+        let prices = fetch_usd_prices(&sources, &queries).await;
+        assert_eq!(prices.get("uatom"), Some(&10.0));
+        assert_eq!(prices.get("uosmo"), Some(&0.5));
+        assert_eq!(prices.get("ufoo"), None);
+
+        let cache = RegistryCache::new(5).with_price_sources(sources);
+        assert_eq!(cache.usd_prices("cosmoshub", &queries).await, prices);
+        assert_eq!(cache.usd_prices("cosmoshub", &queries).await, prices);
+
+        assert_eq!(format_usd(1234.565), "$1,234.57");
+        assert_eq!(format_usd(0.0), "$0.00");
+        assert_eq!(format_usd(0.004), "<$0.01");
+
+        let atom = DisplayUnit {
+            symbol: "ATOM".to_string(),
+            exponent: 6,
+        };
+        let wallet_balance = |denom: &str, amount: &str, display, usd_price| WalletBalance {
+            balance: Balance {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            },
+            ibc_trace: None,
+            asset_label: None,
+            display,
+            usd_price,
+        };
+        let balances = vec![
+            wallet_balance("uatom", "2500000", Some(atom.clone()), Some(10.0)),
+            wallet_balance("ufoo", "7", None, None),
+            wallet_balance("uatom", "1000000", Some(atom), None),
+        ];
+        assert_eq!(balances[0].usd_value(), Some(25.0));
+        let formatted = format_wallet_balances("cosmos1abc", "cosmoshub", &balances, 0, false);
+        assert!(formatted.contains("Base Amount: `2500000`\nValue: `$25.00`"));
+        assert_eq!(formatted.matches("Value: _unpriced_").count(), 2);
+        assert!(formatted.contains("*Total value:* `$25.00`"));
+        assert!(formatted.ends_with("_2 assets unpriced and not counted\\._"));
+
+        let page = format_wallet_balances("cosmos1abc", "cosmoshub", &balances[..1], 0, true);
+        assert!(page.contains("*Value on this page:* `$25.00`"));
+        assert!(!page.contains("unpriced"));
+    }
+
+    #[test]
+    fn test_address_conversion() {
+        let chain = |name: &str, prefix: &str, slip44| ChainAddressPrefix {
+            chain_name: name.to_string(),
+            bech32_prefix: prefix.to_string(),
+            slip44,
+        };
+        let chains = vec![
+            chain("cosmoshub", "cosmos", 118),
+            chain("evmos", "evmos", 60),
+            chain("osmosis", "osmo", 118),
+            chain("terra2", "terra", 330),
+            chain("cosmoshub-fork", "cosmos", 118),
+        ];
+
+        let bytes: Vec<u8> = (1..=20).collect();
+        let account = DecodedAddress {
+            prefix: Some("cosmos".to_string()),
+            kind: AddressKind::Account,
+            bytes: bytes.clone(),
+        };
+        let cosmos = account.encode("cosmos").unwrap();
+        let osmo = account.encode("osmo").unwrap();
+        assert_eq!(decode_address(&cosmos).unwrap(), account);
+        assert_eq!(source_slip44(&account, &chains), Some(118));
+
+        let formatted = format_address_conversions(&cosmos, &account, &chains);
+        assert!(formatted.contains("*Same key \\(coin type 118\\): 2 prefixes*"));
+        assert!(formatted.contains(&format!("cosmoshub, cosmoshub\\-fork: `{cosmos}`")));
+        assert!(formatted.contains(&format!("osmosis: `{osmo}`")));
+        assert!(formatted.contains("*Different coin type: 2 prefixes*"));
+        assert!(formatted.contains("⚠️ evmos \\(coin type 60\\): `evmos1"));
+        assert!(formatted.contains("⚠️ terra2 \\(coin type 330\\): `terra1"));
+        assert!(!formatted.contains("EVM:"));
+
+        let valoper = DecodedAddress {
+            prefix: Some("osmo".to_string()),
+            kind: AddressKind::Validator,
+            bytes: bytes.clone(),
+        };
+        let osmovaloper = valoper.encode("osmo").unwrap();
+        assert!(osmovaloper.starts_with("osmovaloper1"));
+        assert_eq!(decode_address(&osmovaloper).unwrap(), valoper);
+        assert!(
+            format_address_conversions(&osmovaloper, &valoper, &chains).contains(&format!(
+                "cosmoshub, cosmoshub\\-fork: `{}`",
+                valoper.encode("cosmos").unwrap()
+            ))
+        );
+
+        let hex_address = format!("0x{}", hex::encode(&bytes));
+        let evm = decode_address(&hex_address.to_uppercase().replacen("0X", "0x", 1)).unwrap();
+        assert_eq!(evm.prefix, None);
+        assert_eq!(evm.evm_address(), Some(hex_address.clone()));
+        assert_eq!(source_slip44(&evm, &chains), Some(60));
+        let evmos = evm.encode("evmos").unwrap();
+        let formatted = format_address_conversions(&hex_address, &evm, &chains);
+        assert!(formatted.contains(&format!("EVM: `{hex_address}`")));
+        assert!(formatted.contains(&format!(
+            "*Same key \\(coin type 60\\): 1 prefixes*\n\nevmos: `{evmos}`"
+        )));
+        assert!(decode_address(&evmos)
+            .unwrap()
+            .evm_address()
+            .is_some_and(|address| address == hex_address));
+
+        assert!(decode_address("0x1234").is_err());
+        assert!(decode_address("cosmos1invalid").is_err());
+        let unknown = DecodedAddress {
+            prefix: Some("unknown".to_string()),
+            ..account
+        };
+        let formatted = format_address_conversions("unknown1", &unknown, &chains);
+        assert!(formatted.contains("*Registry chains: 4 prefixes*"));
+        assert!(!formatted.contains("⚠️"));
+    }
+
+    #[test]
+    fn test_portfolio_scan_formatting() {
+        let atom = DisplayUnit {
+            symbol: "ATOM".to_string(),
+            exponent: 6,
+        };
+        let wallet_balance =
+            |denom: &str, amount: &str, label: Option<&str>, display, price| WalletBalance {
+                balance: Balance {
+                    denom: denom.to_string(),
+                    amount: amount.to_string(),
+                },
+                ibc_trace: None,
+                asset_label: label.map(str::to_string),
+                display,
+                usd_price: price,
+            };
+        let scan = PortfolioScan {
+            slip44: 118,
+            scanned: 40,
+            chains: vec![
+                ChainPortfolio {
+                    chain: "osmosis".to_string(),
+                    address: "osmo1abc".to_string(),
+                    balances: vec![wallet_balance(
+                        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+                        "3",
+                        None,
+                        None,
+                        None,
+                    )],
+                    complete: false,
+                },
+                ChainPortfolio {
+                    chain: "cosmoshub".to_string(),
+                    address: "cosmos1abc".to_string(),
+                    balances: vec![
+                        wallet_balance("ufoo", "10", Some("FOO"), None, None),
+                        wallet_balance("uatom", "2000000", Some("ATOM"), Some(atom), Some(5.0)),
+                    ],
+                    complete: true,
+                },
+            ],
+            failed_chains: vec!["zeta".to_string(), "alpha".to_string()],
+        };
+        assert_eq!(scan.chains[1].usd_value(), 10.0);
+
+        let formatted = format_portfolio_scan("cosmos1abc", &scan);
+        assert!(formatted.starts_with(
+            "🌐 *Portfolio for* `cosmos1abc`\nScanned 40 chains with coin type 118; 2 hold balances\\.\n*Total value:* `$10.00`\n_2 assets unpriced and not counted\\._"
+        ));
+        let hub = formatted.find("*cosmoshub* `cosmos1abc`").unwrap();
+        let osmosis = formatted.find("*osmosis* `osmo1abc`").unwrap();
+        assert!(hub < osmosis);
+        assert!(formatted.contains(
+            "• ATOM: `2 ATOM` ≈ `$10.00`\n• FOO: `10` ≈ _unpriced_\nChain value: `$10.00`"
+        ));
+        assert!(formatted.contains(
+            "• ibc/27394FB0\\.\\.\\.: `3` ≈ _unpriced_\n_More balances exist beyond the pages scanned\\._"
+        ));
+        assert!(formatted.ends_with("_Could not query 2 chains: alpha, zeta_"));
+
+        let empty = PortfolioScan {
+            slip44: 60,
+            scanned: 3,
+            chains: Vec::new(),
+            failed_chains: Vec::new(),
+        };
+        assert!(format_portfolio_scan("0xabc", &empty)
+            .ends_with("0 hold balances\\.\n\nNo balances found on any scanned chain\\."));
+    }
+
+    #[test]
+    fn test_wallet_address_checks() {
+        let bytes: Vec<u8> = (1..=20).collect();
+        let encode = |prefix: &str, kind| {
+            DecodedAddress {
+                prefix: None,
+                kind,
+                bytes: bytes.clone(),
+            }
+            .encode(prefix)
+            .unwrap()
+        };
+        let osmo = encode("osmo", AddressKind::Account);
+        let check = |input: &str, evm| check_wallet_address(input, "osmosis", "osmo", evm);
+
+        assert_eq!(
+            check(&osmo, false),
+            WalletAddressCheck::Account(osmo.clone())
+        );
+        assert_eq!(
+            check(&format!("  {}  ", osmo.to_uppercase()), false),
+            WalletAddressCheck::Account(osmo.clone())
+        );
+        let valoper = encode("osmo", AddressKind::Validator);
+        assert_eq!(
+            check(&valoper, false),
+            WalletAddressCheck::Validator(valoper)
+        );
+        assert_eq!(
+            check(&encode("cosmos", AddressKind::Account), false),
+            WalletAddressCheck::OtherPrefix {
+                prefix: "cosmos".to_string(),
+                kind: AddressKind::Account
+            }
+        );
+        assert_eq!(
+            check(&encode("cosmos", AddressKind::Validator), false),
+            WalletAddressCheck::OtherPrefix {
+                prefix: "cosmos".to_string(),
+                kind: AddressKind::Validator
+            }
+        );
+        for prefix in ["osmo", "cosmos"] {
+            assert!(matches!(
+                check(&encode(prefix, AddressKind::Consensus), false),
+                WalletAddressCheck::Invalid(reason) if reason.contains("valcons")
+            ));
+        }
+
+        let mut corrupted = osmo.clone();
+        let last = corrupted.pop().unwrap();
+        corrupted.push(if last == 'q' { 'p' } else { 'q' });
+        assert!(matches!(
+            check(&corrupted, false),
+            WalletAddressCheck::Invalid(_)
+        ));
+        assert!(matches!(
+            check("osmo1", false),
+            WalletAddressCheck::Invalid(_)
+        ));
+        let bech32m =
+            bech32::encode::<bech32::Bech32m>(bech32::Hrp::parse("osmo").unwrap(), &bytes).unwrap();
+        assert_eq!(
+            check(&bech32m, false),
+            WalletAddressCheck::Account(bech32m.clone())
+        );
+
+        let hex_address = format!("0x{}", hex::encode(&bytes));
+        assert!(matches!(
+            check(&hex_address, false),
+            WalletAddressCheck::Invalid(reason) if reason.contains("not an EVM chain")
+        ));
+        assert_eq!(check(&hex_address, true), WalletAddressCheck::Account(osmo));
+    }
+
+    #[test]
+    fn test_validator_info_formatting() {
+        let validator = ValidatorInfo {
+            operator_address: "cosmosvaloper1abc".to_string(),
+            moniker: "Node.Guardian".to_string(),
+            identity: String::new(),
+            website: "https://example.com".to_string(),
+            details: String::new(),
+            status: "Bonded".to_string(),
+            jailed: true,
+            tokens: "2500000".to_string(),
+            delegator_shares: "2500000.000000000000000000".to_string(),
+            commission_rate: "50000000000000000".to_string(),
+            max_commission_rate: "200000000000000000".to_string(),
+            max_change_rate: "0.010000000000000000".to_string(),
+            min_self_delegation: "1000000".to_string(),
+            bond_denom: Some("uatom".to_string()),
+        };
+        let formatted =
+            format_validator_info("cosmoshub", &validator, &six_decimal_units("uatom", "ATOM"));
+        assert_eq!(
+            formatted,
+            "🛡 *Validator on cosmoshub*\n\n*Node\\.Guardian*\nOperator: `cosmosvaloper1abc`\nStatus: Bonded \\(jailed\\)\nTokens: `2.5 ATOM (2500000)`\nCommission: 5% \\(max 20%, max change 1%/day\\)\nMin Self Delegation: `1 ATOM (1000000)`\nWebsite: https://example\\.com"
+        );
+    }
+
+    #[test]
+    fn test_registry_cw20_tokens() {
+        let assetlist = serde_json::json!({
+            "chain_name": "juno",
+            "assets": [
+                { "base": "ujuno", "type_asset": "sdk.coin", "symbol": "JUNO" },
+                {
+                    "base": "cw20:juno1neta",
+                    "type_asset": "cw20",
+                    "address": "juno1neta",
+                    "symbol": "NETA"
+                },
+                { "base": "cw20:juno1legacy", "symbol": "OLD" },
+                { "base": "ibc/ABC", "type_asset": "ics20", "symbol": "ATOM" }
+            ]
+        });
+        assert_eq!(
+            registry_cw20_tokens(&assetlist),
+            vec![
+                RegistryCw20Token {
+                    base: "cw20:juno1neta".to_string(),
+                    contract: "juno1neta".to_string(),
+                },
+                RegistryCw20Token {
+                    base: "cw20:juno1legacy".to_string(),
+                    contract: "juno1legacy".to_string(),
+                },
+            ]
+        );
+        assert!(registry_cw20_tokens(&serde_json::json!({})).is_empty());
+
+        let units = six_decimal_units("cw20:juno1neta", "NETA");
+        let balances = vec![WalletBalance {
+            balance: Balance {
+                denom: "cw20:juno1neta".to_string(),
+                amount: "1250000".to_string(),
+            },
+            ibc_trace: None,
+            asset_label: Some("NETA".to_string()),
+            display: units.get("cw20:juno1neta").cloned(),
+            usd_price: None,
+        }];
+        let formatted = format_wallet_balances("juno1abc", "juno", &balances, 0, false);
+        assert!(formatted.contains(
+            "*NETA*\nAmount: `1.25 NETA`\nBase Amount: `1250000`\nValue: _unpriced_\nDenom: `cw20:juno1neta`"
+        ));
     }
 }
@@ -1,32 +1,100 @@
 use base64::{engine::general_purpose, Engine as _};
-use cosmos_chain_registry::chain;
+use cosmos_chain_registry::{assets::Asset, chain, AssetList, ChainInfo, IBCPath};
 use cosmos_sdk_proto::cosmos::{
-    bank::v1beta1::{query_client::QueryClient as BankQueryClient, QueryAllBalancesRequest},
+    auth::v1beta1::{
+        query_client::QueryClient as AuthQueryClient, BaseAccount, ModuleAccount,
+        QueryAccountRequest,
+    },
+    authz::v1beta1::{
+        query_client::QueryClient as AuthzQueryClient, GenericAuthorization, GrantAuthorization,
+        QueryGranteeGrantsRequest, QueryGranterGrantsRequest,
+    },
+    bank::v1beta1::{
+        query_client::QueryClient as BankQueryClient, QueryAllBalancesRequest,
+        QueryParamsRequest as BankQueryParamsRequest, QuerySendEnabledRequest,
+        QuerySupplyOfRequest, SendAuthorization,
+    },
     base::{
         query::v1beta1::PageRequest,
         tendermint::v1beta1::{
-            service_client::ServiceClient as TendermintServiceClient, GetLatestBlockRequest,
-            GetNodeInfoRequest,
+            service_client::ServiceClient as TendermintServiceClient, AbciQueryRequest,
+            GetLatestBlockRequest, GetNodeInfoRequest,
+        },
+    },
+    distribution::v1beta1::{
+        query_client::QueryClient as DistributionQueryClient,
+        QueryParamsRequest as DistributionQueryParamsRequest,
+    },
+    feegrant::v1beta1::{
+        query_client::QueryClient as FeegrantQueryClient, AllowedMsgAllowance, BasicAllowance,
+        Grant as FeegrantGrant, PeriodicAllowance, QueryAllowancesByGranterRequest,
+        QueryAllowancesRequest,
+    },
+    gov::{
+        v1::{
+            query_client::QueryClient as GovV1QueryClient,
+            QueryParamsRequest as GovV1QueryParamsRequest,
+        },
+        v1beta1::{
+            query_client::QueryClient as GovV1beta1QueryClient,
+            QueryParamsRequest as GovV1beta1QueryParamsRequest,
         },
     },
+    slashing::v1beta1::{
+        query_client::QueryClient as SlashingQueryClient,
+        QueryParamsRequest as SlashingQueryParamsRequest,
+    },
+    staking::v1beta1::{
+        query_client::QueryClient as StakingQueryClient,
+        stake_authorization::Policy as StakeAuthorizationPolicy,
+        AuthorizationType as StakeAuthorizationType, BondStatus,
+        QueryParamsRequest as StakingQueryParamsRequest, QueryValidatorRequest, StakeAuthorization,
+    },
+    tx::v1beta1::{service_client::ServiceClient as TxServiceClient, GetTxsEventRequest},
+    vesting::v1beta1::{
+        BaseVestingAccount, ContinuousVestingAccount, DelayedVestingAccount,
+        PeriodicVestingAccount, PermanentLockedAccount,
+    },
 };
+use futures::{future::BoxFuture, stream, StreamExt};
 use ibc_proto::ibc::{
     applications::transfer::v1::{
         query_client::QueryClient as TransferQueryClient, QueryDenomTraceRequest,
+        QueryParamsRequest as TransferQueryParamsRequest, QueryTotalEscrowForDenomRequest,
+        TransferAuthorization,
     },
     core::{
         channel::v1::{
-            query_client::QueryClient as ChannelQueryClient, QueryChannelClientStateRequest,
-            QueryChannelRequest,
+            query_client::QueryClient as ChannelQueryClient, Order as IbcChannelOrder,
+            QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
+            QueryNextSequenceReceiveRequest, QueryNextSequenceSendRequest,
+            QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest,
+            QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest, State as IbcChannelState,
+        },
+        client::v1::{
+            query_client::QueryClient as ClientQueryClient, Height as IbcHeight,
+            QueryClientStatesRequest, QueryClientStatusRequest, QueryConsensusStateRequest,
         },
         connection::v1::{
             query_client::QueryClient as ConnectionQueryClient, QueryConnectionRequest,
+            QueryConnectionsRequest, State as IbcConnectionState,
         },
     },
-    lightclients::tendermint::v1::ClientState as TendermintClientState,
+    lightclients::{
+        localhost::v2::ClientState as LocalhostClientState,
+        solomachine::v3::ClientState as SoloMachineClientState,
+        tendermint::v1::{
+            ClientState as TendermintClientState, ConsensusState as TendermintConsensusState,
+        },
+        wasm::v1::{ClientState as WasmClientState, ConsensusState as WasmConsensusState},
+    },
 };
 use osmosis_std::{
     shim::{Any as OsmosisAny, Duration as OsmosisDuration, Timestamp as OsmosisTimestamp},
+    types::cosmwasm::wasm::v1::{
+        QueryAllContractStateRequest, QueryAllContractStateResponse,
+        QuerySmartContractStateRequest, QuerySmartContractStateResponse,
+    },
     types::osmosis::{
         concentratedliquidity::v1beta1::{
             IncentiveRecordsRequest, IncentiveRecordsResponse, Pool as ConcentratedPool,
@@ -35,18 +103,26 @@ use osmosis_std::{
         gamm::{
             poolmodels::stableswap::v1beta1::Pool as StableswapPool, v1beta1::Pool as GammPool,
         },
+        ibcratelimit::v1beta1::{
+            ParamsRequest as RateLimitParamsRequest, ParamsResponse as RateLimitParamsResponse,
+        },
         incentives::{GaugeByIdRequest, GaugeByIdResponse},
         poolincentives::v1beta1::{QueryGaugeIdsRequest, QueryGaugeIdsResponse},
         poolmanager::v1beta1::{
             PoolRequest as PoolManagerPoolRequest, PoolResponse as PoolManagerPoolResponse,
         },
-        txfees::v1beta1::{QueryDenomSpotPriceRequest, QueryDenomSpotPriceResponse},
+        txfees::v1beta1::{
+            QueryDenomSpotPriceRequest, QueryDenomSpotPriceResponse, QueryEipBaseFeeRequest,
+            QueryEipBaseFeeResponse,
+        },
     },
 };
 use prost::Message as _;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
 use std::time::Duration;
 use tonic::{
     codec::ProstCodec,
@@ -200,13 +276,100 @@ pub fn get_polkachu_installation_url(chain_name: &str) -> Option<String> {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IbcChannelInfo {
     pub chain_id: String,
-    pub counterparty_chain_id: String,
+    /// Set when the light client tracks a Cosmos chain, directly or wrapped in 08-wasm.
+    pub counterparty_chain_id: Option<String>,
+    pub client_kind: LightClientKind,
     pub client_id: String,
     pub connection_id: String,
     pub counterparty_client_id: String,
     pub counterparty_connection_id: String,
     pub channel_id: String,
     pub counterparty_channel_id: String,
+    pub counterparty_port_id: String,
+    pub channel_state: String,
+    pub connection_state: String,
+    pub client_status: LightClientStatus,
+}
+
+/// What a light client tracks, decoded according to its `ClientState` type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LightClientKind {
+    Tendermint {
+        chain_id: String,
+    },
+    /// 08-wasm client; `wrapped_chain_id` is set when the contract wraps a Tendermint client.
+    Wasm {
+        checksum: String,
+        wrapped_chain_id: Option<String>,
+    },
+    SoloMachine {
+        diversifier: Option<String>,
+    },
+    Localhost,
+    Unknown {
+        type_url: String,
+    },
+}
+
+impl LightClientKind {
+    pub fn client_type(&self) -> &str {
+        match self {
+            LightClientKind::Tendermint { .. } => "07-tendermint",
+            LightClientKind::Wasm { .. } => "08-wasm",
+            LightClientKind::SoloMachine { .. } => "06-solomachine",
+            LightClientKind::Localhost => "09-localhost",
+            LightClientKind::Unknown { type_url } => type_url.trim_start_matches('/'),
+        }
+    }
+
+    pub fn counterparty_chain_id(&self) -> Option<&str> {
+        match self {
+            LightClientKind::Tendermint { chain_id } => Some(chain_id),
+            LightClientKind::Wasm {
+                wrapped_chain_id, ..
+            } => wrapped_chain_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The best identifier for the other side: a chain ID, a wasm checksum, a solo machine
+    /// diversifier, or the chain itself for 09-localhost.
+    pub fn counterparty_label(&self, own_chain_id: &str) -> String {
+        match self {
+            LightClientKind::Tendermint { chain_id } => chain_id.clone(),
+            LightClientKind::Wasm {
+                wrapped_chain_id: Some(chain_id),
+                ..
+            } => format!("{chain_id} (wasm-wrapped tendermint)"),
+            LightClientKind::Wasm { checksum, .. } => {
+                format!("wasm client {}", &checksum[..checksum.len().min(16)])
+            }
+            LightClientKind::SoloMachine {
+                diversifier: Some(diversifier),
+            } => format!("solo machine {diversifier}"),
+            LightClientKind::SoloMachine { diversifier: None } => "solo machine".to_string(),
+            LightClientKind::Localhost => format!("{own_chain_id} (localhost)"),
+            LightClientKind::Unknown { type_url } => format!("unknown ({type_url})"),
+        }
+    }
+}
+
+/// Health of the light client this chain keeps for the counterparty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LightClientStatus {
+    pub status: Option<String>,
+    pub trusting_period: Option<i64>,
+    pub unbonding_period: Option<i64>,
+    pub latest_height: Option<String>,
+    pub frozen: bool,
+    pub last_update: Option<i64>,
+}
+
+impl LightClientStatus {
+    /// Seconds until the trusting period lapses without a client update; negative once expired.
+    pub fn expires_in(&self, now: i64) -> Option<i64> {
+        Some(self.last_update? + self.trusting_period? - now)
+    }
 }
 
 fn normalize_grpc_uri(address: &str) -> Option<String> {
@@ -283,11 +446,54 @@ pub async fn find_healthy_grpc_endpoint(endpoints: &[chain::Grpc]) -> Option<Str
     None
 }
 
-pub async fn query_ibc_denom_grpc(
-    grpc_endpoint: &str,
+/// `Denom` query messages shared by ibc-go v9 (`transfer.v2`) and v10 (`transfer.v1`), which
+/// replace `DenomTrace` with structured hops. Not yet generated in `ibc-proto`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryDenomRequest {
+    #[prost(string, tag = "1")]
+    hash: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryDenomResponse {
+    #[prost(message, optional, tag = "1")]
+    denom: Option<TransferDenom>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct TransferDenom {
+    #[prost(string, tag = "1")]
+    base: String,
+    #[prost(message, repeated, tag = "3")]
+    trace: Vec<TransferHop>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct TransferHop {
+    #[prost(string, tag = "1")]
+    port_id: String,
+    #[prost(string, tag = "2")]
+    channel_id: String,
+}
+
+/// Newest first: ibc-go v10, then v9. Older chains only serve `DenomTrace`.
+const DENOM_QUERY_PATHS: [&str; 2] = [
+    "/ibc.applications.transfer.v1.Query/Denom",
+    "/ibc.applications.transfer.v2.Query/Denom",
+];
+
+fn is_unimplemented(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<tonic::Status>()
+        .map(|status| status.code() == tonic::Code::Unimplemented)
+        .unwrap_or(false)
+}
+
+async fn query_denom_trace_legacy(
+    transport: Channel,
     ibc_hash: &str,
 ) -> anyhow::Result<IbcDenomTrace> {
-    let mut client = TransferQueryClient::new(connect_grpc(grpc_endpoint).await?);
+    let mut client = TransferQueryClient::new(transport);
     let response = client
         .denom_trace(QueryDenomTraceRequest {
             hash: ibc_hash.to_string(),
@@ -298,10 +504,60 @@ pub async fn query_ibc_denom_grpc(
         .denom_trace
         .ok_or_else(|| anyhow::anyhow!("IBC denom trace not found"))?;
 
-    Ok(IbcDenomTrace {
-        path: trace.path,
-        base_denom: trace.base_denom,
-    })
+    Ok(IbcDenomTrace::from_path(&trace.path, &trace.base_denom))
+}
+
+/// Resolves an IBC hash with whichever denom API the node serves, skipping the ones it reports
+/// as unimplemented.
+pub async fn query_ibc_denom_grpc(
+    grpc_endpoint: &str,
+    ibc_hash: &str,
+) -> anyhow::Result<IbcDenomTrace> {
+    let transport = connect_grpc(grpc_endpoint).await?;
+    let mut first_error = None;
+
+    for path in DENOM_QUERY_PATHS {
+        match grpc_unary::<QueryDenomRequest, QueryDenomResponse>(
+            transport.clone(),
+            path,
+            QueryDenomRequest {
+                hash: ibc_hash.to_string(),
+            },
+        )
+        .await
+        {
+            Ok(response) => {
+                let denom = response
+                    .denom
+                    .ok_or_else(|| anyhow::anyhow!("IBC denom not found"))?;
+                return Ok(IbcDenomTrace {
+                    hops: denom
+                        .trace
+                        .into_iter()
+                        .map(|hop| IbcDenomHop {
+                            port_id: hop.port_id,
+                            channel_id: hop.channel_id,
+                        })
+                        .collect(),
+                    base_denom: denom.base,
+                });
+            }
+            Err(e) if is_unimplemented(&e) => {
+                log::debug!("{} not served by {}", path, grpc_endpoint);
+            }
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match query_denom_trace_legacy(transport, ibc_hash).await {
+        Ok(trace) => Ok(trace),
+        Err(e) => Err(match first_error {
+            Some(first_error) if is_unimplemented(&e) => first_error,
+            _ => e,
+        }),
+    }
 }
 
 pub async fn query_ibc_denom_grpc_first(
@@ -324,24 +580,243 @@ pub async fn query_ibc_denom_grpc_first(
     Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
 }
 
-fn decode_tendermint_client_chain_id(
+/// Decoded view of a `ClientState`: what the client tracks, the health fields the state
+/// itself carries, and whether its consensus states hold a Tendermint timestamp.
+struct DecodedClientState {
+    kind: LightClientKind,
+    status: LightClientStatus,
+    latest_height: Option<IbcHeight>,
+    tendermint_consensus: bool,
+}
+
+fn tendermint_client_status(client_state: &TendermintClientState) -> LightClientStatus {
+    LightClientStatus {
+        trusting_period: client_state.trusting_period.as_ref().map(|d| d.seconds),
+        unbonding_period: client_state.unbonding_period.as_ref().map(|d| d.seconds),
+        latest_height: client_state.latest_height.as_ref().map(format_ibc_height),
+        frozen: client_state
+            .frozen_height
+            .as_ref()
+            .is_some_and(|height| height.revision_height > 0),
+        ..Default::default()
+    }
+}
+
+fn format_ibc_height(height: &IbcHeight) -> String {
+    format!("{}-{}", height.revision_number, height.revision_height)
+}
+
+/// 08-wasm contracts that wrap a Tendermint client store its state either as an `Any` or as
+/// the raw message. Anything without a plausible chain ID and trusting period is rejected.
+fn decode_wrapped_tendermint(data: &[u8]) -> Option<TendermintClientState> {
+    let client_state = match tendermint_proto::google::protobuf::Any::decode(data) {
+        Ok(any)
+            if any
+                .type_url
+                .ends_with("ibc.lightclients.tendermint.v1.ClientState") =>
+        {
+            TendermintClientState::decode(any.value.as_slice()).ok()?
+        }
+        _ => TendermintClientState::decode(data).ok()?,
+    };
+
+    let plausible_chain_id = !client_state.chain_id.is_empty()
+        && client_state.chain_id.chars().all(|c| c.is_ascii_graphic());
+    (plausible_chain_id && client_state.trusting_period.is_some()).then_some(client_state)
+}
+
+/// Decodes a client state for display. A recognised type whose bytes do not decode is
+/// reported as unknown, so the connection and channel data around it still show.
+fn decode_client_state(
     client_state: &tendermint_proto::google::protobuf::Any,
-) -> anyhow::Result<String> {
-    if !client_state
+) -> DecodedClientState {
+    try_decode_client_state(client_state).unwrap_or_else(|e| {
+        log::warn!(
+            "Could not decode {} client state: {}",
+            client_state.type_url,
+            e
+        );
+        DecodedClientState {
+            kind: LightClientKind::Unknown {
+                type_url: client_state.type_url.clone(),
+            },
+            status: LightClientStatus::default(),
+            latest_height: None,
+            tendermint_consensus: false,
+        }
+    })
+}
+
+fn try_decode_client_state(
+    client_state: &tendermint_proto::google::protobuf::Any,
+) -> anyhow::Result<DecodedClientState> {
+    let type_url = client_state.type_url.as_str();
+    let value = client_state.value.as_slice();
+
+    if type_url.ends_with("ibc.lightclients.tendermint.v1.ClientState") {
+        let state = TendermintClientState::decode(value)?;
+        if state.chain_id.is_empty() {
+            return Err(anyhow::anyhow!("decoded client state missing chain ID"));
+        }
+        return Ok(DecodedClientState {
+            status: tendermint_client_status(&state),
+            latest_height: state.latest_height,
+            kind: LightClientKind::Tendermint {
+                chain_id: state.chain_id,
+            },
+            tendermint_consensus: true,
+        });
+    }
+
+    if type_url.ends_with("ibc.lightclients.wasm.v1.ClientState") {
+        let state = WasmClientState::decode(value)?;
+        let wrapped = decode_wrapped_tendermint(&state.data);
+        let mut status = wrapped
+            .as_ref()
+            .map(tendermint_client_status)
+            .unwrap_or_default();
+        status.latest_height = state.latest_height.as_ref().map(format_ibc_height);
+        return Ok(DecodedClientState {
+            status,
+            latest_height: state.latest_height,
+            tendermint_consensus: wrapped.is_some(),
+            kind: LightClientKind::Wasm {
+                checksum: hex::encode(&state.checksum),
+                wrapped_chain_id: wrapped.map(|state| state.chain_id),
+            },
+        });
+    }
+
+    if type_url.ends_with("ibc.lightclients.solomachine.v3.ClientState") {
+        let state = SoloMachineClientState::decode(value)?;
+        return Ok(DecodedClientState {
+            status: LightClientStatus {
+                latest_height: Some(format!("sequence {}", state.sequence)),
+                frozen: state.is_frozen,
+                ..Default::default()
+            },
+            latest_height: None,
+            tendermint_consensus: false,
+            kind: LightClientKind::SoloMachine {
+                diversifier: state
+                    .consensus_state
+                    .and_then(|consensus| non_empty(consensus.diversifier)),
+            },
+        });
+    }
+
+    if type_url.ends_with("ibc.lightclients.localhost.v2.ClientState") {
+        let state = LocalhostClientState::decode(value)?;
+        return Ok(DecodedClientState {
+            status: LightClientStatus {
+                latest_height: state.latest_height.as_ref().map(format_ibc_height),
+                ..Default::default()
+            },
+            latest_height: None,
+            tendermint_consensus: false,
+            kind: LightClientKind::Localhost,
+        });
+    }
+
+    Ok(DecodedClientState {
+        kind: LightClientKind::Unknown {
+            type_url: type_url.to_string(),
+        },
+        status: LightClientStatus::default(),
+        latest_height: None,
+        tendermint_consensus: false,
+    })
+}
+
+pub fn light_client_kind(
+    client_state: &tendermint_proto::google::protobuf::Any,
+) -> LightClientKind {
+    decode_client_state(client_state).kind
+}
+
+/// Reads the consensus timestamp at the client's latest height, unwrapping 08-wasm
+/// consensus states that carry a Tendermint one.
+fn decode_consensus_timestamp(
+    consensus_state: &tendermint_proto::google::protobuf::Any,
+) -> Option<i64> {
+    let tendermint_bytes = if consensus_state
         .type_url
-        .ends_with("ibc.lightclients.tendermint.v1.ClientState")
+        .ends_with("ibc.lightclients.tendermint.v1.ConsensusState")
     {
-        return Err(anyhow::anyhow!(
-            "unsupported client state type {}",
-            client_state.type_url
-        ));
+        consensus_state.value.clone()
+    } else if consensus_state
+        .type_url
+        .ends_with("ibc.lightclients.wasm.v1.ConsensusState")
+    {
+        let data = WasmConsensusState::decode(consensus_state.value.as_slice())
+            .ok()?
+            .data;
+        match tendermint_proto::google::protobuf::Any::decode(data.as_slice()) {
+            Ok(any)
+                if any
+                    .type_url
+                    .ends_with("ibc.lightclients.tendermint.v1.ConsensusState") =>
+            {
+                any.value
+            }
+            _ => data,
+        }
+    } else {
+        return None;
+    };
+
+    TendermintConsensusState::decode(tendermint_bytes.as_slice())
+        .ok()?
+        .timestamp
+        .map(|timestamp| timestamp.seconds)
+}
+
+/// Starts from what the client state carries, then asks the chain for its status and, for
+/// Tendermint-backed clients, the timestamp of the latest consensus state. Best effort.
+async fn query_light_client_status(
+    transport: Channel,
+    client_id: &str,
+    client_state: &DecodedClientState,
+) -> LightClientStatus {
+    let mut status = client_state.status.clone();
+
+    let mut client = ClientQueryClient::new(transport);
+    match client
+        .client_status(QueryClientStatusRequest {
+            client_id: client_id.to_string(),
+        })
+        .await
+    {
+        Ok(response) => status.status = non_empty(response.into_inner().status),
+        Err(e) => log::warn!("ClientStatus query failed for {client_id}: {e}"),
     }
 
-    let client_state = TendermintClientState::decode(client_state.value.as_slice())?;
-    if client_state.chain_id.is_empty() {
-        return Err(anyhow::anyhow!("decoded client state missing chain ID"));
+    let Some(latest_height) = client_state
+        .latest_height
+        .filter(|_| client_state.tendermint_consensus)
+    else {
+        return status;
+    };
+    match client
+        .consensus_state(QueryConsensusStateRequest {
+            client_id: client_id.to_string(),
+            revision_number: latest_height.revision_number,
+            revision_height: latest_height.revision_height,
+            latest_height: false,
+        })
+        .await
+    {
+        Ok(response) => {
+            status.last_update = response
+                .into_inner()
+                .consensus_state
+                .as_ref()
+                .and_then(decode_consensus_timestamp);
+        }
+        Err(e) => log::warn!("ConsensusState query failed for {client_id}: {e}"),
     }
-    Ok(client_state.chain_id)
+
+    status
 }
 
 pub async fn query_ibc_channel_info_grpc(
@@ -373,12 +848,12 @@ pub async fn query_ibc_channel_info_grpc(
         .channel
         .ok_or_else(|| anyhow::anyhow!("Channel {} not found on port {}", channel_id, port_id))?;
 
-    let counterparty_channel_id = channel
+    let counterparty_end = channel
         .counterparty
         .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("Counterparty channel not found"))?
-        .channel_id
-        .clone();
+        .ok_or_else(|| anyhow::anyhow!("Counterparty channel not found"))?;
+    let counterparty_channel_id = counterparty_end.channel_id.clone();
+    let counterparty_port_id = counterparty_end.port_id.clone();
 
     let connection_id = channel
         .connection_hops
@@ -386,7 +861,7 @@ pub async fn query_ibc_channel_info_grpc(
         .ok_or_else(|| anyhow::anyhow!("Connection ID not found"))?
         .clone();
 
-    let mut connection_client = ConnectionQueryClient::new(transport);
+    let mut connection_client = ConnectionQueryClient::new(transport.clone());
     let connection_response = connection_client
         .connection(QueryConnectionRequest {
             connection_id: connection_id.clone(),
@@ -398,6 +873,7 @@ pub async fn query_ibc_channel_info_grpc(
         .ok_or_else(|| anyhow::anyhow!("Connection {} not found", connection_id))?;
 
     let client_id = connection.client_id.clone();
+    let connection_state = connection_state_label(connection.state);
     let counterparty = connection
         .counterparty
         .ok_or_else(|| anyhow::anyhow!("Counterparty connection not found"))?;
@@ -415,17 +891,27 @@ pub async fn query_ibc_channel_info_grpc(
     let client_state = identified_client_state
         .client_state
         .ok_or_else(|| anyhow::anyhow!("Counterparty client state payload not found"))?;
-    let counterparty_chain_id = decode_tendermint_client_chain_id(&client_state)?;
+    let decoded_client_state = decode_client_state(&client_state);
+    let client_status =
+        query_light_client_status(transport, &client_id, &decoded_client_state).await;
 
     Ok(IbcChannelInfo {
         chain_id,
-        counterparty_chain_id,
+        counterparty_chain_id: decoded_client_state
+            .kind
+            .counterparty_chain_id()
+            .map(str::to_string),
+        client_kind: decoded_client_state.kind,
         client_id,
         connection_id,
         counterparty_client_id: counterparty.client_id,
         counterparty_connection_id: counterparty.connection_id,
         channel_id: channel_id.to_string(),
         counterparty_channel_id,
+        counterparty_port_id,
+        channel_state: channel_state_label(channel.state),
+        connection_state,
+        client_status,
     })
 }
 
@@ -450,432 +936,5908 @@ pub async fn query_ibc_channel_info_grpc_first(
     Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
 }
 
-pub fn extract_channel_from_path(path: &str) -> Option<String> {
-    // Parse IBC path like "transfer/channel-23/transfer/channel-0"
-    // We want to extract the first channel after "transfer/"
-    let parts: Vec<&str> = path.split('/').collect();
-
-    for (i, part) in parts.iter().enumerate() {
-        if *part == "transfer" && i + 1 < parts.len() {
-            let channel = parts[i + 1];
-            if channel.starts_with("channel-") {
-                return Some(channel.to_string());
-            }
-        }
-    }
-    None
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IbcChannelSummary {
+    pub port_id: String,
+    pub channel_id: String,
+    pub state: String,
+    pub ordering: String,
+    pub version: String,
+    pub connection_id: String,
+    pub client_id: Option<String>,
+    pub counterparty_port_id: String,
+    pub counterparty_channel_id: String,
+    pub counterparty_chain_id: Option<String>,
+    pub counterparty_chain_name: Option<String>,
 }
 
-pub fn format_channel_input(input: &str) -> String {
-    // If user enters just a number, format it as channel-N
-    // If user enters channel-N, use as is
-    if input.starts_with("channel-") {
-        input.to_string()
-    } else if input.parse::<u32>().is_ok() {
-        format!("channel-{}", input)
-    } else {
-        input.to_string()
+impl IbcChannelSummary {
+    pub fn is_open_transfer(&self) -> bool {
+        self.port_id == "transfer" && self.state == "Open"
     }
 }
 
-pub fn escape_markdown(text: &str) -> String {
-    text.chars()
-        .map(|c| match c {
-            '_' | '*' | '[' | ']' | '(' | ')' | '~' | '`' | '>' | '#' | '+' | '-' | '=' | '|'
-            | '{' | '}' | '.' | '!' => format!("\\{}", c),
-            _ => c.to_string(),
-        })
-        .collect()
+const IBC_LIST_PAGE_LIMIT: u64 = 200;
+const MAX_IBC_LIST_PAGES: usize = 25;
+
+fn channel_state_label(state: i32) -> String {
+    match IbcChannelState::try_from(state) {
+        Ok(IbcChannelState::Init) => "Init",
+        Ok(IbcChannelState::Tryopen) => "TryOpen",
+        Ok(IbcChannelState::Open) => "Open",
+        Ok(IbcChannelState::Closed) => "Closed",
+        Ok(IbcChannelState::Flushing) => "Flushing",
+        Ok(IbcChannelState::Flushcomplete) => "FlushComplete",
+        _ => "Uninitialized",
+    }
+    .to_string()
 }
 
-/// ABCI info response containing version and block data
-#[derive(Debug, Clone)]
-pub struct AbciInfo {
-    pub version: String,
-    pub last_block_height: String,
-    pub last_block_app_hash: String,
+fn connection_state_label(state: i32) -> String {
+    match IbcConnectionState::try_from(state) {
+        Ok(IbcConnectionState::Init) => "Init",
+        Ok(IbcConnectionState::Tryopen) => "TryOpen",
+        Ok(IbcConnectionState::Open) => "Open",
+        _ => "Uninitialized",
+    }
+    .to_string()
 }
 
-fn bytes_to_upper_hex(bytes: &[u8]) -> String {
-    if bytes.is_empty() {
-        return "Unknown".to_string();
+fn channel_order_label(ordering: i32) -> String {
+    match IbcChannelOrder::try_from(ordering) {
+        Ok(IbcChannelOrder::Unordered) => "Unordered",
+        Ok(IbcChannelOrder::Ordered) => "Ordered",
+        _ => "None",
     }
+    .to_string()
+}
 
-    bytes.iter().map(|byte| format!("{byte:02X}")).collect()
+fn channel_sort_key(channel_id: &str) -> u64 {
+    channel_id
+        .strip_prefix("channel-")
+        .and_then(|number| number.parse().ok())
+        .unwrap_or(u64::MAX)
 }
 
-pub async fn query_abci_info_grpc(grpc_endpoint: &str) -> Option<AbciInfo> {
-    let channel = connect_grpc(grpc_endpoint).await.ok()?;
-    let mut client = TendermintServiceClient::new(channel);
+/// Maps every connection on the chain to its client ID in a handful of paged calls.
+async fn query_connection_clients(transport: Channel) -> anyhow::Result<HashMap<String, String>> {
+    let mut client = ConnectionQueryClient::new(transport);
+    let mut clients = HashMap::new();
+    let mut key = Vec::new();
 
-    let node_info = client
-        .get_node_info(GetNodeInfoRequest {})
-        .await
-        .ok()?
-        .into_inner();
-    let version = node_info
-        .application_version
-        .map(|version| {
-            if !version.version.is_empty() {
-                version.version
-            } else if !version.cosmos_sdk_version.is_empty() {
-                version.cosmos_sdk_version
-            } else {
-                "Unknown".to_string()
-            }
-        })
-        .unwrap_or_else(|| "Unknown".to_string());
+    for _ in 0..MAX_IBC_LIST_PAGES {
+        let response = client
+            .connections(QueryConnectionsRequest {
+                pagination: page_request(key, IBC_LIST_PAGE_LIMIT),
+            })
+            .await?
+            .into_inner();
+        clients.extend(
+            response
+                .connections
+                .into_iter()
+                .map(|connection| (connection.id, connection.client_id)),
+        );
+        match next_page_key(response.pagination) {
+            Some(next_key) => key = next_key,
+            None => break,
+        }
+    }
 
-    let latest_block = client
-        .get_latest_block(GetLatestBlockRequest {})
-        .await
-        .ok()?
-        .into_inner();
+    Ok(clients)
+}
 
-    if let Some(sdk_block) = latest_block.sdk_block {
-        let header = sdk_block.header?;
-        return Some(AbciInfo {
-            version,
-            last_block_height: header.height.to_string(),
-            last_block_app_hash: bytes_to_upper_hex(&header.app_hash),
-        });
+/// Decodes the counterparty chain ID of every light client in a handful of paged calls,
+/// instead of one `ChannelClientState` query per channel.
+async fn query_client_chain_ids(transport: Channel) -> anyhow::Result<HashMap<String, String>> {
+    let mut client = ClientQueryClient::new(transport);
+    let mut chain_ids = HashMap::new();
+    let mut key = Vec::new();
+
+    for _ in 0..MAX_IBC_LIST_PAGES {
+        let response = client
+            .client_states(QueryClientStatesRequest {
+                pagination: page_request(key, IBC_LIST_PAGE_LIMIT),
+            })
+            .await?
+            .into_inner();
+        for identified in response.client_states {
+            if let Some(chain_id) = identified
+                .client_state
+                .as_ref()
+                .map(light_client_kind)
+                .and_then(|kind| kind.counterparty_chain_id().map(str::to_string))
+            {
+                chain_ids.insert(identified.client_id, chain_id);
+            }
+        }
+        match next_page_key(response.pagination) {
+            Some(next_key) => key = next_key,
+            None => break,
+        }
     }
 
-    let header = latest_block.block?.header?;
-    Some(AbciInfo {
-        version,
-        last_block_height: header.height.to_string(),
-        last_block_app_hash: bytes_to_upper_hex(&header.app_hash),
-    })
+    Ok(chain_ids)
 }
 
-/// Balance entry from bank query
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Balance {
-    pub denom: String,
-    pub amount: String,
-}
+pub async fn query_ibc_channels_grpc(
+    grpc_endpoint: &str,
+) -> anyhow::Result<Vec<IbcChannelSummary>> {
+    let transport = connect_grpc(grpc_endpoint).await?;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct IbcDenomTrace {
-    pub path: String,
-    pub base_denom: String,
-}
+    let mut channel_client = ChannelQueryClient::new(transport.clone());
+    let mut identified_channels = Vec::new();
+    let mut key = Vec::new();
+    for _ in 0..MAX_IBC_LIST_PAGES {
+        let response = channel_client
+            .channels(QueryChannelsRequest {
+                pagination: page_request(key, IBC_LIST_PAGE_LIMIT),
+            })
+            .await?
+            .into_inner();
+        identified_channels.extend(response.channels);
+        match next_page_key(response.pagination) {
+            Some(next_key) => key = next_key,
+            None => break,
+        }
+    }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct WalletBalance {
-    pub balance: Balance,
-    pub ibc_trace: Option<IbcDenomTrace>,
-    pub asset_label: Option<String>,
+    // Counterparty chain IDs are best effort; the channel list is still useful without them
+    let (connection_clients, client_chain_ids) = tokio::join!(
+        query_connection_clients(transport.clone()),
+        query_client_chain_ids(transport),
+    );
+    let connection_clients = connection_clients.unwrap_or_else(|e| {
+        log::warn!("Could not list connections on {grpc_endpoint}: {e}");
+        HashMap::new()
+    });
+    let client_chain_ids = client_chain_ids.unwrap_or_else(|e| {
+        log::warn!("Could not list client states on {grpc_endpoint}: {e}");
+        HashMap::new()
+    });
+
+    let mut channels: Vec<IbcChannelSummary> = identified_channels
+        .into_iter()
+        .map(|channel| {
+            let connection_id = channel.connection_hops.first().cloned().unwrap_or_default();
+            let client_id = connection_clients.get(&connection_id).cloned();
+            let counterparty_chain_id = client_id
+                .as_ref()
+                .and_then(|client_id| client_chain_ids.get(client_id))
+                .cloned();
+            let counterparty = channel.counterparty.unwrap_or_default();
+
+            IbcChannelSummary {
+                state: channel_state_label(channel.state),
+                ordering: channel_order_label(channel.ordering),
+                port_id: channel.port_id,
+                channel_id: channel.channel_id,
+                version: channel.version,
+                connection_id,
+                client_id,
+                counterparty_port_id: counterparty.port_id,
+                counterparty_channel_id: counterparty.channel_id,
+                counterparty_chain_id,
+                counterparty_chain_name: None,
+            }
+        })
+        .collect();
+    channels.sort_by_key(|channel| channel_sort_key(&channel.channel_id));
+
+    Ok(channels)
+}
+
+pub async fn query_ibc_channels_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+) -> anyhow::Result<Vec<IbcChannelSummary>> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for IBC channel list: {}", endpoint);
+        match query_ibc_channels_grpc(endpoint).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!("Failed gRPC channel list with endpoint {}: {}", endpoint, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+/// Channels listed in the channel views; hubs have hundreds, so the rest are only counted.
+pub const MAX_CHANNELS_SHOWN: usize = 30;
+
+pub fn format_ibc_channels(
+    chain: &str,
+    channels: &[IbcChannelSummary],
+    transfer_only: bool,
+) -> String {
+    let mut shown: Vec<&IbcChannelSummary> = channels
+        .iter()
+        .filter(|channel| !transfer_only || channel.is_open_transfer())
+        .collect();
+    // Open channels first, so the cap hides closed and half-open ones before live ones.
+    shown.sort_by_key(|channel| channel.state != "Open");
+
+    let mut message = if transfer_only {
+        format!(
+            "🔗 *Open Transfer Channels on {}*\n\n{} of {} channels",
+            escape_markdown(chain),
+            shown.len(),
+            channels.len()
+        )
+    } else {
+        format!(
+            "🔗 *IBC Channels on {}*\n\n{} channels, {} open",
+            escape_markdown(chain),
+            channels.len(),
+            channels
+                .iter()
+                .filter(|channel| channel.state == "Open")
+                .count()
+        )
+    };
+
+    if shown.is_empty() {
+        message.push_str("\n\nNo matching channels found\\.");
+        return message;
+    }
+
+    let total_shown = shown.len();
+    for channel in shown.into_iter().take(MAX_CHANNELS_SHOWN) {
+        let counterparty = match (
+            &channel.counterparty_chain_name,
+            &channel.counterparty_chain_id,
+        ) {
+            (Some(name), Some(chain_id)) => {
+                format!(
+                    "*{}* \\({}\\)",
+                    escape_markdown(name),
+                    escape_markdown(chain_id)
+                )
+            }
+            (None, Some(chain_id)) => escape_markdown(chain_id),
+            _ => "unknown chain".to_string(),
+        };
+        message.push_str(&format!(
+            "\n\n`{}/{}` → {} `{}/{}`\n{} · {} · `{}`\nConnection: `{}` · Client: `{}`",
+            escape_markdown_code(&channel.port_id),
+            escape_markdown_code(&channel.channel_id),
+            counterparty,
+            escape_markdown_code(&channel.counterparty_port_id),
+            escape_markdown_code(&channel.counterparty_channel_id),
+            escape_markdown(&channel.state),
+            escape_markdown(&channel.ordering),
+            escape_markdown_code(&channel.version),
+            escape_markdown_code(&channel.connection_id),
+            escape_markdown_code(channel.client_id.as_deref().unwrap_or("unknown"))
+        ));
+    }
+    if total_shown > MAX_CHANNELS_SHOWN {
+        message.push_str(&format!(
+            "\n\n… {} more channels",
+            total_shown - MAX_CHANNELS_SHOWN
+        ));
+    }
+
+    message
+}
+
+/// Balance queries in flight at once while scanning escrow accounts.
+const ESCROW_QUERY_CONCURRENCY: usize = 8;
+/// Assets and per-asset channels shown in the escrow view.
+const MAX_ESCROW_ASSETS: usize = 25;
+const MAX_ESCROW_CHANNELS_PER_ASSET: usize = 5;
+
+/// ICS-20 escrow account for a channel: the first 20 bytes of
+/// `sha256("ics20-1" || 0x00 || "{port}/{channel}")`, bech32-encoded with the chain prefix.
+pub fn ics20_escrow_address(
+    prefix: &str,
+    port_id: &str,
+    channel_id: &str,
+) -> anyhow::Result<String> {
+    let mut preimage = b"ics20-1".to_vec();
+    preimage.push(0);
+    preimage.extend_from_slice(format!("{port_id}/{channel_id}").as_bytes());
+    let hash = Sha256::digest(&preimage);
+
+    Ok(bech32::encode::<bech32::Bech32>(
+        bech32::Hrp::parse(prefix)?,
+        &hash[..20],
+    )?)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct OsmosisPoolAsset {
+pub struct ChannelEscrow {
+    pub port_id: String,
+    pub channel_id: String,
+    pub counterparty: Option<String>,
+    pub address: String,
+    pub balances: Vec<Balance>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EscrowReport {
+    pub escrows: Vec<ChannelEscrow>,
+    /// `TotalEscrowForDenom` results, for chains on ibc-go v7.1 or newer.
+    pub total_escrow: HashMap<String, String>,
+    pub failed_channels: usize,
+}
+
+async fn query_all_balances_with(
+    transport: Channel,
+    address: &str,
+) -> anyhow::Result<Vec<Balance>> {
+    let mut client = BankQueryClient::new(transport);
+    let mut balances = Vec::new();
+    let mut key = Vec::new();
+
+    for _ in 0..MAX_IBC_LIST_PAGES {
+        let response = client
+            .all_balances(QueryAllBalancesRequest {
+                address: address.to_string(),
+                pagination: page_request(key, IBC_LIST_PAGE_LIMIT),
+                resolve_denom: false,
+            })
+            .await?
+            .into_inner();
+        balances.extend(response.balances.into_iter().map(|coin| Balance {
+            denom: coin.denom,
+            amount: coin.amount,
+        }));
+        match next_page_key(response.pagination) {
+            Some(next_key) => key = next_key,
+            None => break,
+        }
+    }
+
+    Ok(balances)
+}
+
+/// Reads the escrow account of every `(port, channel, counterparty)` given, then asks
+/// `TotalEscrowForDenom` about each denom found. Channels whose balances fail are counted.
+pub async fn query_channel_escrows_grpc(
+    grpc_endpoint: &str,
+    prefix: &str,
+    channels: &[(String, String, Option<String>)],
+) -> anyhow::Result<EscrowReport> {
+    let transport = connect_grpc(grpc_endpoint).await?;
+
+    let results: Vec<anyhow::Result<ChannelEscrow>> = stream::iter(channels.iter().cloned())
+        .map(|(port_id, channel_id, counterparty)| {
+            let transport = transport.clone();
+            async move {
+                let address = ics20_escrow_address(prefix, &port_id, &channel_id)?;
+                let balances = query_all_balances_with(transport, &address).await?;
+                Ok(ChannelEscrow {
+                    port_id,
+                    channel_id,
+                    counterparty,
+                    address,
+                    balances,
+                })
+            }
+        })
+        .buffer_unordered(ESCROW_QUERY_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut report = EscrowReport::default();
+    let mut last_error = None;
+    for result in results {
+        match result {
+            Ok(escrow) => report.escrows.push(escrow),
+            Err(e) => {
+                report.failed_channels += 1;
+                last_error = Some(e);
+            }
+        }
+    }
+    if report.escrows.is_empty() {
+        if let Some(e) = last_error {
+            return Err(e);
+        }
+    }
+    report
+        .escrows
+        .sort_by(|a, b| a.channel_id.cmp(&b.channel_id));
+
+    let denoms: std::collections::BTreeSet<String> = report
+        .escrows
+        .iter()
+        .flat_map(|escrow| escrow.balances.iter().map(|balance| balance.denom.clone()))
+        .collect();
+    report.total_escrow = stream::iter(denoms)
+        .map(|denom| {
+            let mut client = TransferQueryClient::new(transport.clone());
+            async move {
+                let amount = client
+                    .total_escrow_for_denom(QueryTotalEscrowForDenomRequest {
+                        denom: denom.clone(),
+                    })
+                    .await
+                    .ok()?
+                    .into_inner()
+                    .amount?
+                    .amount;
+                Some((denom, amount))
+            }
+        })
+        .buffer_unordered(ESCROW_QUERY_CONCURRENCY)
+        .filter_map(|total| async move { total })
+        .collect()
+        .await;
+
+    Ok(report)
+}
+
+pub async fn query_channel_escrows_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+    prefix: &str,
+    channels: &[(String, String, Option<String>)],
+) -> anyhow::Result<EscrowReport> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for escrow balances: {}", endpoint);
+        match query_channel_escrows_grpc(endpoint, prefix, channels).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!("Failed gRPC escrow query with endpoint {}: {}", endpoint, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+/// One channel's escrowed amount of an asset.
+type EscrowShare<'a> = (&'a ChannelEscrow, u128);
+
+fn parse_amount(amount: &str) -> u128 {
+    amount.parse().unwrap_or(0)
+}
+
+/// Groups escrowed amounts by asset, largest first, with each asset's biggest channels.
+/// `labels` maps denoms to registry symbols.
+pub fn format_escrow_report(
+    chain: &str,
+    report: &EscrowReport,
+    labels: &HashMap<String, String>,
+    units: &DenomUnits,
+) -> String {
+    let mut message = format!("🔒 IBC Escrow on {chain}");
+    if let [escrow] = report.escrows.as_slice() {
+        message.push_str(&format!(
+            "\n{}/{}{}\nEscrow Address: {}",
+            escrow.port_id,
+            escrow.channel_id,
+            escrow
+                .counterparty
+                .as_ref()
+                .map(|counterparty| format!(" → {counterparty}"))
+                .unwrap_or_default(),
+            escrow.address
+        ));
+    } else {
+        message.push_str(&format!(
+            "\n{} transfer channels scanned",
+            report.escrows.len()
+        ));
+    }
+    if report.failed_channels > 0 {
+        message.push_str(&format!(
+            " ({} could not be queried)",
+            report.failed_channels
+        ));
+    }
+
+    let mut by_denom: HashMap<&str, Vec<EscrowShare>> = HashMap::new();
+    for escrow in &report.escrows {
+        for balance in &escrow.balances {
+            by_denom
+                .entry(balance.denom.as_str())
+                .or_default()
+                .push((escrow, parse_amount(&balance.amount)));
+        }
+    }
+    if by_denom.is_empty() {
+        message.push_str("\n\nNothing is escrowed.");
+        return message;
+    }
+
+    let mut assets: Vec<(&str, u128, Vec<EscrowShare>)> = by_denom
+        .into_iter()
+        .map(|(denom, mut channels)| {
+            channels.sort_by_key(|(_, amount)| std::cmp::Reverse(*amount));
+            let total = channels.iter().map(|(_, amount)| amount).sum();
+            (denom, total, channels)
+        })
+        .collect();
+    assets.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    for (denom, total, channels) in assets.iter().take(MAX_ESCROW_ASSETS) {
+        let label = labels
+            .get(*denom)
+            .map(|label| format!("{label} ({denom})"))
+            .unwrap_or_else(|| denom.to_string());
+        message.push_str(&format!(
+            "\n\n{}\nEscrowed: {} over {} channel{}",
+            label,
+            units.format(&total.to_string(), denom),
+            channels.len(),
+            if channels.len() == 1 { "" } else { "s" }
+        ));
+        if let Some(chain_total) = report.total_escrow.get(*denom) {
+            message.push_str(&format!(
+                " | chain-wide total: {}",
+                units.format(chain_total, denom)
+            ));
+        }
+
+        if report.escrows.len() > 1 {
+            for (escrow, amount) in channels.iter().take(MAX_ESCROW_CHANNELS_PER_ASSET) {
+                let share = if *total > 0 {
+                    *amount as f64 / *total as f64 * 100.0
+                } else {
+                    0.0
+                };
+                message.push_str(&format!(
+                    "\n• {}{}: {} ({:.1}%)",
+                    escrow.channel_id,
+                    escrow
+                        .counterparty
+                        .as_ref()
+                        .map(|counterparty| format!(" → {counterparty}"))
+                        .unwrap_or_default(),
+                    units.format_short(&amount.to_string(), denom),
+                    share
+                ));
+            }
+            if channels.len() > MAX_ESCROW_CHANNELS_PER_ASSET {
+                message.push_str(&format!(
+                    "\n… {} more channels",
+                    channels.len() - MAX_ESCROW_CHANNELS_PER_ASSET
+                ));
+            }
+        }
+    }
+    if assets.len() > MAX_ESCROW_ASSETS {
+        message.push_str(&format!(
+            "\n\n… {} more assets",
+            assets.len() - MAX_ESCROW_ASSETS
+        ));
+    }
+
+    message
+}
+
+/// `ratelimit` module query messages, served as `ratelimit.v1` by the ibc-apps module and as
+/// `stride.ratelimit` on Stride. Not generated in the proto crates we use.
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryAllRateLimitsRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryAllRateLimitsResponse {
+    #[prost(message, repeated, tag = "1")]
+    rate_limits: Vec<ModuleRateLimit>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ModuleRateLimit {
+    #[prost(message, optional, tag = "1")]
+    path: Option<ModuleRateLimitPath>,
+    #[prost(message, optional, tag = "2")]
+    quota: Option<ModuleRateLimitQuota>,
+    #[prost(message, optional, tag = "3")]
+    flow: Option<ModuleRateLimitFlow>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ModuleRateLimitPath {
+    #[prost(string, tag = "1")]
+    denom: String,
+    #[prost(string, tag = "2")]
+    channel_or_client_id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ModuleRateLimitQuota {
+    #[prost(string, tag = "1")]
+    max_percent_send: String,
+    #[prost(string, tag = "2")]
+    max_percent_recv: String,
+    #[prost(uint64, tag = "3")]
+    duration_hours: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ModuleRateLimitFlow {
+    #[prost(string, tag = "1")]
+    inflow: String,
+    #[prost(string, tag = "2")]
+    outflow: String,
+    #[prost(string, tag = "3")]
+    channel_value: String,
+}
+
+const RATE_LIMIT_MODULE_PATHS: [&str; 2] = [
+    "/ratelimit.v1.Query/AllRateLimits",
+    "/stride.ratelimit.Query/AllRateLimits",
+];
+
+/// Storage namespace of the Osmosis ibc-rate-limit contract's `(channel, denom)` trackers.
+const OSMOSIS_RATE_LIMIT_NAMESPACE: &[u8] = b"flow";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub channel_id: String,
     pub denom: String,
-    pub amount: String,
-    pub weight: Option<String>,
+    pub name: Option<String>,
+    pub max_percent_send: String,
+    pub max_percent_recv: String,
+    pub duration_secs: u64,
+    pub inflow: String,
+    pub outflow: String,
+    pub channel_value: Option<String>,
+    /// Unix seconds at which the current window resets, when the source exposes it.
+    pub period_end: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateLimitSource {
+    OsmosisContract(String),
+    Module(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateLimitLookup {
+    /// Neither the Osmosis contract nor a `ratelimit` module answered.
+    NotSupported,
+    Limits {
+        source: RateLimitSource,
+        limits: Vec<RateLimitInfo>,
+    },
+}
+
+/// Splits a cw-storage-plus `Map<(String, String), _>` key into its two parts, checking the
+/// length-prefixed namespace first.
+pub fn parse_rate_limit_key(key: &[u8]) -> Option<(String, String)> {
+    fn take_prefixed(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+        let len = u16::from_be_bytes([*bytes.first()?, *bytes.get(1)?]) as usize;
+        let rest = bytes.get(2..)?;
+        (rest.len() >= len).then(|| rest.split_at(len))
+    }
+
+    let (namespace, rest) = take_prefixed(key)?;
+    if namespace != OSMOSIS_RATE_LIMIT_NAMESPACE {
+        return None;
+    }
+    let (channel_id, denom) = take_prefixed(rest)?;
+    Some((
+        String::from_utf8(channel_id.to_vec()).ok()?,
+        String::from_utf8(denom.to_vec()).ok()?,
+    ))
+}
+
+/// Reads the JSON `Vec<RateLimit>` the Osmosis contract stores per `(channel, denom)`.
+pub fn parse_osmosis_rate_limits(
+    channel_id: &str,
+    denom: &str,
+    value: &Value,
+) -> Vec<RateLimitInfo> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|limit| {
+            let quota = &limit["quota"];
+            let flow = &limit["flow"];
+            let json_string = |value: &Value| match value {
+                Value::String(text) => text.clone(),
+                Value::Number(number) => number.to_string(),
+                _ => "0".to_string(),
+            };
+            RateLimitInfo {
+                channel_id: channel_id.to_string(),
+                denom: denom.to_string(),
+                name: quota["name"]
+                    .as_str()
+                    .and_then(|name| non_empty(name.to_string())),
+                max_percent_send: json_string(&quota["max_percentage_send"]),
+                max_percent_recv: json_string(&quota["max_percentage_recv"]),
+                duration_secs: quota["duration"].as_u64().unwrap_or_default(),
+                inflow: json_string(&flow["inflow"]),
+                outflow: json_string(&flow["outflow"]),
+                channel_value: quota["channel_value"].as_str().map(str::to_string),
+                period_end: flow["period_end"]
+                    .as_str()
+                    .and_then(|nanos| nanos.parse::<i64>().ok())
+                    .map(|nanos| nanos / 1_000_000_000),
+            }
+        })
+        .collect()
+}
+
+fn rate_limit_applies(limit: &RateLimitInfo, channel_id: &str, denom: Option<&str>) -> bool {
+    (limit.channel_id == channel_id || limit.channel_id == "any")
+        && denom.is_none_or(|denom| limit.denom == denom)
+}
+
+async fn query_osmosis_rate_limits(
+    transport: Channel,
+    contract: &str,
+) -> anyhow::Result<Vec<RateLimitInfo>> {
+    let mut limits = Vec::new();
+    let mut key = Vec::new();
+
+    for _ in 0..MAX_IBC_LIST_PAGES {
+        let response: QueryAllContractStateResponse = grpc_unary(
+            transport.clone(),
+            "/cosmwasm.wasm.v1.Query/AllContractState",
+            QueryAllContractStateRequest {
+                address: contract.to_string(),
+                pagination: Some(
+                    osmosis_std::types::cosmos::base::query::v1beta1::PageRequest {
+                        key,
+                        offset: 0,
+                        limit: IBC_LIST_PAGE_LIMIT,
+                        count_total: false,
+                        reverse: false,
+                    },
+                ),
+            },
+        )
+        .await?;
+
+        for model in response.models {
+            let Some((channel_id, denom)) = parse_rate_limit_key(&model.key) else {
+                continue;
+            };
+            match serde_json::from_slice::<Value>(&model.value) {
+                Ok(value) => limits.extend(parse_osmosis_rate_limits(&channel_id, &denom, &value)),
+                Err(e) => log::warn!("Unreadable rate limit for {channel_id}/{denom}: {e}"),
+            }
+        }
+        match response
+            .pagination
+            .and_then(|pagination| pagination.next_key)
+            .filter(|next_key| !next_key.is_empty())
+        {
+            Some(next_key) => key = next_key,
+            None => break,
+        }
+    }
+
+    Ok(limits)
+}
+
+fn module_rate_limit_info(limit: ModuleRateLimit) -> Option<RateLimitInfo> {
+    let path = limit.path?;
+    let quota = limit.quota.unwrap_or_default();
+    let flow = limit.flow.unwrap_or_default();
+    Some(RateLimitInfo {
+        channel_id: path.channel_or_client_id,
+        denom: path.denom,
+        name: None,
+        max_percent_send: quota.max_percent_send,
+        max_percent_recv: quota.max_percent_recv,
+        duration_secs: quota.duration_hours * 3_600,
+        inflow: flow.inflow,
+        outflow: flow.outflow,
+        channel_value: non_empty(flow.channel_value),
+        period_end: None,
+    })
+}
+
+/// Detects which rate limiter the chain runs, if any, and returns the limits on `channel_id`
+/// (including Osmosis `any`-channel limits), optionally narrowed to one denom.
+pub async fn query_rate_limits_grpc(
+    grpc_endpoint: &str,
+    channel_id: &str,
+    denom: Option<&str>,
+) -> anyhow::Result<RateLimitLookup> {
+    let transport = connect_grpc(grpc_endpoint).await?;
+
+    // Only Unimplemented means "not Osmosis"; anything else must not read as "no limiter"
+    let contract = match grpc_unary::<RateLimitParamsRequest, RateLimitParamsResponse>(
+        transport.clone(),
+        "/osmosis.ibcratelimit.v1beta1.Query/Params",
+        RateLimitParamsRequest {},
+    )
+    .await
+    {
+        Ok(response) => response
+            .params
+            .and_then(|params| non_empty(params.contract_address)),
+        Err(e) if is_unimplemented(&e) => None,
+        Err(e) => return Err(e),
+    };
+    if let Some(contract) = contract {
+        let limits = query_osmosis_rate_limits(transport, &contract)
+            .await?
+            .into_iter()
+            .filter(|limit| rate_limit_applies(limit, channel_id, denom))
+            .collect();
+        return Ok(RateLimitLookup::Limits {
+            source: RateLimitSource::OsmosisContract(contract),
+            limits,
+        });
+    }
+
+    for path in RATE_LIMIT_MODULE_PATHS {
+        match grpc_unary::<QueryAllRateLimitsRequest, QueryAllRateLimitsResponse>(
+            transport.clone(),
+            path,
+            QueryAllRateLimitsRequest {},
+        )
+        .await
+        {
+            Ok(response) => {
+                let module = path
+                    .trim_start_matches('/')
+                    .split(".Query")
+                    .next()
+                    .unwrap_or(path)
+                    .to_string();
+                let limits = response
+                    .rate_limits
+                    .into_iter()
+                    .filter_map(module_rate_limit_info)
+                    .filter(|limit| rate_limit_applies(limit, channel_id, denom))
+                    .collect();
+                return Ok(RateLimitLookup::Limits {
+                    source: RateLimitSource::Module(module),
+                    limits,
+                });
+            }
+            Err(e) if is_unimplemented(&e) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(RateLimitLookup::NotSupported)
+}
+
+pub async fn query_rate_limits_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+    channel_id: &str,
+    denom: Option<&str>,
+) -> anyhow::Result<RateLimitLookup> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for rate limits: {}", endpoint);
+        match query_rate_limits_grpc(endpoint, channel_id, denom).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!(
+                    "Failed gRPC rate limit query with endpoint {}: {}",
+                    endpoint,
+                    e
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+/// One direction of a quota: net flow against the cap the percentage allows.
+fn format_rate_limit_usage(
+    direction: &str,
+    net_flow: i128,
+    percent: &str,
+    channel_value: Option<u128>,
+    units: &DenomUnits,
+    denom: &str,
+) -> String {
+    let used = net_flow.max(0) as u128;
+    match (channel_value, percent.parse::<u128>()) {
+        (Some(value), Ok(percent)) if value > 0 => {
+            let cap = value * percent / 100;
+            format!(
+                "• {direction}: {} of {} allowed ({percent}% of channel value, {:.1}% used)",
+                units.format_short(&used.to_string(), denom),
+                units.format_short(&cap.to_string(), denom),
+                used as f64 / cap.max(1) as f64 * 100.0
+            )
+        }
+        _ => format!(
+            "• {direction}: {} net, max {percent}% of channel value",
+            units.format_short(&used.to_string(), denom)
+        ),
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct OsmosisPoolInfo {
-    pub pool_type: String,
-    pub address: Option<String>,
-    pub swap_fee: Option<String>,
-    pub total_shares: Option<String>,
-    pub assets: Vec<OsmosisPoolAsset>,
-    pub current_tick_liquidity: Option<String>,
+pub fn format_rate_limits(
+    chain: &str,
+    channel_id: &str,
+    denom: Option<&str>,
+    lookup: &RateLimitLookup,
+    labels: &HashMap<String, String>,
+    units: &DenomUnits,
+    now: i64,
+) -> String {
+    let target = match denom {
+        Some(denom) => format!("{channel_id} / {denom}"),
+        None => channel_id.to_string(),
+    };
+    let (source, limits) = match lookup {
+        RateLimitLookup::NotSupported => {
+            return format!(
+                "⏱ Rate Limits on {chain}\n\n✅ {chain} runs no IBC rate limiter; transfers on {target} are not capped."
+            );
+        }
+        RateLimitLookup::Limits { source, limits } => (source, limits),
+    };
+
+    let source = match source {
+        RateLimitSource::OsmosisContract(contract) => format!("ibc-rate-limit contract {contract}"),
+        RateLimitSource::Module(module) => format!("{module} module"),
+    };
+    let mut message = format!("⏱ Rate Limits on {chain} for {target}\nSource: {source}");
+    if limits.is_empty() {
+        message.push_str(&format!("\n\n✅ No rate limit configured for {target}."));
+        return message;
+    }
+
+    for limit in limits {
+        let label = labels
+            .get(&limit.denom)
+            .map(|label| format!("{label} ({})", limit.denom))
+            .unwrap_or_else(|| limit.denom.clone());
+        message.push_str(&format!(
+            "\n\n{} on {}{} ({} window)",
+            label,
+            limit.channel_id,
+            limit
+                .name
+                .as_ref()
+                .map(|name| format!(" [{name}]"))
+                .unwrap_or_default(),
+            human_duration(limit.duration_secs as i64)
+        ));
+
+        let inflow = parse_amount(&limit.inflow) as i128;
+        let outflow = parse_amount(&limit.outflow) as i128;
+        let channel_value = limit.channel_value.as_deref().map(parse_amount);
+        message.push_str(&format!(
+            "\n{}\n{}",
+            format_rate_limit_usage(
+                "Send",
+                outflow - inflow,
+                &limit.max_percent_send,
+                channel_value,
+                units,
+                &limit.denom
+            ),
+            format_rate_limit_usage(
+                "Receive",
+                inflow - outflow,
+                &limit.max_percent_recv,
+                channel_value,
+                units,
+                &limit.denom
+            )
+        ));
+        if let Some(value) = channel_value {
+            message.push_str(&format!(
+                "\n• Channel value: {}",
+                units.format(&value.to_string(), &limit.denom)
+            ));
+        }
+        match limit.period_end {
+            // The Osmosis contract only rolls a window over on the next transfer
+            Some(period_end) if period_end <= now => message.push_str(&format!(
+                "\n• Window expired {} ({} ago); the flow above is from that period and resets on the next transfer",
+                unix_to_string(period_end),
+                human_duration(now - period_end)
+            )),
+            Some(period_end) => message.push_str(&format!(
+                "\n• Resets: {} (in {})",
+                unix_to_string(period_end),
+                human_duration(period_end - now)
+            )),
+            None => message.push_str(&format!(
+                "\n• Resets: every {}",
+                human_duration(limit.duration_secs as i64)
+            )),
+        }
+    }
+
+    message
+}
+
+/// Clients this close to expiry get flagged so someone can submit an update in time.
+const CLIENT_EXPIRY_WARNING_SECS: i64 = 86_400;
+
+pub fn format_light_client_status(status: &LightClientStatus, now: i64) -> String {
+    let expires_in = status.expires_in(now);
+    let computed_status = if status.frozen {
+        "Frozen"
+    } else if expires_in.is_some_and(|seconds| seconds <= 0) {
+        "Expired"
+    } else {
+        "Active"
+    };
+    let status_label = status.status.as_deref().unwrap_or(computed_status);
+    let status_icon = match status_label {
+        "Active" if expires_in.is_some_and(|seconds| seconds < CLIENT_EXPIRY_WARNING_SECS) => "⚠️",
+        "Active" => "✅",
+        _ => "❌",
+    };
+
+    let mut lines = vec![format!("• Status: {status_icon} {status_label}")];
+    if let Some(height) = &status.latest_height {
+        lines.push(format!("• Latest Height: {height}"));
+    }
+    if let Some(trusting_period) = status.trusting_period {
+        lines.push(format!(
+            "• Trusting Period: {}",
+            human_duration(trusting_period)
+        ));
+    }
+    if let Some(unbonding_period) = status.unbonding_period {
+        lines.push(format!(
+            "• Unbonding Period: {}",
+            human_duration(unbonding_period)
+        ));
+    }
+    if let Some(last_update) = status.last_update {
+        lines.push(format!(
+            "• Last Update: {} ({} ago)",
+            unix_to_string(last_update),
+            human_duration(now - last_update)
+        ));
+    }
+    match expires_in {
+        Some(seconds) if seconds <= 0 => lines.push(format!(
+            "• Expires In: expired {} ago, a client upgrade or recovery proposal is needed",
+            human_duration(-seconds)
+        )),
+        Some(seconds) if seconds < CLIENT_EXPIRY_WARNING_SECS => lines.push(format!(
+            "• Expires In: {}, update the client soon",
+            human_duration(seconds)
+        )),
+        Some(seconds) => lines.push(format!("• Expires In: ~{}", human_duration(seconds))),
+        None => lines.push("• Expires In: unknown".to_string()),
+    }
+
+    lines.join("\n")
+}
+
+/// One direction of a channel: packets sent from `source` and what the destination has seen.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PacketFlow {
+    pub source: String,
+    pub destination: String,
+    pub next_sequence_send: Option<u64>,
+    pub next_sequence_recv: Option<u64>,
+    /// Next acknowledgement the source expects; only advances on ordered channels.
+    pub next_sequence_ack: Option<u64>,
+    /// Sequences still committed on the source, i.e. sent but not yet acknowledged.
+    pub commitments: Vec<u64>,
+    /// Committed sequences the destination has not received.
+    pub unreceived_packets: Vec<u64>,
+    /// Sequences acknowledged on the destination whose acks have not been relayed back.
+    pub unreceived_acks: Vec<u64>,
+    /// Send time of the oldest committed packet, when a node with tx indexing could tell us.
+    pub oldest_sent_at: Option<i64>,
+    /// False when the destination side could not be queried.
+    pub destination_checked: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingPacketReport {
+    pub port_id: String,
+    pub channel_id: String,
+    pub counterparty_port_id: String,
+    pub counterparty_channel_id: String,
+    pub outgoing: PacketFlow,
+    pub incoming: PacketFlow,
+}
+
+/// Where one end of a channel lives, for the packet queries below.
+pub struct ChannelEnd<'a> {
+    pub chain: &'a str,
+    pub grpc_endpoint: Option<&'a str>,
+    pub port_id: &'a str,
+    pub channel_id: &'a str,
+}
+
+async fn query_packet_commitment_sequences(
+    transport: Channel,
+    port_id: &str,
+    channel_id: &str,
+) -> anyhow::Result<Vec<u64>> {
+    let mut client = ChannelQueryClient::new(transport);
+    let mut sequences = Vec::new();
+    let mut key = Vec::new();
+
+    for _ in 0..MAX_IBC_LIST_PAGES {
+        let response = client
+            .packet_commitments(QueryPacketCommitmentsRequest {
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+                pagination: page_request(key, IBC_LIST_PAGE_LIMIT),
+            })
+            .await?
+            .into_inner();
+        sequences.extend(
+            response
+                .commitments
+                .into_iter()
+                .map(|packet| packet.sequence),
+        );
+        match next_page_key(response.pagination) {
+            Some(next_key) => key = next_key,
+            None => break,
+        }
+    }
+
+    sequences.sort_unstable();
+    Ok(sequences)
+}
+
+async fn query_next_sequences(
+    transport: Channel,
+    port_id: &str,
+    channel_id: &str,
+) -> (Option<u64>, Option<u64>) {
+    let mut client = ChannelQueryClient::new(transport);
+    let send = client
+        .next_sequence_send(QueryNextSequenceSendRequest {
+            port_id: port_id.to_string(),
+            channel_id: channel_id.to_string(),
+        })
+        .await
+        .ok()
+        .map(|response| response.into_inner().next_sequence_send);
+    let recv = client
+        .next_sequence_receive(QueryNextSequenceReceiveRequest {
+            port_id: port_id.to_string(),
+            channel_id: channel_id.to_string(),
+        })
+        .await
+        .ok()
+        .map(|response| response.into_inner().next_sequence_receive);
+    (send, recv)
+}
+
+/// ibc-go has no gRPC query for the next ack sequence, so this reads the IBC store key
+/// directly, where it is kept as a big-endian u64.
+async fn query_next_sequence_ack(
+    transport: Channel,
+    port_id: &str,
+    channel_id: &str,
+) -> Option<u64> {
+    let response = TendermintServiceClient::new(transport)
+        .abci_query(AbciQueryRequest {
+            data: format!("nextSequenceAck/ports/{port_id}/channels/{channel_id}").into_bytes(),
+            path: "/store/ibc/key".to_string(),
+            height: 0,
+            prove: false,
+        })
+        .await
+        .ok()?
+        .into_inner();
+    if response.code != 0 {
+        return None;
+    }
+    let bytes: [u8; 8] = response.value.as_slice().try_into().ok()?;
+    Some(u64::from_be_bytes(bytes))
+}
+
+/// Looks up when a packet was sent via tx search. Needs a node with tx indexing enabled.
+async fn query_packet_send_time(
+    transport: Channel,
+    port_id: &str,
+    channel_id: &str,
+    sequence: u64,
+) -> Option<i64> {
+    let conditions = [
+        format!("send_packet.packet_src_port='{port_id}'"),
+        format!("send_packet.packet_src_channel='{channel_id}'"),
+        format!("send_packet.packet_sequence='{sequence}'"),
+    ];
+    let mut client = TxServiceClient::new(transport);
+
+    let response = match client
+        .get_txs_event(GetTxsEventRequest {
+            query: conditions.join(" AND "),
+            page: 1,
+            limit: 1,
+            ..Default::default()
+        })
+        .await
+    {
+        Ok(response) => response,
+        // Cosmos SDK < 0.47 only understands the older `events` list
+        Err(_) => {
+            #[allow(deprecated)]
+            let request = GetTxsEventRequest {
+                events: conditions.to_vec(),
+                page: 1,
+                limit: 1,
+                ..Default::default()
+            };
+            client.get_txs_event(request).await.ok()?
+        }
+    };
+
+    let timestamp = response
+        .into_inner()
+        .tx_responses
+        .first()?
+        .timestamp
+        .clone();
+    chrono::DateTime::parse_from_rfc3339(&timestamp)
+        .ok()
+        .map(|timestamp| timestamp.timestamp())
+}
+
+/// Sequences among `commitments` the destination has written acknowledgements for, paged like
+/// the commitments themselves.
+async fn query_packet_ack_sequences(
+    transport: Channel,
+    port_id: &str,
+    channel_id: &str,
+    commitments: &[u64],
+) -> anyhow::Result<Vec<u64>> {
+    let mut client = ChannelQueryClient::new(transport);
+    let mut sequences = Vec::new();
+    let mut key = Vec::new();
+
+    for _ in 0..MAX_IBC_LIST_PAGES {
+        let response = client
+            .packet_acknowledgements(QueryPacketAcknowledgementsRequest {
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+                pagination: page_request(key, IBC_LIST_PAGE_LIMIT),
+                packet_commitment_sequences: commitments.to_vec(),
+            })
+            .await?
+            .into_inner();
+        sequences.extend(
+            response
+                .acknowledgements
+                .into_iter()
+                .map(|ack| ack.sequence),
+        );
+        match next_page_key(response.pagination) {
+            Some(next_key) => key = next_key,
+            None => break,
+        }
+    }
+
+    Ok(sequences)
+}
+
+/// Fills in receive and ack status for committed packets from the destination's view.
+async fn inspect_destination(
+    flow: &mut PacketFlow,
+    source: &ChannelEnd<'_>,
+    source_transport: Channel,
+    destination: &ChannelEnd<'_>,
+    destination_transport: Channel,
+) {
+    let unreceived = ChannelQueryClient::new(destination_transport.clone())
+        .unreceived_packets(QueryUnreceivedPacketsRequest {
+            port_id: destination.port_id.to_string(),
+            channel_id: destination.channel_id.to_string(),
+            packet_commitment_sequences: flow.commitments.clone(),
+        })
+        .await;
+    let acknowledged = query_packet_ack_sequences(
+        destination_transport,
+        destination.port_id,
+        destination.channel_id,
+        &flow.commitments,
+    )
+    .await;
+
+    let (Ok(unreceived), Ok(ack_sequences)) = (unreceived, acknowledged) else {
+        return;
+    };
+    flow.destination_checked = true;
+    flow.unreceived_packets = unreceived.into_inner().sequences;
+
+    if !ack_sequences.is_empty() {
+        flow.unreceived_acks = ChannelQueryClient::new(source_transport)
+            .unreceived_acks(QueryUnreceivedAcksRequest {
+                port_id: source.port_id.to_string(),
+                channel_id: source.channel_id.to_string(),
+                packet_ack_sequences: ack_sequences,
+            })
+            .await
+            .map(|response| response.into_inner().sequences)
+            .unwrap_or_default();
+    }
+}
+
+/// Follows packets from `source` to `destination`: commitments left on the source, which of
+/// them the destination has not received, and which acks still need relaying back.
+async fn query_packet_flow(
+    source: &ChannelEnd<'_>,
+    source_transport: Channel,
+    destination: &ChannelEnd<'_>,
+    destination_transport: Option<Channel>,
+) -> anyhow::Result<PacketFlow> {
+    let commitments = query_packet_commitment_sequences(
+        source_transport.clone(),
+        source.port_id,
+        source.channel_id,
+    )
+    .await?;
+    let ((next_sequence_send, _), next_sequence_ack) = tokio::join!(
+        query_next_sequences(source_transport.clone(), source.port_id, source.channel_id),
+        query_next_sequence_ack(source_transport.clone(), source.port_id, source.channel_id),
+    );
+
+    let mut flow = PacketFlow {
+        source: source.chain.to_string(),
+        destination: destination.chain.to_string(),
+        next_sequence_send,
+        next_sequence_ack,
+        commitments,
+        ..Default::default()
+    };
+
+    if let Some(destination_transport) = destination_transport {
+        if flow.commitments.is_empty() {
+            flow.destination_checked = true;
+        } else {
+            inspect_destination(
+                &mut flow,
+                source,
+                source_transport.clone(),
+                destination,
+                destination_transport.clone(),
+            )
+            .await;
+        }
+
+        let (_, next_sequence_recv) = query_next_sequences(
+            destination_transport,
+            destination.port_id,
+            destination.channel_id,
+        )
+        .await;
+        flow.next_sequence_recv = next_sequence_recv;
+    }
+
+    if let Some(oldest) = flow.commitments.first() {
+        flow.oldest_sent_at =
+            query_packet_send_time(source_transport, source.port_id, source.channel_id, *oldest)
+                .await;
+    }
+
+    Ok(flow)
+}
+
+pub async fn query_pending_packets_grpc(
+    local: &ChannelEnd<'_>,
+    counterparty: &ChannelEnd<'_>,
+) -> anyhow::Result<PendingPacketReport> {
+    let local_endpoint = local
+        .grpc_endpoint
+        .ok_or_else(|| anyhow::anyhow!("No gRPC endpoint for {}", local.chain))?;
+    let local_transport = connect_grpc(local_endpoint).await?;
+    let counterparty_transport = match counterparty.grpc_endpoint {
+        Some(endpoint) => match connect_grpc(endpoint).await {
+            Ok(transport) => Some(transport),
+            Err(e) => {
+                log::warn!(
+                    "Could not connect to {} at {endpoint}: {e}",
+                    counterparty.chain
+                );
+                None
+            }
+        },
+        None => None,
+    };
+
+    let outgoing = query_packet_flow(
+        local,
+        local_transport.clone(),
+        counterparty,
+        counterparty_transport.clone(),
+    );
+    let incoming = async {
+        match counterparty_transport.clone() {
+            Some(transport) => {
+                query_packet_flow(
+                    counterparty,
+                    transport,
+                    local,
+                    Some(local_transport.clone()),
+                )
+                .await
+            }
+            None => Ok(PacketFlow {
+                source: counterparty.chain.to_string(),
+                destination: local.chain.to_string(),
+                ..Default::default()
+            }),
+        }
+    };
+    let (outgoing, incoming) = tokio::join!(outgoing, incoming);
+
+    Ok(PendingPacketReport {
+        port_id: local.port_id.to_string(),
+        channel_id: local.channel_id.to_string(),
+        counterparty_port_id: counterparty.port_id.to_string(),
+        counterparty_channel_id: counterparty.channel_id.to_string(),
+        outgoing: outgoing?,
+        incoming: incoming.unwrap_or_else(|e| {
+            log::warn!("Could not inspect packets from {}: {e}", counterparty.chain);
+            PacketFlow {
+                source: counterparty.chain.to_string(),
+                destination: local.chain.to_string(),
+                ..Default::default()
+            }
+        }),
+    })
+}
+
+const MAX_LISTED_SEQUENCES: usize = 10;
+
+fn format_sequences(sequences: &[u64]) -> String {
+    let mut listed: Vec<String> = sequences
+        .iter()
+        .take(MAX_LISTED_SEQUENCES)
+        .map(|sequence| sequence.to_string())
+        .collect();
+    if sequences.len() > MAX_LISTED_SEQUENCES {
+        listed.push(format!("… {} more", sequences.len() - MAX_LISTED_SEQUENCES));
+    }
+    listed.join(", ")
+}
+
+fn format_packet_flow(flow: &PacketFlow, now: i64) -> String {
+    let mut lines = vec![format!("{} → {}", flow.source, flow.destination)];
+    lines.push(format!(
+        "• Next sequence send: {} | ack: {} | next recv on {}: {}",
+        flow.next_sequence_send
+            .map(|sequence| sequence.to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        flow.next_sequence_ack
+            .map(|sequence| sequence.to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        flow.destination,
+        flow.next_sequence_recv
+            .map(|sequence| sequence.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    ));
+
+    if flow.commitments.is_empty() {
+        lines.push("• No packets awaiting acknowledgement".to_string());
+        return lines.join("\n");
+    }
+
+    lines.push(format!(
+        "• Awaiting ack: {} ({})",
+        flow.commitments.len(),
+        format_sequences(&flow.commitments)
+    ));
+    if flow.destination_checked {
+        lines.push(format!(
+            "• Not received on {}: {}{}",
+            flow.destination,
+            flow.unreceived_packets.len(),
+            if flow.unreceived_packets.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", format_sequences(&flow.unreceived_packets))
+            }
+        ));
+        lines.push(format!(
+            "• Acks not relayed back: {}{}",
+            flow.unreceived_acks.len(),
+            if flow.unreceived_acks.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", format_sequences(&flow.unreceived_acks))
+            }
+        ));
+    } else {
+        lines.push(format!(
+            "• Could not query {}, receive and ack status unknown",
+            flow.destination
+        ));
+    }
+
+    let oldest = flow.commitments[0];
+    match flow.oldest_sent_at {
+        Some(sent_at) => lines.push(format!(
+            "• Oldest pending: #{} sent {} ({} ago)",
+            oldest,
+            unix_to_string(sent_at),
+            human_duration(now - sent_at)
+        )),
+        None => lines.push(format!(
+            "• Oldest pending: #{oldest} (send time unavailable)"
+        )),
+    }
+
+    lines.join("\n")
+}
+
+pub fn format_pending_packets(chain: &str, report: &PendingPacketReport, now: i64) -> String {
+    let in_flight = report.outgoing.commitments.len() + report.incoming.commitments.len();
+    let oldest_age = [
+        report.outgoing.oldest_sent_at,
+        report.incoming.oldest_sent_at,
+    ]
+    .into_iter()
+    .flatten()
+    .min()
+    .map(|sent_at| now - sent_at);
+
+    let verdict = match (in_flight, oldest_age) {
+        (0, _) => "✅ No packets in flight".to_string(),
+        (count, Some(age)) if age > 3_600 => format!(
+            "⚠️ {count} packet(s) in flight, oldest is {} old. Relayers may be down on this channel.",
+            human_duration(age)
+        ),
+        (count, _) => format!("ℹ️ {count} packet(s) in flight"),
+    };
+
+    format!(
+        "📦 Packet Status for {}/{} on {}\nCounterparty: {} {}/{}\n\n{}\n\n{}\n\n{}",
+        report.port_id,
+        report.channel_id,
+        chain,
+        report.outgoing.destination,
+        report.counterparty_port_id,
+        report.counterparty_channel_id,
+        verdict,
+        format_packet_flow(&report.outgoing, now),
+        format_packet_flow(&report.incoming, now)
+    )
+}
+
+/// Splits a full IBC trace such as `transfer/channel-141/transfer/channel-0/uatom` into its
+/// hops and the base denom, which may itself contain slashes.
+pub fn split_trace_path(full_path: &str) -> (Vec<IbcDenomHop>, String) {
+    let parts: Vec<&str> = full_path.split('/').collect();
+    let mut hops = Vec::new();
+    let mut index = 0;
+
+    while index + 1 < parts.len() && is_channel_identifier(parts[index + 1]) {
+        hops.push(IbcDenomHop {
+            port_id: parts[index].to_string(),
+            channel_id: parts[index + 1].to_string(),
+        });
+        index += 2;
+    }
+
+    (hops, parts[index..].join("/"))
+}
+
+fn is_channel_identifier(segment: &str) -> bool {
+    segment.starts_with("channel-") || segment.starts_with("08-wasm-")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenomRouteHop {
+    pub chain: String,
+    pub port_id: String,
+    pub channel_id: String,
+    pub counterparty_chain_id: Option<String>,
+    pub counterparty_chain_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenomRoute {
+    pub chain: String,
+    pub base_denom: String,
+    pub hops: Vec<DenomRouteHop>,
+    /// Hops that could not be followed because a channel or chain did not resolve.
+    pub unresolved_hops: usize,
+}
+
+impl DenomRoute {
+    pub fn origin_chain(&self) -> Option<&str> {
+        if self.unresolved_hops > 0 {
+            return None;
+        }
+        match self.hops.last() {
+            Some(hop) => hop.counterparty_chain_name.as_deref(),
+            None => Some(&self.chain),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryTraceCheck {
+    Canonical,
+    NonCanonical { ibc_denom: String, path: String },
+    NotListed,
+}
+
+/// Compares a full trace path with the `traces` recorded in a chain's raw `assetlist.json`.
+/// An asset with the same base denom and origin but a different path means the funds took a
+/// route the registry does not consider canonical.
+pub fn check_registry_trace(
+    assetlist_json: &Value,
+    full_path: &str,
+    origin_chain: Option<&str>,
+) -> RegistryTraceCheck {
+    let (_, base_denom) = split_trace_path(full_path);
+    let mut alternative = None;
+
+    for asset in assetlist_json["assets"].as_array().into_iter().flatten() {
+        let Some(traces) = asset["traces"].as_array() else {
+            continue;
+        };
+        let Some(path) = traces
+            .iter()
+            .rev()
+            .find(|trace| trace["type"].as_str() == Some("ibc"))
+            .and_then(|trace| trace["chain"]["path"].as_str())
+        else {
+            continue;
+        };
+
+        if path == full_path {
+            return RegistryTraceCheck::Canonical;
+        }
+
+        let asset_origin = traces
+            .first()
+            .and_then(|trace| trace["counterparty"]["chain_name"].as_str());
+        let same_origin = match (origin_chain, asset_origin) {
+            (Some(origin), Some(asset_origin)) => origin == asset_origin,
+            _ => true,
+        };
+        if alternative.is_none() && same_origin && split_trace_path(path).1 == base_denom {
+            alternative = Some(RegistryTraceCheck::NonCanonical {
+                ibc_denom: asset["base"].as_str().unwrap_or_default().to_string(),
+                path: path.to_string(),
+            });
+        }
+    }
+
+    alternative.unwrap_or(RegistryTraceCheck::NotListed)
+}
+
+pub fn format_denom_route(route: &DenomRoute, check: Option<&RegistryTraceCheck>) -> String {
+    let mut line = route.chain.clone();
+    for hop in &route.hops {
+        let channel = if hop.port_id == "transfer" {
+            hop.channel_id.clone()
+        } else {
+            format!("{}/{}", hop.port_id, hop.channel_id)
+        };
+        let counterparty = hop
+            .counterparty_chain_name
+            .as_deref()
+            .or(hop.counterparty_chain_id.as_deref())
+            .unwrap_or("?");
+        line.push_str(&format!(" ← {channel} ← {counterparty}"));
+    }
+    if route.unresolved_hops > 0 {
+        line.push_str(&format!(
+            " ← … ({} more hop{} could not be resolved)",
+            route.unresolved_hops,
+            if route.unresolved_hops == 1 { "" } else { "s" }
+        ));
+    } else if route
+        .hops
+        .last()
+        .is_none_or(|hop| hop.counterparty_chain_id.is_some())
+    {
+        line.push_str(&format!(" (native {})", route.base_denom));
+    }
+
+    let mut message = format!("🧭 Route ({} hop", route.hops.len() + route.unresolved_hops);
+    if route.hops.len() + route.unresolved_hops != 1 {
+        message.push('s');
+    }
+    message.push_str(&format!("):\n{line}"));
+
+    match check {
+        Some(RegistryTraceCheck::Canonical) => {
+            message.push_str("\n\n✅ Canonical path per the chain registry")
+        }
+        Some(RegistryTraceCheck::NonCanonical { ibc_denom, path }) => message.push_str(&format!(
+            "\n\n⚠️ Non-canonical path. The chain registry lists {path} ({ibc_denom}) for this asset; \
+            this denom is not fungible with it."
+        )),
+        Some(RegistryTraceCheck::NotListed) => {
+            message.push_str("\n\nℹ️ This asset is not listed in the chain registry")
+        }
+        None => {}
+    }
+
+    message
+}
+
+/// Computes the `ibc/` denom for a full trace path such as `transfer/channel-0/uatom`.
+pub fn ibc_denom_hash(full_path: &str) -> String {
+    format!(
+        "ibc/{}",
+        hex::encode_upper(Sha256::digest(full_path.as_bytes()))
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryTransferChannel {
+    pub source_channel: String,
+    pub destination_port: String,
+    pub destination_channel: String,
+    pub preferred: bool,
+    pub status: Option<String>,
+}
+
+/// `chain`'s `(port, channel)` ends of every channel in the `_IBC` path, each with the chain on
+/// the other end. Empty when `chain` is not part of the path.
+pub fn registry_channel_counterparties(
+    path: &IBCPath,
+    chain: &str,
+) -> Vec<((String, String), String)> {
+    let (source_is_chain_1, counterparty) = if path.chain_1.chain_name == chain {
+        (true, &path.chain_2.chain_name)
+    } else if path.chain_2.chain_name == chain {
+        (false, &path.chain_1.chain_name)
+    } else {
+        return Vec::new();
+    };
+
+    path.channels
+        .iter()
+        .map(|channel| {
+            let (port, channel) = if source_is_chain_1 {
+                (&channel.chain_1.port_id, &channel.chain_1.channel_id)
+            } else {
+                (&channel.chain_2.port_id, &channel.chain_2.channel_id)
+            };
+            ((port.clone(), channel.clone()), counterparty.clone())
+        })
+        .collect()
+}
+
+/// A copy of `path` without the channels whose end on either chain is in `closed`, a set of
+/// `(chain name, channel ID)` pairs seen closed on chain.
+pub fn without_closed_channels(path: &IBCPath, closed: &HashSet<(String, String)>) -> IBCPath {
+    let is_closed = |chain: &str, channel_id: &str| {
+        closed.contains(&(chain.to_string(), channel_id.to_string()))
+    };
+    let mut open = path.clone();
+    open.channels.retain(|channel| {
+        !is_closed(&path.chain_1.chain_name, &channel.chain_1.channel_id)
+            && !is_closed(&path.chain_2.chain_name, &channel.chain_2.channel_id)
+    });
+    open
+}
+
+/// Picks the transfer channel the registry recommends from `source_chain` across an `_IBC`
+/// path: preferred channels first, then live ones, never channels tagged as killed.
+pub fn canonical_transfer_channel(
+    path: &IBCPath,
+    source_chain: &str,
+) -> Option<RegistryTransferChannel> {
+    let source_is_chain_1 = path.chain_1.chain_name == source_chain;
+    if !source_is_chain_1 && path.chain_2.chain_name != source_chain {
+        return None;
+    }
+
+    path.channels
+        .iter()
+        .filter(|channel| {
+            channel.chain_1.port_id == "transfer" && channel.chain_2.port_id == "transfer"
+        })
+        .map(|channel| {
+            let (source_channel, destination_port, destination_channel) = if source_is_chain_1 {
+                (
+                    &channel.chain_1.channel_id,
+                    &channel.chain_2.port_id,
+                    &channel.chain_2.channel_id,
+                )
+            } else {
+                (
+                    &channel.chain_2.channel_id,
+                    &channel.chain_1.port_id,
+                    &channel.chain_1.channel_id,
+                )
+            };
+            let tags = channel.tags.as_ref();
+            RegistryTransferChannel {
+                source_channel: source_channel.clone(),
+                destination_port: destination_port.clone(),
+                destination_channel: destination_channel.clone(),
+                preferred: tags.map(|tags| tags.preferred).unwrap_or(false),
+                status: tags.and_then(|tags| tags.status.clone()),
+            }
+        })
+        .filter(|channel| channel.status.as_deref() != Some("killed"))
+        .min_by_key(|channel| {
+            (
+                !channel.preferred,
+                channel.status.as_deref() != Some("live"),
+            )
+        })
+}
+
+/// Matches a user query against an asset list by base denom, symbol or display name.
+pub fn find_registry_asset<'a>(assets: &'a AssetList, query: &str) -> Option<&'a Asset> {
+    assets
+        .assets
+        .iter()
+        .find(|asset| asset.base == query)
+        .or_else(|| {
+            assets.assets.iter().find(|asset| {
+                asset.symbol.eq_ignore_ascii_case(query)
+                    || asset.display.eq_ignore_ascii_case(query)
+            })
+        })
+}
+
+/// Full trace path a denom will have on the destination after crossing `channel`. Sending a
+/// voucher back over the channel it arrived on unwinds that hop instead of adding one.
+pub fn destination_trace_path(
+    channel: &RegistryTransferChannel,
+    source_denom: &str,
+    source_trace: Option<&IbcDenomTrace>,
+) -> String {
+    let Some(trace) = source_trace else {
+        return format!(
+            "{}/{}/{}",
+            channel.destination_port, channel.destination_channel, source_denom
+        );
+    };
+
+    match trace.hops.first() {
+        Some(first)
+            if first.port_id == "transfer" && first.channel_id == channel.source_channel =>
+        {
+            IbcDenomTrace {
+                hops: trace.hops[1..].to_vec(),
+                base_denom: trace.base_denom.clone(),
+            }
+            .full_path()
+        }
+        _ => format!(
+            "{}/{}/{}",
+            channel.destination_port,
+            channel.destination_channel,
+            trace.full_path()
+        ),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DenomVerification {
+    /// The destination resolved the hash through its denom query.
+    Trace,
+    /// No trace, but the destination reports a supply of the denom.
+    Supply(String),
+    NotFound,
+    Unchecked(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetDenomLookup {
+    pub source_chain: String,
+    pub destination_chain: String,
+    pub asset_label: Option<String>,
+    pub source_denom: String,
+    pub channel: RegistryTransferChannel,
+    pub full_path: String,
+    pub destination_denom: String,
+    pub verification: DenomVerification,
+}
+
+impl AssetDenomLookup {
+    pub fn new(
+        source_chain: &str,
+        destination_chain: &str,
+        asset_label: Option<String>,
+        source_denom: &str,
+        channel: RegistryTransferChannel,
+        source_trace: Option<&IbcDenomTrace>,
+    ) -> Self {
+        let full_path = destination_trace_path(&channel, source_denom, source_trace);
+        let destination_denom = if split_trace_path(&full_path).0.is_empty() {
+            full_path.clone()
+        } else {
+            ibc_denom_hash(&full_path)
+        };
+
+        AssetDenomLookup {
+            source_chain: source_chain.to_string(),
+            destination_chain: destination_chain.to_string(),
+            asset_label,
+            source_denom: source_denom.to_string(),
+            channel,
+            full_path,
+            destination_denom,
+            verification: DenomVerification::Unchecked("not checked".to_string()),
+        }
+    }
+}
+
+pub async fn query_supply_of_grpc(grpc_endpoint: &str, denom: &str) -> anyhow::Result<String> {
+    let mut client = BankQueryClient::new(connect_grpc(grpc_endpoint).await?);
+    let response = client
+        .supply_of(QuerySupplyOfRequest {
+            denom: denom.to_string(),
+        })
+        .await?
+        .into_inner();
+
+    Ok(response
+        .amount
+        .map(|coin| coin.amount)
+        .unwrap_or_else(|| "0".to_string()))
+}
+
+pub async fn query_supply_of_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+    denom: &str,
+) -> anyhow::Result<String> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for supply: {}", endpoint);
+        match query_supply_of_grpc(endpoint, denom).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!("Failed gRPC supply query with endpoint {}: {}", endpoint, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+/// Confirms a computed denom on the destination via its denom query, falling back to bank supply.
+pub async fn verify_destination_denom(
+    grpc_endpoints: &[chain::Grpc],
+    denom: &str,
+) -> DenomVerification {
+    if let Some(hash) = denom.strip_prefix("ibc/") {
+        if query_ibc_denom_grpc_first(grpc_endpoints, hash)
+            .await
+            .is_ok()
+        {
+            return DenomVerification::Trace;
+        }
+    }
+
+    match query_supply_of_grpc_first(grpc_endpoints, denom).await {
+        Ok(amount) if amount != "0" => DenomVerification::Supply(amount),
+        Ok(_) => DenomVerification::NotFound,
+        Err(e) => DenomVerification::Unchecked(e.to_string()),
+    }
+}
+
+pub fn format_asset_denom_lookup(lookup: &AssetDenomLookup) -> String {
+    let asset = lookup
+        .asset_label
+        .clone()
+        .unwrap_or_else(|| lookup.source_denom.clone());
+    let channel_note = match (lookup.channel.preferred, lookup.channel.status.as_deref()) {
+        (true, _) => " (preferred)".to_string(),
+        (false, Some(status)) => format!(" ({status})"),
+        (false, None) => String::new(),
+    };
+
+    let mut message = format!(
+        "🔎 {} from {} on {}\n\nSource Denom: {}\nChannel: {} {} → {} {}/{}{}\nPath: {}\n",
+        asset,
+        lookup.source_chain,
+        lookup.destination_chain,
+        lookup.source_denom,
+        lookup.source_chain,
+        lookup.channel.source_channel,
+        lookup.destination_chain,
+        lookup.channel.destination_port,
+        lookup.channel.destination_channel,
+        channel_note,
+        lookup.full_path
+    );
+
+    if lookup.destination_denom.starts_with("ibc/") {
+        message.push_str(&format!("IBC Denom: {}", lookup.destination_denom));
+    } else {
+        message.push_str(&format!(
+            "Denom: {} (returns to its native denom)",
+            lookup.destination_denom
+        ));
+    }
+
+    let verdict = match &lookup.verification {
+        DenomVerification::Trace => {
+            format!(
+                "✅ Confirmed on {} via denom query",
+                lookup.destination_chain
+            )
+        }
+        DenomVerification::Supply(amount) => format!(
+            "✅ Confirmed on {} via bank supply ({})",
+            lookup.destination_chain,
+            format_amount(amount)
+        ),
+        DenomVerification::NotFound => format!(
+            "⚠️ Not seen on {} yet; no transfer has used this path",
+            lookup.destination_chain
+        ),
+        DenomVerification::Unchecked(reason) => {
+            format!("ℹ️ Not verified on-chain: {reason}")
+        }
+    };
+    message.push_str(&format!("\n\n{verdict}"));
+    message
+}
+
+pub fn format_raw_denom_hash(full_path: &str) -> String {
+    format!(
+        "🔢 IBC Denom Hash\n\nPath: {}\nIBC Denom: {}",
+        full_path,
+        ibc_denom_hash(full_path)
+    )
+}
+
+/// A registry asset listed under an `ibc/` denom, indexed by that denom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryIbcAsset {
+    pub chain: String,
+    pub symbol: String,
+    /// Full trace path from the asset's `traces`, when the registry records one.
+    pub path: Option<String>,
+}
+
+/// Collects `(ibc denom, asset)` pairs from a raw `assetlist.json`. The denom is precomputed
+/// from the last IBC trace path, so assets that only record traces are indexed too.
+pub fn registry_ibc_denoms(chain: &str, assetlist_json: &Value) -> Vec<(String, RegistryIbcAsset)> {
+    let mut denoms = Vec::new();
+
+    for asset in assetlist_json["assets"].as_array().into_iter().flatten() {
+        let path = asset["traces"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .rev()
+            .find(|trace| trace["type"].as_str() == Some("ibc"))
+            .and_then(|trace| trace["chain"]["path"].as_str())
+            .map(str::to_string);
+        let denom = match (&path, asset["base"].as_str()) {
+            (Some(path), _) => ibc_denom_hash(path),
+            (None, Some(base)) if base.starts_with("ibc/") => base.to_string(),
+            _ => continue,
+        };
+
+        denoms.push((
+            denom,
+            RegistryIbcAsset {
+                chain: chain.to_string(),
+                symbol: asset["symbol"].as_str().unwrap_or_default().to_string(),
+                path,
+            },
+        ));
+    }
+
+    denoms
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalDenomMatch {
+    pub asset: RegistryIbcAsset,
+    /// The trace the chain itself returned, or `None` when it could not be confirmed.
+    pub trace: Option<IbcDenomTrace>,
+}
+
+pub fn format_global_denom_matches(ibc_denom: &str, matches: &[GlobalDenomMatch]) -> String {
+    if matches.is_empty() {
+        return format!(
+            "🌐 {ibc_denom}\n\nNo registry asset list contains this denom. \
+            Select a chain with /start and paste it again to query that chain directly."
+        );
+    }
+
+    let (confirmed, unconfirmed): (Vec<_>, Vec<_>) =
+        matches.iter().partition(|found| found.trace.is_some());
+    let mut message = format!(
+        "🌐 {}\n\nFound on {} chain{}",
+        ibc_denom,
+        confirmed.len(),
+        if confirmed.len() == 1 { "" } else { "s" }
+    );
+
+    for found in &confirmed {
+        let trace = found.trace.as_ref().expect("partitioned on trace");
+        message.push_str(&format!(
+            "\n\n✅ {} ({})\nPath: {}\nBase Denom: {}",
+            found.asset.chain,
+            if found.asset.symbol.is_empty() {
+                trace.base_denom.as_str()
+            } else {
+                found.asset.symbol.as_str()
+            },
+            trace.path(),
+            trace.base_denom
+        ));
+    }
+
+    if !unconfirmed.is_empty() {
+        message.push_str("\n\nListed in the registry but not confirmed on-chain:");
+        for found in unconfirmed {
+            message.push_str(&format!(
+                "\n• {} ({}){}",
+                found.asset.chain,
+                found.asset.symbol,
+                found
+                    .asset
+                    .path
+                    .as_ref()
+                    .map(|path| format!(": {path}"))
+                    .unwrap_or_default()
+            ));
+        }
+    }
+
+    message
+}
+
+/// Chains known to run packet-forward-middleware on their transfer stack. The registry has no
+/// field for it, so multi-hop routes only pass through these.
+pub const PFM_HUBS: [&str; 9] = [
+    "cosmoshub",
+    "osmosis",
+    "noble",
+    "neutron",
+    "stride",
+    "juno",
+    "persistence",
+    "kujira",
+    "umee",
+];
+
+pub const MAX_ROUTE_HOPS: usize = 3;
+pub const MAX_ROUTES_SHOWN: usize = 5;
+
+/// Which chains the registry connects, built from the `_IBC` file names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IbcGraph {
+    neighbours: HashMap<String, BTreeSet<String>>,
+}
+
+impl IbcGraph {
+    /// Path files are named `<chain_a>-<chain_b>`; chain names may themselves contain `-`, so
+    /// each split point is checked against the known chain list.
+    pub fn from_path_names(path_names: &[String], chains: &[String]) -> Self {
+        let known: HashSet<&str> = chains.iter().map(String::as_str).collect();
+        let mut graph = IbcGraph::default();
+
+        for name in path_names {
+            let split = name
+                .match_indices('-')
+                .map(|(i, _)| i)
+                .find(|&i| known.contains(&name[..i]) && known.contains(&name[i + 1..]));
+            match split {
+                Some(i) => graph.add_edge(&name[..i], &name[i + 1..]),
+                None => log::debug!("Skipping IBC path with unknown chains: {}", name),
+            }
+        }
+        graph
+    }
+
+    fn add_edge(&mut self, chain_a: &str, chain_b: &str) {
+        self.neighbours
+            .entry(chain_a.to_string())
+            .or_default()
+            .insert(chain_b.to_string());
+        self.neighbours
+            .entry(chain_b.to_string())
+            .or_default()
+            .insert(chain_a.to_string());
+    }
+
+    pub fn contains(&self, chain: &str) -> bool {
+        self.neighbours.contains_key(chain)
+    }
+
+    pub fn neighbours(&self, chain: &str) -> Vec<String> {
+        self.neighbours
+            .get(chain)
+            .map(|neighbours| neighbours.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn connected(&self, chain_a: &str, chain_b: &str) -> bool {
+        self.neighbours
+            .get(chain_a)
+            .is_some_and(|neighbours| neighbours.contains(chain_b))
+    }
+
+    /// Every loop-free chain sequence from `source` to `destination` of at most `max_hops`
+    /// hops whose intermediate chains are all `hubs`.
+    pub fn candidate_routes(
+        &self,
+        source: &str,
+        destination: &str,
+        hubs: &[&str],
+        max_hops: usize,
+    ) -> Vec<Vec<String>> {
+        let mut routes = Vec::new();
+        let mut stack = vec![vec![source.to_string()]];
+
+        while let Some(route) = stack.pop() {
+            let last = route.last().expect("routes start with the source");
+            if self.connected(last, destination) {
+                let mut complete = route.clone();
+                complete.push(destination.to_string());
+                routes.push(complete);
+            }
+            if route.len() >= max_hops {
+                continue;
+            }
+            for hub in hubs {
+                if *hub != destination
+                    && !route.iter().any(|chain| chain == hub)
+                    && self.connected(last, hub)
+                {
+                    let mut next = route.clone();
+                    next.push(hub.to_string());
+                    stack.push(next);
+                }
+            }
+        }
+        routes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteHop {
+    pub from: String,
+    pub to: String,
+    pub channel: RegistryTransferChannel,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IbcRoute {
+    pub hops: Vec<RouteHop>,
+}
+
+impl IbcRoute {
+    pub fn preferred_hops(&self) -> usize {
+        self.hops.iter().filter(|hop| hop.channel.preferred).count()
+    }
+}
+
+/// Fewest hops first, then the routes using the most registry-preferred channels.
+pub fn rank_ibc_routes(routes: &mut [IbcRoute]) {
+    routes.sort_by_key(|route| {
+        (
+            route.hops.len(),
+            route.hops.len() - route.preferred_hops(),
+            route
+                .hops
+                .iter()
+                .map(|hop| hop.to.clone())
+                .collect::<Vec<_>>(),
+        )
+    });
+}
+
+pub fn format_ibc_routes(source: &str, destination: &str, routes: &[IbcRoute]) -> String {
+    if routes.is_empty() {
+        return format!(
+            "🧭 {source} → {destination}\n\nNo route found within {MAX_ROUTE_HOPS} hops. \
+            Multi-hop routes only pass through chains known to run packet forwarding: {}.",
+            PFM_HUBS.join(", ")
+        );
+    }
+
+    let mut message = format!(
+        "🧭 {} → {}\n\n{} route{} found",
+        source,
+        destination,
+        routes.len(),
+        if routes.len() == 1 { "" } else { "s" }
+    );
+    if routes.len() > MAX_ROUTES_SHOWN {
+        message.push_str(&format!(", showing the best {MAX_ROUTES_SHOWN}"));
+    }
+
+    for (index, route) in routes.iter().take(MAX_ROUTES_SHOWN).enumerate() {
+        let chains: Vec<&str> = std::iter::once(source)
+            .chain(route.hops.iter().map(|hop| hop.to.as_str()))
+            .collect();
+        message.push_str(&format!(
+            "\n\n{}. {} ({} hop{}{})",
+            index + 1,
+            chains.join(" → "),
+            route.hops.len(),
+            if route.hops.len() == 1 { "" } else { "s" },
+            if route.hops.len() > 1 {
+                ", via PFM"
+            } else {
+                ""
+            }
+        ));
+        for hop in &route.hops {
+            message.push_str(&format!(
+                "\n• {} {} → {} {}{}{}",
+                hop.from,
+                hop.channel.source_channel,
+                hop.to,
+                hop.channel.destination_channel,
+                if hop.channel.preferred {
+                    " ⭐ preferred"
+                } else {
+                    ""
+                },
+                hop.channel
+                    .status
+                    .as_ref()
+                    .filter(|status| status.as_str() != "live")
+                    .map(|status| format!(" ({status})"))
+                    .unwrap_or_default()
+            ));
+        }
+    }
+
+    message
+}
+
+/// One end of a channel as a registry `_IBC` file describes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryChannelEnd {
+    pub chain_name: String,
+    /// `chain_id` from the chain's registry `chain.json`, when it could be read.
+    pub chain_id: Option<String>,
+    pub client_id: String,
+    pub connection_id: String,
+    pub port_id: String,
+    pub channel_id: String,
+}
+
+impl RegistryChannelEnd {
+    /// Both ends of every channel in an `_IBC` file, oriented so `chain` is the first.
+    pub fn pairs_from_path(
+        path: &IBCPath,
+        chain: &str,
+        chain_ids: &HashMap<String, String>,
+    ) -> Vec<(RegistryChannelEnd, RegistryChannelEnd)> {
+        let end = |chain_name: &str,
+                   client_id: &str,
+                   connection_id: &str,
+                   port_id: &str,
+                   channel_id: &str| {
+            RegistryChannelEnd {
+                chain_name: chain_name.to_string(),
+                chain_id: chain_ids.get(chain_name).cloned(),
+                client_id: client_id.to_string(),
+                connection_id: connection_id.to_string(),
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+            }
+        };
+
+        path.channels
+            .iter()
+            .map(|channel| {
+                let first = end(
+                    &path.chain_1.chain_name,
+                    &path.chain_1.client_id,
+                    &path.chain_1.connection_id,
+                    &channel.chain_1.port_id,
+                    &channel.chain_1.channel_id,
+                );
+                let second = end(
+                    &path.chain_2.chain_name,
+                    &path.chain_2.client_id,
+                    &path.chain_2.connection_id,
+                    &channel.chain_2.port_id,
+                    &channel.chain_2.channel_id,
+                );
+                if path.chain_2.chain_name == chain {
+                    (second, first)
+                } else {
+                    (first, second)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Differences between the registry's view of one channel end and what that chain reports.
+pub fn channel_end_mismatches(
+    expected: &RegistryChannelEnd,
+    counterparty: &RegistryChannelEnd,
+    actual: &IbcChannelInfo,
+) -> Vec<String> {
+    let mut issues = Vec::new();
+    let mut compare = |field: &str, registry: &str, on_chain: &str| {
+        if registry != on_chain {
+            issues.push(format!(
+                "{}: {field} is {on_chain} on-chain, registry says {registry}",
+                expected.chain_name
+            ));
+        }
+    };
+    compare("client", &expected.client_id, &actual.client_id);
+    compare("connection", &expected.connection_id, &actual.connection_id);
+    compare(
+        "counterparty client",
+        &counterparty.client_id,
+        &actual.counterparty_client_id,
+    );
+    compare(
+        "counterparty connection",
+        &counterparty.connection_id,
+        &actual.counterparty_connection_id,
+    );
+    compare(
+        "counterparty channel",
+        &counterparty.channel_id,
+        &actual.counterparty_channel_id,
+    );
+    compare(
+        "counterparty port",
+        &counterparty.port_id,
+        &actual.counterparty_port_id,
+    );
+
+    if actual.channel_state != "Open" {
+        issues.push(format!(
+            "{}: channel {} is {}",
+            expected.chain_name, expected.channel_id, actual.channel_state
+        ));
+    }
+    if actual.connection_state != "Open" {
+        issues.push(format!(
+            "{}: connection {} is {}",
+            expected.chain_name, actual.connection_id, actual.connection_state
+        ));
+    }
+    if let (Some(registry_chain_id), Some(tracked_chain_id)) = (
+        counterparty.chain_id.as_deref(),
+        actual.counterparty_chain_id.as_deref(),
+    ) {
+        if registry_chain_id != tracked_chain_id {
+            issues.push(format!(
+                "{}: client {} tracks {tracked_chain_id}, but {} is {registry_chain_id} in the registry",
+                expected.chain_name, actual.client_id, counterparty.chain_name
+            ));
+        }
+    }
+    if let Some(registry_chain_id) = expected.chain_id.as_deref() {
+        if registry_chain_id != actual.chain_id {
+            issues.push(format!(
+                "{}: node reports chain-id {}, registry says {registry_chain_id}",
+                expected.chain_name, actual.chain_id
+            ));
+        }
+    }
+
+    issues
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelConsistency {
+    pub local: RegistryChannelEnd,
+    pub remote: RegistryChannelEnd,
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    pub checks: Vec<ChannelConsistency>,
+    /// Counterparties whose `_IBC` file could not be fetched.
+    pub unreadable_paths: Vec<String>,
+}
+
+pub fn format_consistency_report(chain: &str, report: &ConsistencyReport) -> String {
+    let mismatched: Vec<&ChannelConsistency> = report
+        .checks
+        .iter()
+        .filter(|check| !check.issues.is_empty())
+        .collect();
+    let counterparties: HashSet<&str> = report
+        .checks
+        .iter()
+        .map(|check| check.remote.chain_name.as_str())
+        .collect();
+
+    let mut message = format!(
+        "🔎 Registry IBC Check for {}\n\n{} channel{} on {} path{} checked: {} consistent, {} with mismatches",
+        chain,
+        report.checks.len(),
+        if report.checks.len() == 1 { "" } else { "s" },
+        counterparties.len(),
+        if counterparties.len() == 1 { "" } else { "s" },
+        report.checks.len() - mismatched.len(),
+        mismatched.len()
+    );
+    if !report.unreadable_paths.is_empty() {
+        message.push_str(&format!(
+            "\nUnreadable _IBC files: {}",
+            report.unreadable_paths.join(", ")
+        ));
+    }
+
+    for check in mismatched {
+        message.push_str(&format!(
+            "\n\n❌ {} ↔ {} ({}/{} ↔ {}/{})",
+            check.local.chain_name,
+            check.remote.chain_name,
+            check.local.port_id,
+            check.local.channel_id,
+            check.remote.port_id,
+            check.remote.channel_id
+        ));
+        for issue in &check.issues {
+            message.push_str(&format!("\n• {issue}"));
+        }
+    }
+
+    message
+}
+
+/// Relative timeout PFM applies to each forwarded hop.
+pub const FORWARD_HOP_TIMEOUT: &str = "10m";
+pub const FORWARD_RETRIES: u8 = 2;
+/// Placeholder receiver for intermediate hops; PFM ignores it, and being invalid bech32 it
+/// cannot strand funds on a hub that lacks the middleware.
+pub const FORWARD_INTERMEDIATE_RECEIVER: &str = "pfm";
+
+/// Which prompt of the memo builder dialogue the next message answers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum MemoStep {
+    #[default]
+    Route,
+    Asset,
+    Receiver,
+    Swap,
+}
+
+/// Osmosis' crosschain-swaps (XCS v2) contract that IBC-hook swap memos call into.
+pub const OSMOSIS_CROSSCHAIN_SWAPS_CONTRACT: &str =
+    "osmo1uwk8xc6q0s6t5qcpr6rht3n4gpf8lh5l0lu3ljmdjl5slz9ygu4ucm8s6m";
+
+/// A swap through an Osmosis crosschain-swaps contract via IBC hooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsmosisSwap {
+    pub contract: String,
+    pub output_denom: String,
+    pub slippage_percent: String,
+}
+
+/// What the memo builder has collected so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoDraft {
+    pub step: MemoStep,
+    pub hops: Vec<RouteHop>,
+    pub amount: String,
+    pub denom: String,
+    pub receiver: String,
+}
+
+impl MemoDraft {
+    pub fn destination(&self) -> Option<&str> {
+        self.hops.last().map(|hop| hop.to.as_str())
+    }
+}
+
+/// Converts a decimal display amount to base units, refusing more precision than `exponent`.
+pub fn to_base_units(amount: &str, exponent: u32) -> Option<String> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+        || fraction.len() > exponent as usize
+    {
+        return None;
+    }
+
+    let digits = format!("{whole}{fraction:0<width$}", width = exponent as usize);
+    let value: u128 = digits.parse().ok()?;
+    (value > 0).then(|| value.to_string())
+}
+
+/// Builds the memo for the first transfer of `hops`, nesting one PFM `forward` per later hop
+/// and, with a swap, a `wasm` hook for Osmosis as the innermost action. Returns the receiver
+/// for the first transfer alongside the memo, which is `None` for a plain direct transfer.
+pub fn build_forward_memo(
+    hops: &[RouteHop],
+    final_receiver: &str,
+    swap: Option<&OsmosisSwap>,
+) -> (String, Option<Value>) {
+    let last_receiver = swap
+        .map(|swap| swap.contract.clone())
+        .unwrap_or_else(|| final_receiver.to_string());
+    let mut next = swap.map(|swap| {
+        serde_json::json!({
+            "wasm": {
+                "contract": swap.contract,
+                "msg": {
+                    "osmosis_swap": {
+                        "output_denom": swap.output_denom,
+                        "slippage": {
+                            "twap": {
+                                "slippage_percentage": swap.slippage_percent,
+                                "window_seconds": 10
+                            }
+                        },
+                        "receiver": final_receiver,
+                        "on_failed_delivery": "do_nothing"
+                    }
+                }
+            }
+        })
+    });
+
+    for (index, hop) in hops.iter().enumerate().skip(1).rev() {
+        let receiver = if index == hops.len() - 1 {
+            last_receiver.clone()
+        } else {
+            FORWARD_INTERMEDIATE_RECEIVER.to_string()
+        };
+        let mut forward = serde_json::json!({
+            "receiver": receiver,
+            "port": "transfer",
+            "channel": hop.channel.source_channel,
+            "timeout": FORWARD_HOP_TIMEOUT,
+            "retries": FORWARD_RETRIES
+        });
+        if let Some(next) = next.take() {
+            forward["next"] = next;
+        }
+        next = Some(serde_json::json!({ "forward": forward }));
+    }
+
+    let first_receiver = if hops.len() > 1 {
+        FORWARD_INTERMEDIATE_RECEIVER.to_string()
+    } else {
+        last_receiver
+    };
+    (first_receiver, next)
+}
+
+/// Checks a built memo has the shape PFM and IBC hooks expect before it is shown.
+pub fn validate_forward_memo(memo: &Value) -> anyhow::Result<()> {
+    let mut current = memo;
+    loop {
+        if let Some(forward) = current.get("forward") {
+            for field in ["receiver", "port", "channel"] {
+                if forward[field].as_str().is_none_or(str::is_empty) {
+                    anyhow::bail!("forward memo is missing {field}");
+                }
+            }
+            if !forward["channel"]
+                .as_str()
+                .is_some_and(|channel| channel.starts_with("channel-"))
+            {
+                anyhow::bail!("forward memo has an invalid channel");
+            }
+            match forward.get("next") {
+                Some(next) => current = next,
+                None => return Ok(()),
+            }
+        } else if let Some(wasm) = current.get("wasm") {
+            if wasm["contract"].as_str().is_none_or(str::is_empty) || !wasm["msg"].is_object() {
+                anyhow::bail!("wasm hook memo needs a contract and a msg object");
+            }
+            return Ok(());
+        } else {
+            anyhow::bail!("memo must contain a forward or wasm action");
+        }
+    }
+}
+
+/// The memo and the `ibc transfer` command that sends it, for a completed draft.
+pub fn format_forward_memo(
+    chain: &ChainInfo,
+    draft: &MemoDraft,
+    swap: Option<&OsmosisSwap>,
+) -> anyhow::Result<String> {
+    let first_hop = draft
+        .hops
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("Route has no hops"))?;
+    let (first_receiver, memo) = build_forward_memo(&draft.hops, &draft.receiver, swap);
+    if let Some(memo) = &memo {
+        validate_forward_memo(memo)?;
+    }
+
+    let route: Vec<&str> = std::iter::once(first_hop.from.as_str())
+        .chain(draft.hops.iter().map(|hop| hop.to.as_str()))
+        .collect();
+    let mut message = format!("🧾 Transfer Memo\n\nRoute: {}", route.join(" → "));
+    for hop in &draft.hops {
+        message.push_str(&format!(
+            "\n• {} {} → {} {}",
+            hop.from, hop.channel.source_channel, hop.to, hop.channel.destination_channel
+        ));
+    }
+    if let Some(swap) = swap {
+        message.push_str(&format!(
+            "\nSwap on Osmosis to {} (max {}% slippage) via {}",
+            swap.output_denom, swap.slippage_percent, swap.contract
+        ));
+    }
+    message.push_str(&format!("\nFinal receiver: {}", draft.receiver));
+
+    let timeout_nanos = 600_000_000_000u64 * draft.hops.len() as u64;
+    let memo_arg = match &memo {
+        Some(memo) => {
+            let compact = serde_json::to_string(memo)?;
+            message.push_str(&format!(
+                "\n\nMemo:\n{}",
+                serde_json::to_string_pretty(memo)?
+            ));
+            format!(" \\\n  --memo '{compact}'")
+        }
+        None => String::new(),
+    };
+    message.push_str(&format!(
+        "\n\nCLI:\n{} tx ibc-transfer transfer transfer {} {} {}{}{} \\\n  --packet-timeout-timestamp {} \\\n  --chain-id {} --node {} \\\n  --from <key> --gas auto --gas-adjustment 1.4",
+        if chain.daemon_name.is_empty() { "<daemon>" } else { chain.daemon_name.as_str() },
+        first_hop.channel.source_channel,
+        first_receiver,
+        draft.amount,
+        draft.denom,
+        memo_arg,
+        timeout_nanos,
+        chain.chain_id,
+        first_endpoint_address(&chain.apis.rpc)
+    ));
+
+    Ok(message)
+}
+
+pub fn format_channel_input(input: &str) -> String {
+    // If user enters just a number, format it as channel-N
+    // If user enters channel-N, use as is
+    if input.starts_with("channel-") {
+        input.to_string()
+    } else if input.parse::<u32>().is_ok() {
+        format!("channel-{}", input)
+    } else {
+        input.to_string()
+    }
+}
+
+pub fn escape_markdown(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '_' | '*' | '[' | ']' | '(' | ')' | '~' | '`' | '>' | '#' | '+' | '-' | '=' | '|'
+            | '{' | '}' | '.' | '!' => format!("\\{}", c),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+/// ABCI info response containing version and block data
+#[derive(Debug, Clone)]
+pub struct AbciInfo {
+    pub version: String,
+    pub last_block_height: String,
+    pub last_block_app_hash: String,
+}
+
+fn bytes_to_upper_hex(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        return "Unknown".to_string();
+    }
+
+    bytes.iter().map(|byte| format!("{byte:02X}")).collect()
+}
+
+pub async fn query_abci_info_grpc(grpc_endpoint: &str) -> Option<AbciInfo> {
+    let channel = connect_grpc(grpc_endpoint).await.ok()?;
+    let mut client = TendermintServiceClient::new(channel);
+
+    let node_info = client
+        .get_node_info(GetNodeInfoRequest {})
+        .await
+        .ok()?
+        .into_inner();
+    let version = node_info
+        .application_version
+        .map(|version| {
+            if !version.version.is_empty() {
+                version.version
+            } else if !version.cosmos_sdk_version.is_empty() {
+                version.cosmos_sdk_version
+            } else {
+                "Unknown".to_string()
+            }
+        })
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let latest_block = client
+        .get_latest_block(GetLatestBlockRequest {})
+        .await
+        .ok()?
+        .into_inner();
+
+    if let Some(sdk_block) = latest_block.sdk_block {
+        let header = sdk_block.header?;
+        return Some(AbciInfo {
+            version,
+            last_block_height: header.height.to_string(),
+            last_block_app_hash: bytes_to_upper_hex(&header.app_hash),
+        });
+    }
+
+    let header = latest_block.block?.header?;
+    Some(AbciInfo {
+        version,
+        last_block_height: header.height.to_string(),
+        last_block_app_hash: bytes_to_upper_hex(&header.app_hash),
+    })
+}
+
+/// Balance entry from bank query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Balance {
+    pub denom: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IbcDenomHop {
+    pub port_id: String,
+    pub channel_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IbcDenomTrace {
+    /// Hops in trace order: the first is the channel the funds arrived on, the last leads to
+    /// the origin chain.
+    pub hops: Vec<IbcDenomHop>,
+    pub base_denom: String,
+}
+
+impl IbcDenomTrace {
+    /// Builds a trace from a legacy `DenomTrace` path such as `transfer/channel-0`.
+    pub fn from_path(path: &str, base_denom: &str) -> Self {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        IbcDenomTrace {
+            hops: segments
+                .chunks_exact(2)
+                .map(|pair| IbcDenomHop {
+                    port_id: pair[0].to_string(),
+                    channel_id: pair[1].to_string(),
+                })
+                .collect(),
+            base_denom: base_denom.to_string(),
+        }
+    }
+
+    pub fn path(&self) -> String {
+        self.hops
+            .iter()
+            .map(|hop| format!("{}/{}", hop.port_id, hop.channel_id))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    pub fn full_path(&self) -> String {
+        if self.hops.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{}/{}", self.path(), self.base_denom)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletBalance {
+    pub balance: Balance,
+    pub ibc_trace: Option<IbcDenomTrace>,
+    pub asset_label: Option<String>,
+    /// From this chain's asset list, or the origin chain's for traced IBC assets.
+    pub display: Option<DisplayUnit>,
+    /// USD per display unit; `None` when no price source knows the asset.
+    pub usd_price: Option<f64>,
+}
+
+impl WalletBalance {
+    pub fn usd_value(&self) -> Option<f64> {
+        Some(self.display.as_ref()?.value(&self.balance.amount)? * self.usd_price?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsmosisPoolAsset {
+    pub denom: String,
+    pub amount: String,
+    pub weight: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsmosisPoolInfo {
+    pub pool_type: String,
+    pub address: Option<String>,
+    pub swap_fee: Option<String>,
+    pub total_shares: Option<String>,
+    pub assets: Vec<OsmosisPoolAsset>,
+    pub current_tick_liquidity: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OsmosisGaugeIncentive {
+    pub gauge_id: String,
+    pub duration: String,
+    pub incentive_percentage: String,
+    pub coins: Vec<Balance>,
+    pub distributed_coins: Vec<Balance>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OsmosisClIncentive {
+    pub incentive_id: String,
+    pub denom: String,
+    pub remaining_amount: String,
+    pub emission_rate: String,
+    pub start_time: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct OsmosisPoolIncentives {
+    pub gauges: Vec<OsmosisGaugeIncentive>,
+    pub cl_records: Vec<OsmosisClIncentive>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OsmosisTokenPrice {
+    pub name: String,
+    pub symbol: String,
+    pub denom: String,
+    pub quote_symbol: String,
+    pub quote_denom: String,
+    pub price: String,
+}
+
+#[derive(Debug, Clone)]
+struct OsmosisTokenMetadata {
+    name: String,
+    symbol: String,
+    denom: String,
+    preview: bool,
+}
+
+async fn get_json(client: &reqwest::Client, url: &str) -> anyhow::Result<Value> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "request failed with status {} for {}",
+            response.status(),
+            url
+        ));
+    }
+
+    Ok(response.json().await?)
+}
+
+fn encode_query_component(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn resolve_token_metadata(metadata: &Value, input: &str) -> Option<OsmosisTokenMetadata> {
+    let needle = input.trim();
+    let mut preview_match = None;
+
+    for token in metadata.as_object()?.values() {
+        let denom = token["coinMinimalDenom"].as_str().unwrap_or_default();
+        let symbol = token["symbol"].as_str().unwrap_or_default();
+
+        if denom.eq_ignore_ascii_case(needle) || symbol.eq_ignore_ascii_case(needle) {
+            let candidate = OsmosisTokenMetadata {
+                name: token["name"].as_str().unwrap_or(symbol).to_string(),
+                symbol: symbol.to_string(),
+                denom: denom.to_string(),
+                preview: token["preview"].as_bool().unwrap_or(false),
+            };
+
+            if !candidate.preview {
+                return Some(candidate);
+            }
+            preview_match = Some(candidate);
+        }
+    }
+
+    preview_match
+}
+
+pub const BALANCE_PAGE_LIMIT: u64 = 100;
+/// Upper bound for the fetch-all summary, so a pathological account cannot pin the bot.
+pub const MAX_BALANCE_PAGES: usize = 50;
+
+pub async fn query_balances_grpc(
+    grpc_endpoint: &str,
+    address: &str,
+    pagination_key: Option<&str>,
+) -> anyhow::Result<(Vec<Balance>, Option<String>)> {
+    let key = match pagination_key {
+        Some(key) => general_purpose::STANDARD.decode(key).unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    let mut client = BankQueryClient::new(connect_grpc(grpc_endpoint).await?);
+    let response = client
+        .all_balances(QueryAllBalancesRequest {
+            address: address.to_string(),
+            pagination: Some(PageRequest {
+                key,
+                offset: 0,
+                limit: BALANCE_PAGE_LIMIT,
+                count_total: false,
+                reverse: false,
+            }),
+            resolve_denom: false,
+        })
+        .await?
+        .into_inner();
+
+    let balances = response
+        .balances
+        .into_iter()
+        .map(|coin| Balance {
+            denom: coin.denom,
+            amount: coin.amount,
+        })
+        .collect();
+
+    let next_key = response
+        .pagination
+        .and_then(|pagination| (!pagination.next_key.is_empty()).then_some(pagination.next_key))
+        .map(|next_key| general_purpose::STANDARD.encode(next_key));
+
+    Ok((balances, next_key))
+}
+
+pub async fn query_balances_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+    address: &str,
+    pagination_key: Option<&str>,
+) -> anyhow::Result<(Vec<Balance>, Option<String>)> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for balances: {}", endpoint);
+        match query_balances_grpc(endpoint, address, pagination_key).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!(
+                    "Failed gRPC balance query with endpoint {}: {}",
+                    endpoint,
+                    e
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+/// CW20 contracts queried at once for a wallet's token balances.
+const CW20_QUERY_CONCURRENCY: usize = 8;
+
+/// A CW20 token from an asset list, where `base` is the `cw20:<contract>` denom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryCw20Token {
+    pub base: String,
+    pub contract: String,
+}
+
+/// CW20 entries of a raw asset list; the typed `AssetList` drops `type_asset` and
+/// `address`, so this reads the JSON.
+pub fn registry_cw20_tokens(assetlist_json: &Value) -> Vec<RegistryCw20Token> {
+    assetlist_json["assets"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|asset| {
+            let base = asset["base"].as_str()?;
+            let contract = base.strip_prefix("cw20:");
+            if asset["type_asset"].as_str() != Some("cw20") && contract.is_none() {
+                return None;
+            }
+            let contract = asset["address"].as_str().or(contract)?;
+            Some(RegistryCw20Token {
+                base: base.to_string(),
+                contract: contract.to_string(),
+            })
+        })
+        .collect()
+}
+
+async fn query_cw20_balance(
+    transport: Channel,
+    contract: &str,
+    address: &str,
+) -> anyhow::Result<String> {
+    let response: QuerySmartContractStateResponse = grpc_unary(
+        transport,
+        "/cosmwasm.wasm.v1.Query/SmartContractState",
+        QuerySmartContractStateRequest {
+            address: contract.to_string(),
+            query_data: serde_json::to_vec(
+                &serde_json::json!({ "balance": { "address": address } }),
+            )?,
+        },
+    )
+    .await?;
+    let data: Value = serde_json::from_slice(&response.data)?;
+
+    data["balance"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("Unexpected CW20 balance response from {contract}"))
+}
+
+/// Non-zero balances of `tokens` held by `address`, keyed by the `cw20:` base denom. A single
+/// failed contract is skipped, but if every query fails the endpoint is treated as broken so
+/// the caller can try the next one.
+pub async fn query_cw20_balances_grpc(
+    grpc_endpoint: &str,
+    tokens: &[RegistryCw20Token],
+    address: &str,
+) -> anyhow::Result<Vec<Balance>> {
+    let transport = connect_grpc(grpc_endpoint).await?;
+    let results: Vec<anyhow::Result<Option<Balance>>> = stream::iter(tokens.to_vec())
+        .map(|token| {
+            let transport = transport.clone();
+            let address = address.to_string();
+            async move {
+                let amount = query_cw20_balance(transport, &token.contract, &address)
+                    .await
+                    .map_err(|e| {
+                        log::warn!("Failed CW20 balance query for {}: {}", token.contract, e);
+                        e
+                    })?;
+                Ok((parse_amount(&amount) > 0).then_some(Balance {
+                    denom: token.base,
+                    amount,
+                }))
+            }
+        })
+        .buffer_unordered(CW20_QUERY_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut balances = Vec::new();
+    let mut succeeded = 0;
+    let mut last_error = None;
+    for result in results {
+        match result {
+            Ok(balance) => {
+                succeeded += 1;
+                balances.extend(balance);
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    if succeeded == 0 {
+        if let Some(e) = last_error {
+            return Err(e.context(format!(
+                "every CW20 balance query failed on {grpc_endpoint}"
+            )));
+        }
+    }
+    balances.sort_by(|a, b| a.denom.cmp(&b.denom));
+    Ok(balances)
+}
+
+pub async fn query_cw20_balances_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+    tokens: &[RegistryCw20Token],
+    address: &str,
+) -> anyhow::Result<Vec<Balance>> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for CW20 balances: {}", endpoint);
+        match query_cw20_balances_grpc(endpoint, tokens, address).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!(
+                    "Failed gRPC CW20 balance query with endpoint {}: {}",
+                    endpoint,
+                    e
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+/// Format amount with thousands separators
+pub fn format_amount(amount: &str) -> String {
+    // Parse the amount string as a number and format with commas
+    if let Ok(num) = amount.parse::<u128>() {
+        let s = num.to_string();
+        let mut result = String::new();
+        for (i, c) in s.chars().rev().enumerate() {
+            if i > 0 && i % 3 == 0 {
+                result.insert(0, ',');
+            }
+            result.insert(0, c);
+        }
+        result
+    } else {
+        amount.to_string()
+    }
+}
+
+/// Truncate IBC hash for display (e.g., "ibc/27A6..." showing first 8 chars after ibc/)
+pub fn truncate_ibc_hash(hash: &str) -> String {
+    if hash.len() > 12 {
+        format!("{}...", &hash[..12])
+    } else {
+        hash.to_string()
+    }
+}
+
+pub fn prioritize_grpc_endpoints(endpoints: &[chain::Grpc]) -> Vec<String> {
+    let mut polkachu = Vec::new();
+    let mut others = Vec::new();
+
+    for endpoint in endpoints {
+        let Some(uri) = normalize_grpc_uri(&endpoint.address) else {
+            continue;
+        };
+
+        let is_polkachu = endpoint
+            .provider
+            .as_deref()
+            .unwrap_or_default()
+            .to_lowercase()
+            .contains("polkachu")
+            || endpoint.address.to_lowercase().contains("polkachu.com");
+
+        if is_polkachu {
+            polkachu.push(uri);
+        } else {
+            others.push(uri);
+        }
+    }
+
+    polkachu.extend(others);
+    polkachu
+}
+
+fn escape_markdown_code(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('`', "\\`")
+}
+
+fn display_asset_label(
+    denom: &str,
+    trace: Option<&IbcDenomTrace>,
+    asset_label: Option<&str>,
+) -> String {
+    if let Some(asset_label) = asset_label.filter(|label| !label.is_empty()) {
+        return asset_label.to_string();
+    }
+
+    let display = trace.map(|t| t.base_denom.as_str()).unwrap_or(denom);
+    display
+        .rsplit('/')
+        .next()
+        .filter(|segment| !segment.is_empty())
+        .unwrap_or(display)
+        .to_string()
+}
+
+pub fn format_wallet_balances(
+    address: &str,
+    chain: &str,
+    balances: &[WalletBalance],
+    page: usize,
+    more_available: bool,
+) -> String {
+    let display_address = if address.len() > 20 {
+        format!("{}...", &address[..20])
+    } else {
+        address.to_string()
+    };
+
+    let mut message = format!(
+        "💰 *Balances for* `{}` on *{}*",
+        escape_markdown_code(&display_address),
+        escape_markdown(chain)
+    );
+
+    for (index, wallet_balance) in balances.iter().enumerate() {
+        if index > 0 {
+            message.push_str("\n\n\\-\\-\\-");
+        }
+
+        let label = display_asset_label(
+            &wallet_balance.balance.denom,
+            wallet_balance.ibc_trace.as_ref(),
+            wallet_balance.asset_label.as_deref(),
+        );
+        match &wallet_balance.display {
+            Some(unit) => message.push_str(&format!(
+                "\n\n*{}*\nAmount: `{}`\nBase Amount: `{}`",
+                escape_markdown(&label),
+                escape_markdown_code(&unit.format(&wallet_balance.balance.amount)),
+                escape_markdown_code(&wallet_balance.balance.amount)
+            )),
+            None => message.push_str(&format!(
+                "\n\n*{}*\nAmount: `{}`",
+                escape_markdown(&label),
+                escape_markdown_code(&format_amount(&wallet_balance.balance.amount))
+            )),
+        }
+
+        match wallet_balance.usd_value() {
+            Some(value) => message.push_str(&format!(
+                "\nValue: `{}`",
+                escape_markdown_code(&format_usd(value))
+            )),
+            None => message.push_str("\nValue: _unpriced_"),
+        }
+
+        if let Some(trace) = &wallet_balance.ibc_trace {
+            message.push_str(&format!(
+                "\nIBC Denom: `{}`\nIBC Path: `{}` \\({} hop{}\\)\nBase Denom: `{}`",
+                escape_markdown_code(&wallet_balance.balance.denom),
+                escape_markdown_code(&trace.path()),
+                trace.hops.len(),
+                if trace.hops.len() == 1 { "" } else { "s" },
+                escape_markdown_code(&trace.base_denom)
+            ));
+        } else {
+            message.push_str(&format!(
+                "\nDenom: `{}`",
+                escape_markdown_code(&wallet_balance.balance.denom)
+            ));
+        }
+    }
+
+    let values: Vec<f64> = balances
+        .iter()
+        .filter_map(WalletBalance::usd_value)
+        .collect();
+    if !balances.is_empty() {
+        let scope = if page > 0 || more_available {
+            "Value on this page"
+        } else {
+            "Total value"
+        };
+        message.push_str(&format!(
+            "\n\n*{scope}:* `{}`",
+            escape_markdown_code(&format_usd(values.iter().sum()))
+        ));
+        let unpriced = balances.len() - values.len();
+        if unpriced > 0 {
+            message.push_str(&format!(
+                "\n_{unpriced} asset{} unpriced and not counted\\._",
+                if unpriced == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
+    if more_available {
+        message.push_str(&format!(
+            "\n\n_Page {}; more balances are available\\._",
+            page + 1
+        ));
+    } else if page > 0 {
+        message.push_str(&format!("\n\n_Page {}, the last page\\._", page + 1));
+    }
+
+    message
+}
+
+/// Where a paged balance view continues from; kept in the cache under a short token so it
+/// fits in callback data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceCursor {
+    pub chain: String,
+    pub address: String,
+    /// Pagination key this page was fetched with; `None` for the first page.
+    pub key: Option<String>,
+    pub page: usize,
+    /// Token of the previous page's cursor.
+    pub previous: Option<String>,
+}
+
+/// An address entered under the wrong chain, waiting for the user to pick the chain its
+/// prefix belongs to; kept in the cache under a short token like [`BalanceCursor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSwitch {
+    pub chain: String,
+    pub address: String,
+}
+
+/// Every balance across all pages, grouped by denom kind, listing the registry-known ones.
+pub fn format_balance_summary(
+    address: &str,
+    chain: &str,
+    balances: &[Balance],
+    labels: &HashMap<String, String>,
+    units: &DenomUnits,
+    prices: &UsdPrices,
+    complete: bool,
+) -> String {
+    let count = |prefix: &str| {
+        balances
+            .iter()
+            .filter(|balance| balance.denom.starts_with(prefix))
+            .count()
+    };
+    let ibc = count("ibc/");
+    let factory = count("factory/");
+    let other = balances.len() - ibc - factory;
+
+    let mut message = format!(
+        "💰 *All balances for* `{}` on *{}*\n\n{} denoms: {} IBC, {} token factory, {} other",
+        escape_markdown_code(address),
+        escape_markdown(chain),
+        balances.len(),
+        ibc,
+        factory,
+        other
+    );
+    if !complete {
+        message.push_str(&format!(
+            "\n_Stopped after {} pages; the account holds more\\._",
+            MAX_BALANCE_PAGES
+        ));
+    }
+
+    let values: Vec<f64> = balances
+        .iter()
+        .filter_map(|balance| {
+            let price = prices.get(&balance.denom)?;
+            Some(units.get(&balance.denom)?.value(&balance.amount)? * price)
+        })
+        .collect();
+    if !balances.is_empty() {
+        message.push_str(&format!(
+            "\n\n*{}:* `{}`",
+            if complete {
+                "Total value"
+            } else {
+                "Value of fetched balances"
+            },
+            escape_markdown_code(&format_usd(values.iter().sum()))
+        ));
+        let unpriced = balances.len() - values.len();
+        if unpriced > 0 {
+            message.push_str(&format!(
+                "\n_{unpriced} asset{} unpriced and not counted\\._",
+                if unpriced == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
+    let mut known: Vec<(&str, &Balance)> = balances
+        .iter()
+        .filter_map(|balance| {
+            labels
+                .get(&balance.denom)
+                .map(|label| (label.as_str(), balance))
+        })
+        .collect();
+    known.sort_by_key(|(label, _)| label.to_lowercase());
+    if !known.is_empty() {
+        message.push_str("\n\n*Registry assets*");
+        for (label, balance) in &known {
+            message.push_str(&format!(
+                "\n• {}: `{}`",
+                escape_markdown(label),
+                escape_markdown_code(&units.format(&balance.amount, &balance.denom))
+            ));
+        }
+    }
+    let unlisted = balances.len() - known.len();
+    if unlisted > 0 {
+        message.push_str(&format!(
+            "\n\n{} denom{} not in the {} asset list",
+            unlisted,
+            if unlisted == 1 { " is" } else { "s are" },
+            escape_markdown(chain)
+        ));
+    }
+
+    message
+}
+
+/// Ethermint and Injective `EthAccount`. Only the embedded base account is
+/// decoded; the code hash field differs between the two and is not shown.
+#[derive(Clone, PartialEq, prost::Message)]
+struct EthAccount {
+    #[prost(message, optional, tag = "1")]
+    base_account: Option<BaseAccount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VestingPeriod {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub amount: Vec<Balance>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub kind: String,
+    pub start_time: Option<i64>,
+    pub end_time: i64,
+    pub original_vesting: Vec<Balance>,
+    pub delegated_free: Vec<Balance>,
+    pub delegated_vesting: Vec<Balance>,
+    pub periods: Vec<VestingPeriod>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountInfo {
+    pub account_type: String,
+    pub address: String,
+    pub account_number: u64,
+    pub sequence: u64,
+    pub pub_key_type: Option<String>,
+    pub module_name: Option<String>,
+    pub module_permissions: Vec<String>,
+    pub vesting: Option<VestingSchedule>,
+}
+
+fn balances_from_sdk_coins(
+    coins: Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+) -> Vec<Balance> {
+    coins
+        .into_iter()
+        .map(|coin| Balance {
+            denom: coin.denom,
+            amount: coin.amount,
+        })
+        .collect()
+}
+
+fn account_info_from_base(account_type: &str, base: Option<BaseAccount>) -> AccountInfo {
+    let base = base.unwrap_or_default();
+    AccountInfo {
+        account_type: account_type.to_string(),
+        address: base.address,
+        account_number: base.account_number,
+        sequence: base.sequence,
+        pub_key_type: base
+            .pub_key
+            .map(|key| key.type_url.trim_start_matches('/').to_string()),
+        module_name: None,
+        module_permissions: Vec::new(),
+        vesting: None,
+    }
+}
+
+fn account_info_from_vesting(
+    account_type: &str,
+    base_vesting: Option<BaseVestingAccount>,
+    start_time: Option<i64>,
+    periods: Vec<cosmos_sdk_proto::cosmos::vesting::v1beta1::Period>,
+) -> AccountInfo {
+    let base_vesting = base_vesting.unwrap_or_default();
+    let mut info = account_info_from_base(account_type, base_vesting.base_account);
+
+    let mut period_start = start_time.unwrap_or_default();
+    let periods = periods
+        .into_iter()
+        .map(|period| {
+            let start = period_start;
+            period_start += period.length;
+            VestingPeriod {
+                start_time: start,
+                end_time: period_start,
+                amount: balances_from_sdk_coins(period.amount),
+            }
+        })
+        .collect();
+
+    info.vesting = Some(VestingSchedule {
+        kind: account_type.trim_end_matches(" Vesting").to_string(),
+        start_time,
+        end_time: base_vesting.end_time,
+        original_vesting: balances_from_sdk_coins(base_vesting.original_vesting),
+        delegated_free: balances_from_sdk_coins(base_vesting.delegated_free),
+        delegated_vesting: balances_from_sdk_coins(base_vesting.delegated_vesting),
+        periods,
+    });
+    info
+}
+
+pub fn decode_account(
+    account: &tendermint_proto::google::protobuf::Any,
+) -> anyhow::Result<AccountInfo> {
+    let value = account.value.as_slice();
+    match account.type_url.trim_start_matches('/') {
+        "cosmos.auth.v1beta1.BaseAccount" => Ok(account_info_from_base(
+            "Base Account",
+            Some(BaseAccount::decode(value)?),
+        )),
+        "cosmos.auth.v1beta1.ModuleAccount" => {
+            let module = ModuleAccount::decode(value)?;
+            let mut info = account_info_from_base("Module Account", module.base_account);
+            info.module_name = non_empty(module.name);
+            info.module_permissions = module.permissions;
+            Ok(info)
+        }
+        "cosmos.vesting.v1beta1.ContinuousVestingAccount" => {
+            let account = ContinuousVestingAccount::decode(value)?;
+            Ok(account_info_from_vesting(
+                "Continuous Vesting",
+                account.base_vesting_account,
+                Some(account.start_time),
+                Vec::new(),
+            ))
+        }
+        "cosmos.vesting.v1beta1.DelayedVestingAccount" => {
+            let account = DelayedVestingAccount::decode(value)?;
+            Ok(account_info_from_vesting(
+                "Delayed Vesting",
+                account.base_vesting_account,
+                None,
+                Vec::new(),
+            ))
+        }
+        "cosmos.vesting.v1beta1.PeriodicVestingAccount" => {
+            let account = PeriodicVestingAccount::decode(value)?;
+            Ok(account_info_from_vesting(
+                "Periodic Vesting",
+                account.base_vesting_account,
+                Some(account.start_time),
+                account.vesting_periods,
+            ))
+        }
+        "cosmos.vesting.v1beta1.PermanentLockedAccount" => {
+            let account = PermanentLockedAccount::decode(value)?;
+            Ok(account_info_from_vesting(
+                "Permanent Locked",
+                account.base_vesting_account,
+                None,
+                Vec::new(),
+            ))
+        }
+        "ethermint.types.v1.EthAccount" | "injective.types.v1beta1.EthAccount" => Ok(
+            account_info_from_base("Eth Account", EthAccount::decode(value)?.base_account),
+        ),
+        other => Err(anyhow::anyhow!("Unsupported account type {other}")),
+    }
+}
+
+pub async fn query_account_grpc(grpc_endpoint: &str, address: &str) -> anyhow::Result<AccountInfo> {
+    let mut client = AuthQueryClient::new(connect_grpc(grpc_endpoint).await?);
+    let response = client
+        .account(QueryAccountRequest {
+            address: address.to_string(),
+        })
+        .await?
+        .into_inner();
+    let account = response
+        .account
+        .ok_or_else(|| anyhow::anyhow!("Account {} not found", address))?;
+
+    decode_account(&account)
+}
+
+pub async fn query_account_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+    address: &str,
+) -> anyhow::Result<AccountInfo> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for account info: {}", endpoint);
+        match query_account_grpc(endpoint, address).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!(
+                    "Failed gRPC account query with endpoint {}: {}",
+                    endpoint,
+                    e
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorInfo {
+    pub operator_address: String,
+    pub moniker: String,
+    pub identity: String,
+    pub website: String,
+    pub details: String,
+    pub status: String,
+    pub jailed: bool,
+    pub tokens: String,
+    pub delegator_shares: String,
+    pub commission_rate: String,
+    pub max_commission_rate: String,
+    pub max_change_rate: String,
+    pub min_self_delegation: String,
+    /// From the staking params, to show `tokens` in display units.
+    pub bond_denom: Option<String>,
+}
+
+fn bond_status_label(status: i32) -> &'static str {
+    match BondStatus::try_from(status) {
+        Ok(BondStatus::Bonded) => "Bonded",
+        Ok(BondStatus::Unbonding) => "Unbonding",
+        Ok(BondStatus::Unbonded) => "Unbonded",
+        _ => "Unspecified",
+    }
+}
+
+pub async fn query_validator_grpc(
+    grpc_endpoint: &str,
+    operator_address: &str,
+) -> anyhow::Result<ValidatorInfo> {
+    let mut client = StakingQueryClient::new(connect_grpc(grpc_endpoint).await?);
+    let validator = client
+        .validator(QueryValidatorRequest {
+            validator_addr: operator_address.to_string(),
+        })
+        .await?
+        .into_inner()
+        .validator
+        .ok_or_else(|| anyhow::anyhow!("Validator {} not found", operator_address))?;
+    let bond_denom = match client.params(StakingQueryParamsRequest {}).await {
+        Ok(response) => response.into_inner().params.map(|params| params.bond_denom),
+        Err(e) => {
+            log::warn!(
+                "Could not read staking params from {}: {}",
+                grpc_endpoint,
+                e
+            );
+            None
+        }
+    };
+
+    let description = validator.description.unwrap_or_default();
+    let rates = validator
+        .commission
+        .and_then(|commission| commission.commission_rates)
+        .unwrap_or_default();
+    Ok(ValidatorInfo {
+        operator_address: validator.operator_address,
+        moniker: description.moniker,
+        identity: description.identity,
+        website: description.website,
+        details: description.details,
+        status: bond_status_label(validator.status).to_string(),
+        jailed: validator.jailed,
+        tokens: validator.tokens,
+        delegator_shares: validator.delegator_shares,
+        commission_rate: rates.rate,
+        max_commission_rate: rates.max_rate,
+        max_change_rate: rates.max_change_rate,
+        min_self_delegation: validator.min_self_delegation,
+        bond_denom,
+    })
+}
+
+pub async fn query_validator_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+    operator_address: &str,
+) -> anyhow::Result<ValidatorInfo> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for validator info: {}", endpoint);
+        match query_validator_grpc(endpoint, operator_address).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!(
+                    "Failed gRPC validator query with endpoint {}: {}",
+                    endpoint,
+                    e
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+/// A legacy-dec fraction such as a commission rate, as a percentage.
+fn format_dec_percent(raw: &str) -> String {
+    match format_legacy_dec(raw).parse::<f64>() {
+        Ok(fraction) => format!("{}%", format_decimal(fraction * 100.0, 2)),
+        Err(_) => raw.to_string(),
+    }
+}
+
+pub fn format_validator_info(chain: &str, validator: &ValidatorInfo, units: &DenomUnits) -> String {
+    let moniker = if validator.moniker.is_empty() {
+        "unnamed validator"
+    } else {
+        validator.moniker.as_str()
+    };
+    let in_bond_denom = |amount: &str| match &validator.bond_denom {
+        Some(denom) => units.format_coin(amount, denom),
+        None => format_amount(amount),
+    };
+    let tokens = in_bond_denom(&validator.tokens);
+    let mut message = format!(
+        "🛡 *Validator on {}*\n\n*{}*\nOperator: `{}`\nStatus: {}{}\nTokens: `{}`\nCommission: {} \\(max {}, max change {}/day\\)\nMin Self Delegation: `{}`",
+        escape_markdown(chain),
+        escape_markdown(moniker),
+        escape_markdown_code(&validator.operator_address),
+        escape_markdown(&validator.status),
+        if validator.jailed { " \\(jailed\\)" } else { "" },
+        escape_markdown_code(&tokens),
+        escape_markdown(&format_dec_percent(&validator.commission_rate)),
+        escape_markdown(&format_dec_percent(&validator.max_commission_rate)),
+        escape_markdown(&format_dec_percent(&validator.max_change_rate)),
+        escape_markdown_code(&in_bond_denom(&validator.min_self_delegation))
+    );
+    for (label, value) in [
+        ("Identity", &validator.identity),
+        ("Website", &validator.website),
+        ("Details", &validator.details),
+    ] {
+        if !value.is_empty() {
+            message.push_str(&format!("\n{label}: {}", escape_markdown(value)));
+        }
+    }
+
+    message
+}
+
+fn scale_amount(amount: &str, numerator: i64, denominator: i64) -> String {
+    let (Ok(amount), Ok(numerator), Ok(denominator)) = (
+        amount.parse::<u128>(),
+        u128::try_from(numerator),
+        u128::try_from(denominator),
+    ) else {
+        return "0".to_string();
+    };
+    if denominator == 0 {
+        return amount.to_string();
+    }
+
+    amount
+        .checked_mul(numerator)
+        .map(|scaled| scaled / denominator)
+        .unwrap_or_else(|| amount / denominator * numerator)
+        .to_string()
+}
+
+fn subtract_coins(total: &[Balance], subtract: &[Balance]) -> Vec<Balance> {
+    total
+        .iter()
+        .map(|coin| {
+            let total = coin.amount.parse::<u128>().unwrap_or_default();
+            let used: u128 = subtract
+                .iter()
+                .filter(|other| other.denom == coin.denom)
+                .filter_map(|other| other.amount.parse::<u128>().ok())
+                .sum();
+            Balance {
+                denom: coin.denom.clone(),
+                amount: total.saturating_sub(used).to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the (vested, unvested) split of the original vesting amount at `now`,
+/// following the Cosmos SDK x/auth/vesting rules for each account kind.
+pub fn vesting_amounts_at(schedule: &VestingSchedule, now: i64) -> (Vec<Balance>, Vec<Balance>) {
+    let vested: Vec<Balance> = match schedule.kind.as_str() {
+        "Continuous" => {
+            let start = schedule.start_time.unwrap_or_default();
+            schedule
+                .original_vesting
+                .iter()
+                .map(|coin| Balance {
+                    denom: coin.denom.clone(),
+                    amount: if now <= start {
+                        "0".to_string()
+                    } else if now >= schedule.end_time {
+                        coin.amount.clone()
+                    } else {
+                        scale_amount(&coin.amount, now - start, schedule.end_time - start)
+                    },
+                })
+                .collect()
+        }
+        "Delayed" => schedule
+            .original_vesting
+            .iter()
+            .map(|coin| Balance {
+                denom: coin.denom.clone(),
+                amount: if now >= schedule.end_time {
+                    coin.amount.clone()
+                } else {
+                    "0".to_string()
+                },
+            })
+            .collect(),
+        "Periodic" => {
+            let released: Vec<Balance> = schedule
+                .periods
+                .iter()
+                .filter(|period| period.end_time <= now)
+                .flat_map(|period| period.amount.iter().cloned())
+                .collect();
+            let unreleased = subtract_coins(&schedule.original_vesting, &released);
+            subtract_coins(&schedule.original_vesting, &unreleased)
+        }
+        _ => schedule
+            .original_vesting
+            .iter()
+            .map(|coin| Balance {
+                denom: coin.denom.clone(),
+                amount: "0".to_string(),
+            })
+            .collect(),
+    };
+
+    let unvested = subtract_coins(&schedule.original_vesting, &vested);
+    (vested, unvested)
+}
+
+fn unix_to_string(seconds: i64) -> String {
+    chrono::DateTime::<chrono::Utc>::from_timestamp(seconds, 0)
+        .map(|timestamp| timestamp.to_rfc3339())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn format_markdown_coins(coins: &[Balance], units: &DenomUnits) -> String {
+    if coins.is_empty() {
+        return "none".to_string();
+    }
+
+    coins
+        .iter()
+        .map(|coin| match units.get(&coin.denom) {
+            Some(unit) => format!(
+                "`{}` \\(`{}{}`\\)",
+                escape_markdown_code(&unit.format(&coin.amount)),
+                escape_markdown_code(&coin.amount),
+                escape_markdown_code(&coin.denom)
+            ),
+            None => format!(
+                "`{} {}`",
+                escape_markdown_code(&format_amount(&coin.amount)),
+                escape_markdown_code(&coin.denom)
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn format_account_info(
+    chain: &str,
+    account: &AccountInfo,
+    units: &DenomUnits,
+    now: i64,
+) -> String {
+    let mut message = format!(
+        "👤 *Account on {}*\n\nAddress: `{}`\nType: {}\nAccount Number: `{}`\nSequence: `{}`\nPubkey: `{}`",
+        escape_markdown(chain),
+        escape_markdown_code(&account.address),
+        escape_markdown(&account.account_type),
+        account.account_number,
+        account.sequence,
+        escape_markdown_code(account.pub_key_type.as_deref().unwrap_or("none (never signed)"))
+    );
+
+    if let Some(module_name) = &account.module_name {
+        message.push_str(&format!(
+            "\nModule: `{}`",
+            escape_markdown_code(module_name)
+        ));
+        if !account.module_permissions.is_empty() {
+            message.push_str(&format!(
+                "\nPermissions: {}",
+                escape_markdown(&account.module_permissions.join(", "))
+            ));
+        }
+    }
+
+    let Some(schedule) = &account.vesting else {
+        return message;
+    };
+
+    let (vested, unvested) = vesting_amounts_at(schedule, now);
+    message.push_str(&format!(
+        "\n\n*Vesting Schedule \\({}\\)*",
+        escape_markdown(&schedule.kind)
+    ));
+    if let Some(start_time) = schedule.start_time {
+        message.push_str(&format!("\nStart: `{}`", unix_to_string(start_time)));
+    }
+    if schedule.kind != "Permanent Locked" {
+        message.push_str(&format!("\nEnd: `{}`", unix_to_string(schedule.end_time)));
+    }
+    message.push_str(&format!(
+        "\nOriginal: {}\nVested now: {}\nStill locked: {}\nDelegated free: {}\nDelegated vesting: {}",
+        format_markdown_coins(&schedule.original_vesting, units),
+        format_markdown_coins(&vested, units),
+        format_markdown_coins(&unvested, units),
+        format_markdown_coins(&schedule.delegated_free, units),
+        format_markdown_coins(&schedule.delegated_vesting, units)
+    ));
+
+    if !schedule.periods.is_empty() {
+        message.push_str("\n\n*Periods*");
+        for (index, period) in schedule.periods.iter().enumerate().take(24) {
+            let status = if period.end_time <= now {
+                "✅"
+            } else {
+                "🔒"
+            };
+            message.push_str(&format!(
+                "\n{} {}\\. `{}`: {}",
+                status,
+                index + 1,
+                unix_to_string(period.end_time),
+                format_markdown_coins(&period.amount, units)
+            ));
+        }
+        if schedule.periods.len() > 24 {
+            message.push_str(&format!(
+                "\n_…and {} more periods_",
+                schedule.periods.len() - 24
+            ));
+        }
+    }
+
+    message
+}
+
+/// A grant or params value. Coin amounts stay raw until the chain's display units are known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetailValue {
+    Text(String),
+    Coins(Vec<Balance>),
+}
+
+impl DetailValue {
+    pub fn format(&self, units: &DenomUnits) -> String {
+        match self {
+            DetailValue::Text(text) => text.clone(),
+            DetailValue::Coins(coins) => format_coin_lines(coins, units),
+        }
+    }
+}
+
+impl From<String> for DetailValue {
+    fn from(text: String) -> Self {
+        DetailValue::Text(text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthzGrantInfo {
+    pub granter: String,
+    pub grantee: String,
+    pub authorization: String,
+    pub msg_type: Option<String>,
+    pub details: Vec<(String, DetailValue)>,
+    pub expiration: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeAllowanceInfo {
+    pub granter: String,
+    pub grantee: String,
+    pub allowance: String,
+    pub details: Vec<(String, DetailValue)>,
+    pub expiration: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AddressGrants {
+    pub granted_by_address: Vec<AuthzGrantInfo>,
+    pub granted_to_address: Vec<AuthzGrantInfo>,
+    pub allowances_given: Vec<FeeAllowanceInfo>,
+    pub allowances_received: Vec<FeeAllowanceInfo>,
+    pub unavailable: Vec<String>,
+    /// Lists that still had more pages after `MAX_GRANT_PAGES`.
+    pub truncated: Vec<String>,
+}
+
+const GRANTS_PAGE_LIMIT: u64 = 100;
+const MAX_GRANT_PAGES: usize = 5;
+
+fn page_request(key: Vec<u8>, limit: u64) -> Option<PageRequest> {
+    Some(PageRequest {
+        key,
+        offset: 0,
+        limit,
+        count_total: false,
+        reverse: false,
+    })
+}
+
+fn next_page_key(
+    pagination: Option<cosmos_sdk_proto::cosmos::base::query::v1beta1::PageResponse>,
+) -> Option<Vec<u8>> {
+    pagination
+        .and_then(|pagination| (!pagination.next_key.is_empty()).then_some(pagination.next_key))
+}
+
+/// Returns (label, message type, details) for the authorization types we know how to read.
+fn decode_authorization(
+    authorization: &tendermint_proto::google::protobuf::Any,
+) -> (String, Option<String>, Vec<(String, DetailValue)>) {
+    let value = authorization.value.as_slice();
+    let type_url = authorization.type_url.trim_start_matches('/');
+
+    match type_url {
+        "cosmos.authz.v1beta1.GenericAuthorization" => match GenericAuthorization::decode(value) {
+            Ok(generic) => ("Generic".to_string(), non_empty(generic.msg), Vec::new()),
+            Err(_) => (type_url.to_string(), None, Vec::new()),
+        },
+        "cosmos.bank.v1beta1.SendAuthorization" => match SendAuthorization::decode(value) {
+            Ok(send) => {
+                let mut details = vec![("Spend limit".to_string(), coins_value(send.spend_limit))];
+                if !send.allow_list.is_empty() {
+                    details.push(("Allow list".to_string(), send.allow_list.join(", ").into()));
+                }
+                (
+                    "Send".to_string(),
+                    Some("/cosmos.bank.v1beta1.MsgSend".to_string()),
+                    details,
+                )
+            }
+            Err(_) => (type_url.to_string(), None, Vec::new()),
+        },
+        "cosmos.staking.v1beta1.StakeAuthorization" => match StakeAuthorization::decode(value) {
+            Ok(stake) => {
+                let msg_type = match StakeAuthorizationType::try_from(stake.authorization_type) {
+                    Ok(StakeAuthorizationType::Delegate) => "/cosmos.staking.v1beta1.MsgDelegate",
+                    Ok(StakeAuthorizationType::Undelegate) => {
+                        "/cosmos.staking.v1beta1.MsgUndelegate"
+                    }
+                    Ok(StakeAuthorizationType::Redelegate) => {
+                        "/cosmos.staking.v1beta1.MsgBeginRedelegate"
+                    }
+                    Ok(StakeAuthorizationType::CancelUnbondingDelegation) => {
+                        "/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation"
+                    }
+                    _ => "unspecified",
+                };
+                let mut details = vec![(
+                    "Max tokens".to_string(),
+                    stake
+                        .max_tokens
+                        .map(|coin| coins_value(vec![coin]))
+                        .unwrap_or_else(|| "unlimited".to_string().into()),
+                )];
+                match stake.validators {
+                    Some(StakeAuthorizationPolicy::AllowList(validators)) => details.push((
+                        "Allowed validators".to_string(),
+                        validators.address.join(", ").into(),
+                    )),
+                    Some(StakeAuthorizationPolicy::DenyList(validators)) => details.push((
+                        "Denied validators".to_string(),
+                        validators.address.join(", ").into(),
+                    )),
+                    None => {}
+                }
+                ("Stake".to_string(), Some(msg_type.to_string()), details)
+            }
+            Err(_) => (type_url.to_string(), None, Vec::new()),
+        },
+        "ibc.applications.transfer.v1.TransferAuthorization" => {
+            match TransferAuthorization::decode(value) {
+                Ok(transfer) => {
+                    let mut details = Vec::new();
+                    for allocation in transfer.allocations {
+                        let route =
+                            format!("{}/{}", allocation.source_port, allocation.source_channel);
+                        details.push((
+                            format!("{route} spend limit"),
+                            coins_value(allocation.spend_limit),
+                        ));
+                        if !allocation.allow_list.is_empty() {
+                            details.push((
+                                format!("{route} receivers"),
+                                allocation.allow_list.join(", ").into(),
+                            ));
+                        }
+                    }
+                    (
+                        "IBC Transfer".to_string(),
+                        Some("/ibc.applications.transfer.v1.MsgTransfer".to_string()),
+                        details,
+                    )
+                }
+                Err(_) => (type_url.to_string(), None, Vec::new()),
+            }
+        }
+        other => (other.to_string(), None, Vec::new()),
+    }
+}
+
+fn authz_grant_from_proto(grant: GrantAuthorization) -> AuthzGrantInfo {
+    let (authorization, msg_type, details) = grant
+        .authorization
+        .as_ref()
+        .map(decode_authorization)
+        .unwrap_or_else(|| ("unknown".to_string(), None, Vec::new()));
+
+    AuthzGrantInfo {
+        granter: grant.granter,
+        grantee: grant.grantee,
+        authorization,
+        msg_type,
+        details,
+        expiration: grant.expiration.map(|timestamp| timestamp.seconds),
+    }
+}
+
+/// Unwraps basic, periodic and allowed-msg allowances into a label, details and expiry.
+fn decode_fee_allowance(
+    allowance: &tendermint_proto::google::protobuf::Any,
+) -> (String, Vec<(String, DetailValue)>, Option<i64>) {
+    let value = allowance.value.as_slice();
+    let type_url = allowance.type_url.trim_start_matches('/');
+
+    match type_url {
+        "cosmos.feegrant.v1beta1.BasicAllowance" => match BasicAllowance::decode(value) {
+            Ok(basic) => {
+                let (details, expiration) = basic_allowance_details(basic);
+                ("Basic".to_string(), details, expiration)
+            }
+            Err(_) => (type_url.to_string(), Vec::new(), None),
+        },
+        "cosmos.feegrant.v1beta1.PeriodicAllowance" => match PeriodicAllowance::decode(value) {
+            Ok(periodic) => {
+                let (mut details, expiration) =
+                    basic_allowance_details(periodic.basic.unwrap_or_default());
+                details.push((
+                    "Period".to_string(),
+                    proto_duration_to_string(periodic.period.as_ref()).into(),
+                ));
+                details.push((
+                    "Period limit".to_string(),
+                    coins_value(periodic.period_spend_limit),
+                ));
+                details.push((
+                    "Left this period".to_string(),
+                    coins_value(periodic.period_can_spend),
+                ));
+                if let Some(reset) = periodic.period_reset {
+                    details.push((
+                        "Period resets".to_string(),
+                        unix_to_string(reset.seconds).into(),
+                    ));
+                }
+                ("Periodic".to_string(), details, expiration)
+            }
+            Err(_) => (type_url.to_string(), Vec::new(), None),
+        },
+        "cosmos.feegrant.v1beta1.AllowedMsgAllowance" => match AllowedMsgAllowance::decode(value) {
+            Ok(allowed) => {
+                let (inner, mut details, expiration) = allowed
+                    .allowance
+                    .as_ref()
+                    .map(decode_fee_allowance)
+                    .unwrap_or_else(|| ("unknown".to_string(), Vec::new(), None));
+                details.push((
+                    "Allowed messages".to_string(),
+                    allowed.allowed_messages.join(", ").into(),
+                ));
+                (
+                    format!("{inner} (allowed messages only)"),
+                    details,
+                    expiration,
+                )
+            }
+            Err(_) => (type_url.to_string(), Vec::new(), None),
+        },
+        other => (other.to_string(), Vec::new(), None),
+    }
+}
+
+fn basic_allowance_details(basic: BasicAllowance) -> (Vec<(String, DetailValue)>, Option<i64>) {
+    let spend_limit = if basic.spend_limit.is_empty() {
+        "unlimited".to_string().into()
+    } else {
+        coins_value(basic.spend_limit)
+    };
+    (
+        vec![("Spend limit".to_string(), spend_limit)],
+        basic.expiration.map(|timestamp| timestamp.seconds),
+    )
+}
+
+fn fee_allowance_from_proto(grant: FeegrantGrant) -> FeeAllowanceInfo {
+    let (allowance, details, expiration) = grant
+        .allowance
+        .as_ref()
+        .map(decode_fee_allowance)
+        .unwrap_or_else(|| ("unknown".to_string(), Vec::new(), None));
+
+    FeeAllowanceInfo {
+        granter: grant.granter,
+        grantee: grant.grantee,
+        allowance,
+        details,
+        expiration,
+    }
+}
+
+/// Collects up to `MAX_GRANT_PAGES` pages from `fetch_page`, which runs one paginated request.
+/// The flag is true when the chain still had more pages after the last one read.
+async fn collect_grant_pages<T, F, Fut>(mut fetch_page: F) -> anyhow::Result<(Vec<T>, bool)>
+where
+    F: FnMut(Option<PageRequest>) -> Fut,
+    Fut: std::future::Future<
+        Output = anyhow::Result<(
+            Vec<T>,
+            Option<cosmos_sdk_proto::cosmos::base::query::v1beta1::PageResponse>,
+        )>,
+    >,
+{
+    let mut items = Vec::new();
+    let mut key = Vec::new();
+
+    for _ in 0..MAX_GRANT_PAGES {
+        let (page, pagination) = fetch_page(page_request(key, GRANTS_PAGE_LIMIT)).await?;
+        items.extend(page);
+        match next_page_key(pagination) {
+            Some(next_key) => key = next_key,
+            None => return Ok((items, false)),
+        }
+    }
+
+    Ok((items, true))
+}
+
+async fn query_authz_granter_grants(
+    channel: Channel,
+    address: &str,
+) -> anyhow::Result<(Vec<AuthzGrantInfo>, bool)> {
+    let client = AuthzQueryClient::new(channel);
+    collect_grant_pages(|pagination| {
+        let mut client = client.clone();
+        let request = QueryGranterGrantsRequest {
+            granter: address.to_string(),
+            pagination,
+        };
+        async move {
+            let response = client.granter_grants(request).await?.into_inner();
+            let grants = response.grants.into_iter().map(authz_grant_from_proto);
+            Ok((grants.collect(), response.pagination))
+        }
+    })
+    .await
+}
+
+async fn query_authz_grantee_grants(
+    channel: Channel,
+    address: &str,
+) -> anyhow::Result<(Vec<AuthzGrantInfo>, bool)> {
+    let client = AuthzQueryClient::new(channel);
+    collect_grant_pages(|pagination| {
+        let mut client = client.clone();
+        let request = QueryGranteeGrantsRequest {
+            grantee: address.to_string(),
+            pagination,
+        };
+        async move {
+            let response = client.grantee_grants(request).await?.into_inner();
+            let grants = response.grants.into_iter().map(authz_grant_from_proto);
+            Ok((grants.collect(), response.pagination))
+        }
+    })
+    .await
+}
+
+async fn query_fee_allowances_by_granter(
+    channel: Channel,
+    address: &str,
+) -> anyhow::Result<(Vec<FeeAllowanceInfo>, bool)> {
+    let client = FeegrantQueryClient::new(channel);
+    collect_grant_pages(|pagination| {
+        let mut client = client.clone();
+        let request = QueryAllowancesByGranterRequest {
+            granter: address.to_string(),
+            pagination,
+        };
+        async move {
+            let response = client.allowances_by_granter(request).await?.into_inner();
+            let allowances = response
+                .allowances
+                .into_iter()
+                .map(fee_allowance_from_proto);
+            Ok((allowances.collect(), response.pagination))
+        }
+    })
+    .await
+}
+
+async fn query_fee_allowances_by_grantee(
+    channel: Channel,
+    address: &str,
+) -> anyhow::Result<(Vec<FeeAllowanceInfo>, bool)> {
+    let client = FeegrantQueryClient::new(channel);
+    collect_grant_pages(|pagination| {
+        let mut client = client.clone();
+        let request = QueryAllowancesRequest {
+            grantee: address.to_string(),
+            pagination,
+        };
+        async move {
+            let response = client.allowances(request).await?.into_inner();
+            let allowances = response
+                .allowances
+                .into_iter()
+                .map(fee_allowance_from_proto);
+            Ok((allowances.collect(), response.pagination))
+        }
+    })
+    .await
+}
+
+pub async fn query_address_grants_grpc(
+    grpc_endpoint: &str,
+    address: &str,
+) -> anyhow::Result<AddressGrants> {
+    let channel = connect_grpc(grpc_endpoint).await?;
+
+    let (granted_by, granted_to, given, received) = tokio::join!(
+        query_authz_granter_grants(channel.clone(), address),
+        query_authz_grantee_grants(channel.clone(), address),
+        query_fee_allowances_by_granter(channel.clone(), address),
+        query_fee_allowances_by_grantee(channel, address),
+    );
+
+    let mut grants = AddressGrants::default();
+    match granted_by {
+        Ok((list, truncated)) => {
+            grants.granted_by_address = list;
+            if truncated {
+                grants.truncated.push("authz (as granter)".to_string());
+            }
+        }
+        Err(e) => {
+            log::warn!("Authz granter query failed on {grpc_endpoint}: {e}");
+            grants.unavailable.push("authz (as granter)".to_string());
+        }
+    }
+    match granted_to {
+        Ok((list, truncated)) => {
+            grants.granted_to_address = list;
+            if truncated {
+                grants.truncated.push("authz (as grantee)".to_string());
+            }
+        }
+        Err(e) => {
+            log::warn!("Authz grantee query failed on {grpc_endpoint}: {e}");
+            grants.unavailable.push("authz (as grantee)".to_string());
+        }
+    }
+    match given {
+        Ok((list, truncated)) => {
+            grants.allowances_given = list;
+            if truncated {
+                grants.truncated.push("feegrant (as granter)".to_string());
+            }
+        }
+        Err(e) => {
+            log::warn!("Feegrant granter query failed on {grpc_endpoint}: {e}");
+            grants.unavailable.push("feegrant (as granter)".to_string());
+        }
+    }
+    match received {
+        Ok((list, truncated)) => {
+            grants.allowances_received = list;
+            if truncated {
+                grants.truncated.push("feegrant (as grantee)".to_string());
+            }
+        }
+        Err(e) => {
+            log::warn!("Feegrant grantee query failed on {grpc_endpoint}: {e}");
+            grants.unavailable.push("feegrant (as grantee)".to_string());
+        }
+    }
+
+    if grants.unavailable.len() == 4 {
+        return Err(anyhow::anyhow!(
+            "Neither authz nor feegrant queries are available on {grpc_endpoint}"
+        ));
+    }
+
+    Ok(grants)
+}
+
+pub async fn query_address_grants_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+    address: &str,
+) -> anyhow::Result<AddressGrants> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for grants: {}", endpoint);
+        match query_address_grants_grpc(endpoint, address).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!("Failed gRPC grants query with endpoint {}: {}", endpoint, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+fn format_expiration(expiration: Option<i64>, now: i64) -> String {
+    match expiration {
+        Some(expiration) if expiration <= now => {
+            format!("expired {}", escape_markdown(&unix_to_string(expiration)))
+        }
+        Some(expiration) => format!(
+            "expires {} \\(in {}\\)",
+            escape_markdown(&unix_to_string(expiration)),
+            escape_markdown(&human_duration(expiration - now))
+        ),
+        None => "no expiry".to_string(),
+    }
+}
+
+fn format_grant_details(
+    message: &mut String,
+    details: &[(String, DetailValue)],
+    units: &DenomUnits,
+) {
+    for (key, value) in details {
+        message.push_str(&format!(
+            "\n>  {}: `{}`",
+            escape_markdown(key),
+            escape_markdown_code(&value.format(units))
+        ));
+    }
+}
+
+fn format_authz_section(
+    message: &mut String,
+    title: &str,
+    grants: &[AuthzGrantInfo],
+    counterparty: fn(&AuthzGrantInfo) -> &str,
+    units: &DenomUnits,
+    now: i64,
+) {
+    if grants.is_empty() {
+        message.push_str(&format!("\n\n*{title}*\nnone"));
+        return;
+    }
+
+    message.push_str(&format!("\n\n**>*{}* \\({}\\)", title, grants.len()));
+    for grant in grants {
+        message.push_str(&format!(
+            "\n>`{}`\n>  {}: `{}`\n>  {}",
+            escape_markdown_code(counterparty(grant)),
+            escape_markdown(&grant.authorization),
+            escape_markdown_code(grant.msg_type.as_deref().unwrap_or("any")),
+            format_expiration(grant.expiration, now)
+        ));
+        format_grant_details(message, &grant.details, units);
+    }
+    message.push_str("||");
+}
+
+fn format_allowance_section(
+    message: &mut String,
+    title: &str,
+    allowances: &[FeeAllowanceInfo],
+    counterparty: fn(&FeeAllowanceInfo) -> &str,
+    units: &DenomUnits,
+    now: i64,
+) {
+    if allowances.is_empty() {
+        message.push_str(&format!("\n\n*{title}*\nnone"));
+        return;
+    }
+
+    message.push_str(&format!("\n\n**>*{}* \\({}\\)", title, allowances.len()));
+    for allowance in allowances {
+        message.push_str(&format!(
+            "\n>`{}`\n>  {}, {}",
+            escape_markdown_code(counterparty(allowance)),
+            escape_markdown(&allowance.allowance),
+            format_expiration(allowance.expiration, now)
+        ));
+        format_grant_details(message, &allowance.details, units);
+    }
+    message.push_str("||");
+}
+
+pub fn format_address_grants(
+    chain: &str,
+    address: &str,
+    grants: &AddressGrants,
+    units: &DenomUnits,
+    now: i64,
+) -> String {
+    let mut message = format!(
+        "🔐 *Grants on {}*\n\nAddress: `{}`",
+        escape_markdown(chain),
+        escape_markdown_code(address)
+    );
+
+    format_authz_section(
+        &mut message,
+        "Authz Granted To Others",
+        &grants.granted_by_address,
+        |grant| &grant.grantee,
+        units,
+        now,
+    );
+    format_authz_section(
+        &mut message,
+        "Authz Received",
+        &grants.granted_to_address,
+        |grant| &grant.granter,
+        units,
+        now,
+    );
+    format_allowance_section(
+        &mut message,
+        "Fee Allowances Given",
+        &grants.allowances_given,
+        |allowance| &allowance.grantee,
+        units,
+        now,
+    );
+    format_allowance_section(
+        &mut message,
+        "Fee Allowances Received",
+        &grants.allowances_received,
+        |allowance| &allowance.granter,
+        units,
+        now,
+    );
+
+    if !grants.unavailable.is_empty() {
+        message.push_str(&format!(
+            "\n\n_Not exposed by this chain: {}_",
+            escape_markdown(&grants.unavailable.join(", "))
+        ));
+    }
+    if !grants.truncated.is_empty() {
+        message.push_str(&format!(
+            "\n\n_Only the first {} entries shown for: {}_",
+            MAX_GRANT_PAGES as u64 * GRANTS_PAGE_LIMIT,
+            escape_markdown(&grants.truncated.join(", "))
+        ));
+    }
+
+    message
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainParamsSection {
+    pub module: String,
+    pub entries: Vec<(String, DetailValue)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainParams {
+    pub sections: Vec<ChainParamsSection>,
+    /// Modules the node does not serve.
+    pub unavailable: Vec<String>,
+    /// Modules whose query errored for another reason, such as a timeout.
+    pub failed: Vec<String>,
+}
+
+/// Renders a `LegacyDec` value as a trimmed decimal. Values arrive either as a
+/// plain decimal string or, for gogoproto custom types, as the raw 18-decimal integer.
+pub fn format_legacy_dec(raw: &str) -> String {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return "unknown".to_string();
+    }
+
+    let decimal = if raw.contains('.') {
+        raw.to_string()
+    } else if raw.chars().all(|c| c.is_ascii_digit()) {
+        let padded = format!("{raw:0>19}");
+        let (whole, fraction) = padded.split_at(padded.len() - 18);
+        format!("{}.{fraction}", whole.trim_start_matches('0').max("0"))
+    } else {
+        return raw.to_string();
+    };
+
+    let trimmed = decimal.trim_end_matches('0').trim_end_matches('.');
+    if trimmed.is_empty() {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn format_legacy_dec_bytes(raw: &[u8]) -> String {
+    format_legacy_dec(&String::from_utf8_lossy(raw))
+}
+
+/// Formats a second count compactly, e.g. `21d`, `1d 12h`, `10m`.
+pub fn human_duration(seconds: i64) -> String {
+    if seconds <= 0 {
+        return "0s".to_string();
+    }
+
+    let units = [(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")];
+    let mut remaining = seconds;
+    let mut parts = Vec::new();
+    for (unit_seconds, suffix) in units {
+        if remaining >= unit_seconds {
+            parts.push(format!("{}{}", remaining / unit_seconds, suffix));
+            remaining %= unit_seconds;
+        }
+        if parts.len() == 2 {
+            break;
+        }
+    }
+    parts.join(" ")
+}
+
+fn proto_duration_to_string(
+    duration: Option<&tendermint_proto::google::protobuf::Duration>,
+) -> String {
+    duration
+        .map(|duration| human_duration(duration.seconds))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn coins_value(coins: Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>) -> DetailValue {
+    DetailValue::Coins(balances_from_sdk_coins(coins))
+}
+
+fn params_section(
+    module: &str,
+    entries: Vec<(&str, impl Into<DetailValue>)>,
+) -> ChainParamsSection {
+    ChainParamsSection {
+        module: module.to_string(),
+        entries: entries
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.into()))
+            .collect(),
+    }
+}
+
+async fn query_staking_params(channel: Channel) -> anyhow::Result<ChainParamsSection> {
+    let params = StakingQueryClient::new(channel)
+        .params(StakingQueryParamsRequest {})
+        .await?
+        .into_inner()
+        .params
+        .ok_or_else(|| anyhow::anyhow!("staking params missing"))?;
+
+    Ok(params_section(
+        "Staking",
+        vec![
+            (
+                "Unbonding Time",
+                proto_duration_to_string(params.unbonding_time.as_ref()),
+            ),
+            ("Max Validators", params.max_validators.to_string()),
+            ("Max Entries", params.max_entries.to_string()),
+            ("Bond Denom", params.bond_denom),
+            (
+                "Min Commission",
+                format_legacy_dec(&params.min_commission_rate),
+            ),
+        ],
+    ))
+}
+
+async fn query_slashing_params(channel: Channel) -> anyhow::Result<ChainParamsSection> {
+    let params = SlashingQueryClient::new(channel)
+        .params(SlashingQueryParamsRequest {})
+        .await?
+        .into_inner()
+        .params
+        .ok_or_else(|| anyhow::anyhow!("slashing params missing"))?;
+
+    Ok(params_section(
+        "Slashing",
+        vec![
+            (
+                "Signed Blocks Window",
+                params.signed_blocks_window.to_string(),
+            ),
+            (
+                "Min Signed Per Window",
+                format_legacy_dec_bytes(&params.min_signed_per_window),
+            ),
+            (
+                "Downtime Jail",
+                proto_duration_to_string(params.downtime_jail_duration.as_ref()),
+            ),
+            (
+                "Slash Double Sign",
+                format_legacy_dec_bytes(&params.slash_fraction_double_sign),
+            ),
+            (
+                "Slash Downtime",
+                format_legacy_dec_bytes(&params.slash_fraction_downtime),
+            ),
+        ],
+    ))
+}
+
+async fn query_gov_params(channel: Channel) -> anyhow::Result<ChainParamsSection> {
+    let v1_params = GovV1QueryClient::new(channel.clone())
+        .params(GovV1QueryParamsRequest {
+            params_type: String::new(),
+        })
+        .await
+        .ok()
+        .and_then(|response| response.into_inner().params);
+
+    if let Some(params) = v1_params {
+        let mut entries = vec![
+            ("Min Deposit", coins_value(params.min_deposit)),
+            (
+                "Max Deposit Period",
+                proto_duration_to_string(params.max_deposit_period.as_ref()).into(),
+            ),
+            (
+                "Voting Period",
+                proto_duration_to_string(params.voting_period.as_ref()).into(),
+            ),
+            ("Quorum", format_legacy_dec(&params.quorum).into()),
+            ("Threshold", format_legacy_dec(&params.threshold).into()),
+            (
+                "Veto Threshold",
+                format_legacy_dec(&params.veto_threshold).into(),
+            ),
+        ];
+        if params.expedited_voting_period.is_some() {
+            entries.push((
+                "Expedited Voting Period",
+                proto_duration_to_string(params.expedited_voting_period.as_ref()).into(),
+            ));
+            entries.push((
+                "Expedited Threshold",
+                format_legacy_dec(&params.expedited_threshold).into(),
+            ));
+        }
+        return Ok(params_section("Governance", entries));
+    }
+
+    // Chains on Cosmos SDK < 0.46 only expose the split v1beta1 params queries
+    let mut client = GovV1beta1QueryClient::new(channel);
+    let mut entries = Vec::new();
+    for params_type in ["deposit", "voting", "tallying"] {
+        let response = client
+            .params(GovV1beta1QueryParamsRequest {
+                params_type: params_type.to_string(),
+            })
+            .await?
+            .into_inner();
+        if let Some(deposit) = response.deposit_params {
+            entries.push(("Min Deposit", coins_value(deposit.min_deposit)));
+            entries.push((
+                "Max Deposit Period",
+                proto_duration_to_string(deposit.max_deposit_period.as_ref()).into(),
+            ));
+        }
+        if let Some(voting) = response.voting_params {
+            entries.push((
+                "Voting Period",
+                proto_duration_to_string(voting.voting_period.as_ref()).into(),
+            ));
+        }
+        if let Some(tally) = response.tally_params {
+            entries.push(("Quorum", format_legacy_dec_bytes(&tally.quorum).into()));
+            entries.push((
+                "Threshold",
+                format_legacy_dec_bytes(&tally.threshold).into(),
+            ));
+            entries.push((
+                "Veto Threshold",
+                format_legacy_dec_bytes(&tally.veto_threshold).into(),
+            ));
+        }
+    }
+
+    Ok(params_section("Governance", entries))
+}
+
+async fn query_distribution_params(channel: Channel) -> anyhow::Result<ChainParamsSection> {
+    let params = DistributionQueryClient::new(channel)
+        .params(DistributionQueryParamsRequest {})
+        .await?
+        .into_inner()
+        .params
+        .ok_or_else(|| anyhow::anyhow!("distribution params missing"))?;
+
+    Ok(params_section(
+        "Distribution",
+        vec![
+            ("Community Tax", format_legacy_dec(&params.community_tax)),
+            (
+                "Withdraw Address Enabled",
+                params.withdraw_addr_enabled.to_string(),
+            ),
+        ],
+    ))
+}
+
+async fn query_bank_params(channel: Channel) -> anyhow::Result<ChainParamsSection> {
+    let mut client = BankQueryClient::new(channel);
+    let params = client
+        .params(BankQueryParamsRequest {})
+        .await?
+        .into_inner()
+        .params
+        .ok_or_else(|| anyhow::anyhow!("bank params missing"))?;
+
+    // SDK 0.47 moved per-denom overrides out of the params into their own query
+    #[allow(deprecated)]
+    let mut send_enabled = params.send_enabled;
+    if send_enabled.is_empty() {
+        match client
+            .send_enabled(QuerySendEnabledRequest {
+                denoms: Vec::new(),
+                pagination: None,
+            })
+            .await
+        {
+            Ok(response) => send_enabled = response.into_inner().send_enabled,
+            Err(status) if status.code() == tonic::Code::Unimplemented => {}
+            Err(status) => return Err(status.into()),
+        }
+    }
+
+    let mut section = params_section(
+        "Bank",
+        vec![(
+            "Default Send Enabled",
+            params.default_send_enabled.to_string(),
+        )],
+    );
+    section
+        .entries
+        .extend(send_enabled.into_iter().map(|entry| {
+            (
+                format!("Send Enabled ({})", entry.denom),
+                entry.enabled.to_string().into(),
+            )
+        }));
+    Ok(section)
+}
+
+/// `cosmos.consensus.v1` query messages; the module is not generated by cosmos-sdk-proto.
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConsensusQueryParamsRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConsensusQueryParamsResponse {
+    #[prost(message, optional, tag = "1")]
+    params: Option<tendermint_proto::types::ConsensusParams>,
+}
+
+async fn query_consensus_params(channel: Channel) -> anyhow::Result<ChainParamsSection> {
+    let response: ConsensusQueryParamsResponse = grpc_unary(
+        channel,
+        "/cosmos.consensus.v1.Query/Params",
+        ConsensusQueryParamsRequest {},
+    )
+    .await?;
+    let params = response
+        .params
+        .ok_or_else(|| anyhow::anyhow!("consensus params missing"))?;
+
+    let mut entries = Vec::new();
+    if let Some(block) = params.block {
+        entries.push((
+            "Block Max Bytes",
+            format_amount(&block.max_bytes.to_string()),
+        ));
+        entries.push((
+            "Block Max Gas",
+            if block.max_gas < 0 {
+                "unlimited".to_string()
+            } else {
+                format_amount(&block.max_gas.to_string())
+            },
+        ));
+    }
+    if let Some(evidence) = params.evidence {
+        entries.push((
+            "Evidence Max Age",
+            format!(
+                "{} blocks / {}",
+                format_amount(&evidence.max_age_num_blocks.to_string()),
+                proto_duration_to_string(evidence.max_age_duration.as_ref())
+            ),
+        ));
+    }
+
+    Ok(params_section("Consensus", entries))
+}
+
+async fn query_ibc_transfer_params(channel: Channel) -> anyhow::Result<ChainParamsSection> {
+    let params = TransferQueryClient::new(channel)
+        .params(TransferQueryParamsRequest {})
+        .await?
+        .into_inner()
+        .params
+        .ok_or_else(|| anyhow::anyhow!("transfer params missing"))?;
+
+    Ok(params_section(
+        "IBC Transfer",
+        vec![
+            ("Send Enabled", params.send_enabled.to_string()),
+            ("Receive Enabled", params.receive_enabled.to_string()),
+        ],
+    ))
+}
+
+async fn query_chain_params_grpc(grpc_endpoint: &str) -> anyhow::Result<ChainParams> {
+    let channel = connect_grpc(grpc_endpoint).await?;
+
+    let (staking, slashing, gov, distribution, bank, consensus, transfer) = tokio::join!(
+        query_staking_params(channel.clone()),
+        query_slashing_params(channel.clone()),
+        query_gov_params(channel.clone()),
+        query_distribution_params(channel.clone()),
+        query_bank_params(channel.clone()),
+        query_consensus_params(channel.clone()),
+        query_ibc_transfer_params(channel),
+    );
+
+    let mut params = ChainParams::default();
+    for (module, result) in [
+        ("Staking", staking),
+        ("Slashing", slashing),
+        ("Governance", gov),
+        ("Distribution", distribution),
+        ("Bank", bank),
+        ("Consensus", consensus),
+        ("IBC Transfer", transfer),
+    ] {
+        match result {
+            Ok(section) if !section.entries.is_empty() => params.sections.push(section),
+            Ok(_) => params.unavailable.push(module.to_string()),
+            Err(e) if is_unimplemented(&e) => params.unavailable.push(module.to_string()),
+            Err(e) => {
+                log::warn!("{module} params query failed on {grpc_endpoint}: {e}");
+                params.failed.push(module.to_string());
+            }
+        }
+    }
+
+    if params.sections.is_empty() {
+        return Err(anyhow::anyhow!("No module parameters could be queried"));
+    }
+    Ok(params)
+}
+
+pub async fn query_chain_params_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+) -> anyhow::Result<ChainParams> {
+    let mut last_error = None;
+
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for chain params: {}", endpoint);
+        match query_chain_params_grpc(endpoint).await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                log::warn!("Failed gRPC params query with endpoint {}: {}", endpoint, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+}
+
+/// Formats module parameters as MarkdownV2 expandable block quotes, one per module.
+pub fn format_chain_params(chain: &str, params: &ChainParams, units: &DenomUnits) -> String {
+    let mut message = format!("⚙️ *Chain Parameters for {}*", escape_markdown(chain));
+
+    for section in &params.sections {
+        message.push_str(&format!("\n\n**>*{}*", escape_markdown(&section.module)));
+        for (key, value) in &section.entries {
+            message.push_str(&format!(
+                "\n>{}: `{}`",
+                escape_markdown(key),
+                escape_markdown_code(&value.format(units))
+            ));
+        }
+        message.push_str("||");
+    }
+
+    if !params.unavailable.is_empty() {
+        message.push_str(&format!(
+            "\n\n_Not exposed by this chain: {}_",
+            escape_markdown(&params.unavailable.join(", "))
+        ));
+    }
+    if !params.failed.is_empty() {
+        message.push_str(&format!(
+            "\n\n_Could not be queried: {}_",
+            escape_markdown(&params.failed.join(", "))
+        ));
+    }
+
+    message
+}
+
+/// Gas used by a plain bank send on most Cosmos SDK chains, rounded up.
+pub const TYPICAL_SEND_GAS: u64 = 100_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveBaseFee {
+    pub source: String,
+    pub denom: Option<String>,
+    pub price: String,
+}
+
+/// Ethermint-style feemarket query messages (Evmos, Injective forks and friends).
+#[derive(Clone, PartialEq, prost::Message)]
+struct EthermintQueryBaseFeeRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct EthermintQueryBaseFeeResponse {
+    #[prost(string, tag = "1")]
+    base_fee: String,
+}
+
+/// Skip `feemarket` module query messages.
+#[derive(Clone, PartialEq, prost::Message)]
+struct SkipGasPriceRequest {
+    #[prost(string, tag = "1")]
+    denom: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct SkipGasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    price: Option<cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin>,
+}
+
+async fn query_live_base_fee_grpc(
+    grpc_endpoint: &str,
+    fee_denom: Option<&str>,
+) -> anyhow::Result<LiveBaseFee> {
+    let channel = connect_grpc(grpc_endpoint).await?;
+
+    if let Ok(response) = grpc_unary::<QueryEipBaseFeeRequest, QueryEipBaseFeeResponse>(
+        channel.clone(),
+        "/osmosis.txfees.v1beta1.Query/GetEipBaseFee",
+        QueryEipBaseFeeRequest {},
+    )
+    .await
+    {
+        return Ok(LiveBaseFee {
+            source: "Osmosis EIP-1559 txfees".to_string(),
+            denom: fee_denom.map(str::to_string),
+            price: format_legacy_dec(&response.base_fee),
+        });
+    }
+
+    if let Some(denom) = fee_denom {
+        if let Ok(response) = grpc_unary::<SkipGasPriceRequest, SkipGasPriceResponse>(
+            channel.clone(),
+            "/feemarket.feemarket.v1.Query/GasPrice",
+            SkipGasPriceRequest {
+                denom: denom.to_string(),
+            },
+        )
+        .await
+        {
+            if let Some(price) = response.price {
+                return Ok(LiveBaseFee {
+                    source: "Skip feemarket".to_string(),
+                    denom: Some(price.denom),
+                    price: format_legacy_dec(&price.amount),
+                });
+            }
+        }
+    }
+
+    let response: EthermintQueryBaseFeeResponse = grpc_unary(
+        channel,
+        "/ethermint.feemarket.v1.Query/BaseFee",
+        EthermintQueryBaseFeeRequest {},
+    )
+    .await?;
+    if response.base_fee.is_empty() {
+        return Err(anyhow::anyhow!("feemarket base fee is disabled"));
+    }
+
+    // Ethermint reports the base fee as an integer amount per unit of gas
+    Ok(LiveBaseFee {
+        source: "EVM feemarket".to_string(),
+        denom: fee_denom.map(str::to_string),
+        price: response.base_fee,
+    })
 }
 
-#[derive(Debug, Clone)]
-pub struct OsmosisGaugeIncentive {
-    pub gauge_id: String,
-    pub duration: String,
-    pub incentive_percentage: String,
-    pub coins: Vec<Balance>,
-    pub distributed_coins: Vec<Balance>,
+/// Returns the live base fee from whichever fee market module the chain runs,
+/// or `None` when it has none (or no endpoint answered).
+pub async fn query_live_base_fee_grpc_first(
+    grpc_endpoints: &[chain::Grpc],
+    fee_denom: Option<&str>,
+) -> Option<LiveBaseFee> {
+    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
+        log::info!("Trying gRPC endpoint for live base fee: {}", endpoint);
+        match query_live_base_fee_grpc(endpoint, fee_denom).await {
+            Ok(result) => return Some(result),
+            Err(e) => log::debug!("No fee market base fee from {}: {}", endpoint, e),
+        }
+    }
+
+    None
 }
 
-#[derive(Debug, Clone)]
-pub struct OsmosisClIncentive {
-    pub incentive_id: String,
-    pub denom: String,
-    pub remaining_amount: String,
-    pub emission_rate: String,
-    pub start_time: String,
+/// Returns the display symbol and exponent for a base denom from the asset list.
+pub fn display_unit_for_denom(assets: Option<&AssetList>, denom: &str) -> Option<(String, u32)> {
+    let asset = assets?.assets.iter().find(|asset| asset.base == denom)?;
+    asset_display_unit(asset).map(|unit| (unit.symbol, unit.exponent))
 }
 
-#[derive(Debug, Clone)]
-pub struct OsmosisPoolIncentives {
-    pub gauges: Vec<OsmosisGaugeIncentive>,
-    pub cl_records: Vec<OsmosisClIncentive>,
+/// Symbol and exponent of an asset's display `denom_unit`, or its largest unit when the
+/// display unit is not listed.
+pub fn asset_display_unit(asset: &Asset) -> Option<DisplayUnit> {
+    let exponent = asset
+        .denom_units
+        .iter()
+        .find(|unit| unit.denom == asset.display)
+        .or_else(|| asset.denom_units.iter().max_by_key(|unit| unit.exponent))?
+        .exponent;
+    let symbol = [&asset.symbol, &asset.display]
+        .into_iter()
+        .find(|label| !label.is_empty())?
+        .clone();
+
+    Some(DisplayUnit {
+        symbol,
+        exponent: u32::from(exponent),
+    })
 }
 
-#[derive(Debug, Clone)]
-pub struct OsmosisTokenPrice {
-    pub name: String,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayUnit {
     pub symbol: String,
-    pub denom: String,
-    pub quote_symbol: String,
-    pub quote_denom: String,
-    pub price: String,
+    pub exponent: u32,
 }
 
-#[derive(Debug, Clone)]
-struct OsmosisTokenMetadata {
-    name: String,
-    symbol: String,
-    denom: String,
-    preview: bool,
-}
+impl DisplayUnit {
+    /// Base amount in display units, for valuation.
+    pub fn value(&self, amount: &str) -> Option<f64> {
+        let amount = amount.parse::<u128>().ok()? as f64;
+        Some(amount / 10f64.powi(self.exponent as i32))
+    }
 
-async fn get_json(client: &reqwest::Client, url: &str) -> anyhow::Result<Value> {
-    let response = client.get(url).send().await?;
-    if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "request failed with status {} for {}",
-            response.status(),
-            url
-        ));
+    /// `1500000` at exponent 6 becomes `1.5 ATOM`.
+    pub fn format(&self, amount: &str) -> String {
+        format!(
+            "{} {}",
+            format_display_amount(amount, self.exponent),
+            self.symbol
+        )
     }
+}
 
-    Ok(response.json().await?)
+/// Display units by base denom, for showing base-unit amounts the way wallets do.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DenomUnits {
+    units: HashMap<String, DisplayUnit>,
 }
 
-fn encode_query_component(value: &str) -> String {
-    let mut encoded = String::new();
-    for byte in value.bytes() {
-        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
-            encoded.push(byte as char);
-        } else {
-            encoded.push_str(&format!("%{byte:02X}"));
+impl DenomUnits {
+    pub fn from_assets(assets: Option<&AssetList>) -> Self {
+        let units = assets
+            .into_iter()
+            .flat_map(|assets| &assets.assets)
+            .filter_map(|asset| Some((asset.base.clone(), asset_display_unit(asset)?)))
+            .collect();
+        Self { units }
+    }
+
+    pub fn get(&self, denom: &str) -> Option<&DisplayUnit> {
+        self.units.get(denom)
+    }
+
+    /// `1.5 ATOM (1500000)` when the denom has a display unit, keeping the base amount to
+    /// copy; otherwise the base amount with separators.
+    pub fn format(&self, amount: &str, denom: &str) -> String {
+        match self.get(denom) {
+            Some(unit) => format!("{} ({amount})", unit.format(amount)),
+            None => format_amount(amount),
+        }
+    }
+
+    /// Display amount alone when known, for lines where the base amount is shown elsewhere.
+    pub fn format_short(&self, amount: &str, denom: &str) -> String {
+        match self.get(denom) {
+            Some(unit) => unit.format(amount),
+            None => format_amount(amount),
+        }
+    }
+
+    /// Like `format`, naming the denom when it has no display unit.
+    pub fn format_coin(&self, amount: &str, denom: &str) -> String {
+        match self.get(denom) {
+            Some(_) => self.format(amount, denom),
+            None => format!("{} {}", format_amount(amount), truncate_ibc_hash(denom)),
         }
     }
-    encoded
 }
 
-fn resolve_token_metadata(metadata: &Value, input: &str) -> Option<OsmosisTokenMetadata> {
-    let needle = input.trim();
-    let mut preview_match = None;
+/// Shifts a base-unit integer amount by `exponent` decimals, exactly, with thousands
+/// separators on the whole part and trailing zeros trimmed.
+pub fn format_display_amount(amount: &str, exponent: u32) -> String {
+    if amount.is_empty() || !amount.chars().all(|c| c.is_ascii_digit()) {
+        return amount.to_string();
+    }
 
-    for token in metadata.as_object()?.values() {
-        let denom = token["coinMinimalDenom"].as_str().unwrap_or_default();
-        let symbol = token["symbol"].as_str().unwrap_or_default();
+    let exponent = exponent as usize;
+    let digits = format!("{amount:0>width$}", width = exponent + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - exponent);
+    let whole = match whole.trim_start_matches('0') {
+        "" => "0".to_string(),
+        whole => format_amount(whole),
+    };
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
 
-        if denom.eq_ignore_ascii_case(needle) || symbol.eq_ignore_ascii_case(needle) {
-            let candidate = OsmosisTokenMetadata {
-                name: token["name"].as_str().unwrap_or(symbol).to_string(),
-                symbol: symbol.to_string(),
-                denom: denom.to_string(),
-                preview: token["preview"].as_bool().unwrap_or(false),
-            };
+/// Formats a float with up to `max_decimals` places, trimming trailing zeros.
+pub fn format_decimal(value: f64, max_decimals: usize) -> String {
+    let formatted = format!("{value:.max_decimals$}");
+    if formatted.contains('.') {
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        formatted
+    }
+}
 
-            if !candidate.preview {
-                return Some(candidate);
-            }
-            preview_match = Some(candidate);
-        }
+/// A USD price lookup for one wallet denom. `coingecko_id` comes from the asset list entry,
+/// so assets held off Osmosis can still be priced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceQuery {
+    pub denom: String,
+    pub coingecko_id: Option<String>,
+}
+
+impl PriceQuery {
+    /// `None` for assets without a display unit, whose price could not be applied anyway.
+    pub fn from_asset(denom: &str, asset: &Asset) -> Option<Self> {
+        asset_display_unit(asset)?;
+        Some(Self {
+            denom: denom.to_string(),
+            coingecko_id: non_empty(asset.coingecko_id.clone()),
+        })
     }
+}
 
-    preview_match
+/// USD per display unit, by wallet denom.
+pub type UsdPrices = HashMap<String, f64>;
+
+/// Somewhere to price assets in USD. Sources only return the denoms they could price; the
+/// rest are left for the next source.
+pub trait PriceSource: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn usd_prices<'a>(
+        &'a self,
+        queries: &'a [PriceQuery],
+    ) -> BoxFuture<'a, anyhow::Result<UsdPrices>>;
 }
 
-pub async fn query_balances_grpc(
-    grpc_endpoint: &str,
-    address: &str,
-    pagination_key: Option<&str>,
-) -> anyhow::Result<(Vec<Balance>, Option<String>)> {
-    let key = match pagination_key {
-        Some(key) => general_purpose::STANDARD.decode(key).unwrap_or_default(),
-        None => Vec::new(),
-    };
+/// Osmosis SQS token prices, for denoms Osmosis lists under the exact same name.
+pub struct OsmosisSqsPrices;
 
-    let mut client = BankQueryClient::new(connect_grpc(grpc_endpoint).await?);
-    let response = client
-        .all_balances(QueryAllBalancesRequest {
-            address: address.to_string(),
-            pagination: Some(PageRequest {
-                key,
-                offset: 0,
-                limit: 100,
-                count_total: false,
-                reverse: false,
-            }),
-            resolve_denom: false,
+impl PriceSource for OsmosisSqsPrices {
+    fn name(&self) -> &'static str {
+        "Osmosis SQS"
+    }
+
+    fn usd_prices<'a>(
+        &'a self,
+        queries: &'a [PriceQuery],
+    ) -> BoxFuture<'a, anyhow::Result<UsdPrices>> {
+        Box::pin(async move {
+            let metadata = fetch_osmosis_token_metadata()
+                .await
+                .ok_or_else(|| anyhow::anyhow!("Osmosis token metadata is unavailable"))?;
+            let listed: BTreeSet<&str> = metadata
+                .as_object()
+                .map(|tokens| {
+                    tokens
+                        .values()
+                        .filter_map(|token| token["coinMinimalDenom"].as_str())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let bases: BTreeSet<&str> = queries
+                .iter()
+                .map(|query| query.denom.as_str())
+                .filter(|denom| listed.contains(denom))
+                .collect();
+            if bases.is_empty() {
+                return Ok(UsdPrices::new());
+            }
+
+            let client = reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()?;
+            let url = format!(
+                "https://sqs.osmosis.zone/tokens/prices?base={}",
+                bases
+                    .iter()
+                    .copied()
+                    .map(encode_query_component)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+            let price_json = get_json(&client, &url).await?;
+
+            Ok(bases
+                .into_iter()
+                .filter_map(|denom| {
+                    let price = price_json
+                        .get(denom)?
+                        .as_object()?
+                        .values()
+                        .next()?
+                        .as_str()?
+                        .parse::<f64>()
+                        .ok()?;
+                    Some((denom.to_string(), price))
+                })
+                .collect())
         })
-        .await?
-        .into_inner();
+    }
+}
 
-    let balances = response
-        .balances
-        .into_iter()
-        .map(|coin| Balance {
-            denom: coin.denom,
-            amount: coin.amount,
+/// CoinGecko simple prices, by the asset list's `coingecko_id`.
+pub struct CoinGeckoPrices;
+
+impl PriceSource for CoinGeckoPrices {
+    fn name(&self) -> &'static str {
+        "CoinGecko"
+    }
+
+    fn usd_prices<'a>(
+        &'a self,
+        queries: &'a [PriceQuery],
+    ) -> BoxFuture<'a, anyhow::Result<UsdPrices>> {
+        Box::pin(async move {
+            let ids: BTreeSet<&str> = queries
+                .iter()
+                .filter_map(|query| query.coingecko_id.as_deref())
+                .collect();
+            if ids.is_empty() {
+                return Ok(UsdPrices::new());
+            }
+
+            let client = reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()?;
+            let url = format!(
+                "https://api.coingecko.com/api/v3/simple/price?vs_currencies=usd&ids={}",
+                ids.into_iter()
+                    .map(encode_query_component)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+            let price_json = get_json(&client, &url).await?;
+
+            Ok(queries
+                .iter()
+                .filter_map(|query| {
+                    let id = query.coingecko_id.as_deref()?;
+                    let price = price_json.get(id)?.get("usd")?.as_f64()?;
+                    Some((query.denom.clone(), price))
+                })
+                .collect())
         })
-        .collect();
+    }
+}
 
-    let next_key = response
-        .pagination
-        .and_then(|pagination| (!pagination.next_key.is_empty()).then_some(pagination.next_key))
-        .map(|next_key| general_purpose::STANDARD.encode(next_key));
+/// Fixed prices by denom, standing in for the network sources in tests.
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+pub struct FixedPrices(pub UsdPrices);
 
-    Ok((balances, next_key))
+#[cfg(test)]
+impl PriceSource for FixedPrices {
+    fn name(&self) -> &'static str {
+        "fixed prices"
+    }
+
+    fn usd_prices<'a>(
+        &'a self,
+        queries: &'a [PriceQuery],
+    ) -> BoxFuture<'a, anyhow::Result<UsdPrices>> {
+        Box::pin(async move {
+            Ok(queries
+                .iter()
+                .filter_map(|query| Some((query.denom.clone(), *self.0.get(&query.denom)?)))
+                .collect())
+        })
+    }
 }
 
-pub async fn query_balances_grpc_first(
-    grpc_endpoints: &[chain::Grpc],
-    address: &str,
-    pagination_key: Option<&str>,
-) -> anyhow::Result<(Vec<Balance>, Option<String>)> {
-    let mut last_error = None;
+/// Asks each source in turn for the denoms still unpriced. A failing source is logged and
+/// skipped.
+pub async fn fetch_usd_prices(
+    sources: &[Arc<dyn PriceSource>],
+    queries: &[PriceQuery],
+) -> UsdPrices {
+    let mut prices = UsdPrices::new();
+
+    for source in sources {
+        let remaining: Vec<PriceQuery> = queries
+            .iter()
+            .filter(|query| !prices.contains_key(&query.denom))
+            .cloned()
+            .collect();
+        if remaining.is_empty() {
+            break;
+        }
 
-    for endpoint in prioritize_grpc_endpoints(grpc_endpoints).iter().take(3) {
-        log::info!("Trying gRPC endpoint for balances: {}", endpoint);
-        match query_balances_grpc(endpoint, address, pagination_key).await {
-            Ok(result) => return Ok(result),
-            Err(e) => {
-                log::warn!(
-                    "Failed gRPC balance query with endpoint {}: {}",
-                    endpoint,
-                    e
-                );
-                last_error = Some(e);
-            }
+        match source.usd_prices(&remaining).await {
+            Ok(found) => prices.extend(found),
+            Err(e) => log::warn!("Failed {} price lookup: {e}", source.name()),
         }
     }
 
-    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No valid gRPC endpoints available")))
+    prices
 }
 
-/// Format amount with thousands separators
-pub fn format_amount(amount: &str) -> String {
-    // Parse the amount string as a number and format with commas
-    if let Ok(num) = amount.parse::<u128>() {
-        let s = num.to_string();
-        let mut result = String::new();
-        for (i, c) in s.chars().rev().enumerate() {
-            if i > 0 && i % 3 == 0 {
-                result.insert(0, ',');
+/// `$1,234.56`, with sub-cent values shown as `<$0.01` rather than zero.
+pub fn format_usd(value: f64) -> String {
+    if value > 0.0 && value < 0.01 {
+        return "<$0.01".to_string();
+    }
+
+    let cents = (value * 100.0).round() as u128;
+    format!(
+        "${}.{:02}",
+        format_amount(&(cents / 100).to_string()),
+        cents % 100
+    )
+}
+
+fn format_gas_price(price: f32) -> String {
+    format_decimal(f64::from(price), 6)
+}
+
+pub fn format_fee_card(
+    chain: &str,
+    fee_tokens: &[chain::FeeToken],
+    assets: Option<&AssetList>,
+    base_fee: Option<&LiveBaseFee>,
+) -> String {
+    let mut message = format!("⛽ *Fees on {}*", escape_markdown(chain));
+
+    if fee_tokens.is_empty() {
+        message.push_str("\n\nNo fee tokens are listed in the chain registry\\.");
+    }
+
+    for token in fee_tokens {
+        let display = display_unit_for_denom(assets, &token.denom);
+        let label = display
+            .as_ref()
+            .map(|(symbol, _)| symbol.clone())
+            .unwrap_or_else(|| truncate_ibc_hash(&token.denom));
+
+        message.push_str(&format!("\n\n*{}*", escape_markdown(&label)));
+        message.push_str(&format!(
+            "\nDenom: `{}`",
+            escape_markdown_code(&token.denom)
+        ));
+        for (tier, price) in [
+            ("Fixed min", token.fixed_min_gas_price),
+            ("Low", token.low_gas_price),
+            ("Average", token.average_gas_price),
+            ("High", token.high_gas_price),
+        ] {
+            if price > 0.0 {
+                message.push_str(&format!(
+                    "\n{}: `{}{}`",
+                    tier,
+                    format_gas_price(price),
+                    escape_markdown_code(&token.denom)
+                ));
             }
-            result.insert(0, c);
         }
-        result
+
+        let typical_price = [
+            token.average_gas_price,
+            token.low_gas_price,
+            token.fixed_min_gas_price,
+        ]
+        .into_iter()
+        .find(|price| *price > 0.0);
+        if let (Some(price), Some((symbol, exponent))) = (typical_price, display.as_ref()) {
+            let cost = f64::from(price) * TYPICAL_SEND_GAS as f64 / 10f64.powi(*exponent as i32);
+            message.push_str(&format!(
+                "\nTypical send \\(~{} gas\\): `{} {}`",
+                escape_markdown(&format_amount(&TYPICAL_SEND_GAS.to_string())),
+                format_decimal(cost, 6),
+                escape_markdown_code(symbol)
+            ));
+        }
+    }
+
+    if let Some(base_fee) = base_fee {
+        let denom = base_fee.denom.as_deref().unwrap_or_default();
+        message.push_str(&format!(
+            "\n\n*Live Base Fee* \\({}\\)\n`{}{}`",
+            escape_markdown(&base_fee.source),
+            escape_markdown_code(&base_fee.price),
+            escape_markdown_code(denom)
+        ));
+
+        let display = display_unit_for_denom(assets, denom);
+        if let (Ok(price), Some((symbol, exponent))) = (base_fee.price.parse::<f64>(), display) {
+            let cost = price * TYPICAL_SEND_GAS as f64 / 10f64.powi(exponent as i32);
+            message.push_str(&format!(
+                "\nTypical send at base fee: `{} {}`",
+                format_decimal(cost, 6),
+                escape_markdown_code(&symbol)
+            ));
+        }
     } else {
-        amount.to_string()
+        message.push_str("\n\n_No on\\-chain fee market detected; registry prices apply\\._");
     }
+
+    message
 }
 
-/// Truncate IBC hash for display (e.g., "ibc/27A6..." showing first 8 chars after ibc/)
-pub fn truncate_ibc_hash(hash: &str) -> String {
-    if hash.len() > 12 {
-        format!("{}...", &hash[..12])
-    } else {
-        hash.to_string()
+const REGISTRY_RAW_URL: &str = "https://raw.githubusercontent.com/cosmos/chain-registry/master";
+
+/// Fetches a raw JSON file from the chain registry, returning `None` when it does not exist.
+/// Used for fields the typed registry models do not carry yet.
+pub async fn fetch_registry_json(path: &str) -> anyhow::Result<Option<Value>> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    let url = format!("{REGISTRY_RAW_URL}/{path}");
+    let response = client.get(&url).send().await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "request failed with status {} for {}",
+            response.status(),
+            url
+        ));
     }
-}
 
-pub fn prioritize_grpc_endpoints(endpoints: &[chain::Grpc]) -> Vec<String> {
-    let mut polkachu = Vec::new();
-    let mut others = Vec::new();
+    Ok(Some(response.json().await?))
+}
 
-    for endpoint in endpoints {
-        let Some(uri) = normalize_grpc_uri(&endpoint.address) else {
-            continue;
-        };
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SoftwareVersion {
+    pub name: String,
+    pub tag: Option<String>,
+    pub height: Option<u64>,
+    pub proposal: Option<u64>,
+    pub sdk_version: Option<String>,
+}
 
-        let is_polkachu = endpoint
-            .provider
-            .as_deref()
-            .unwrap_or_default()
-            .to_lowercase()
-            .contains("polkachu")
-            || endpoint.address.to_lowercase().contains("polkachu.com");
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainSoftware {
+    pub git_repo: Option<String>,
+    pub recommended_version: Option<String>,
+    pub compatible_versions: Vec<String>,
+    pub genesis_url: Option<String>,
+    pub libraries: Vec<(String, String)>,
+    pub binaries: Vec<(String, String)>,
+    pub versions: Vec<SoftwareVersion>,
+}
 
-        if is_polkachu {
-            polkachu.push(uri);
-        } else {
-            others.push(uri);
-        }
+fn json_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
     }
+}
 
-    polkachu.extend(others);
-    polkachu
+fn json_u64(value: &Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
 }
 
-fn escape_markdown_code(text: &str) -> String {
-    text.replace('\\', "\\\\").replace('`', "\\`")
+/// Reads a library version in either the current `{ "type", "version" }` shape or the
+/// older flat `*_version` string.
+fn library_version(codebase: &Value, key: &str, legacy_key: &str) -> Option<String> {
+    let version =
+        json_string(&codebase[key]["version"]).or_else(|| json_string(&codebase[legacy_key]))?;
+    match json_string(&codebase[key]["type"]) {
+        Some(kind) => Some(format!("{version} ({kind})")),
+        None => Some(version),
+    }
 }
 
-fn display_asset_label(
-    denom: &str,
-    trace: Option<&IbcDenomTrace>,
-    asset_label: Option<&str>,
-) -> String {
-    if let Some(asset_label) = asset_label.filter(|label| !label.is_empty()) {
-        return asset_label.to_string();
+pub fn parse_chain_software(chain_json: &Value) -> ChainSoftware {
+    let codebase = &chain_json["codebase"];
+
+    let mut libraries = Vec::new();
+    for (label, key, legacy_key) in [
+        ("Cosmos SDK", "sdk", "cosmos_sdk_version"),
+        ("Consensus", "consensus", "tendermint_version"),
+        ("IBC", "ibc", "ibc_go_version"),
+        ("CosmWasm", "cosmwasm", "cosmwasm_version"),
+    ] {
+        if let Some(version) = library_version(codebase, key, legacy_key) {
+            libraries.push((label.to_string(), version));
+        }
     }
 
-    let display = trace.map(|t| t.base_denom.as_str()).unwrap_or(denom);
-    display
-        .rsplit('/')
-        .next()
-        .filter(|segment| !segment.is_empty())
-        .unwrap_or(display)
-        .to_string()
+    let binaries = codebase["binaries"]
+        .as_object()
+        .map(|binaries| {
+            binaries
+                .iter()
+                .filter_map(|(platform, url)| Some((platform.clone(), json_string(url)?)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let versions = chain_json["versions"]
+        .as_array()
+        .map(|versions| {
+            versions
+                .iter()
+                .filter_map(|version| {
+                    Some(SoftwareVersion {
+                        name: json_string(&version["name"])?,
+                        tag: json_string(&version["tag"])
+                            .or_else(|| json_string(&version["recommended_version"])),
+                        height: json_u64(&version["height"]),
+                        proposal: json_u64(&version["proposal"]),
+                        sdk_version: json_string(&version["sdk"]["version"])
+                            .or_else(|| json_string(&version["cosmos_sdk_version"])),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ChainSoftware {
+        git_repo: json_string(&codebase["git_repo"]),
+        recommended_version: json_string(&codebase["recommended_version"]),
+        compatible_versions: codebase["compatible_versions"]
+            .as_array()
+            .map(|versions| versions.iter().filter_map(json_string).collect())
+            .unwrap_or_default(),
+        genesis_url: json_string(&codebase["genesis"]["genesis_url"]),
+        libraries,
+        binaries,
+        versions,
+    }
 }
 
-pub fn format_wallet_balances(
-    address: &str,
-    chain: &str,
-    balances: &[WalletBalance],
-    more_available: bool,
-) -> String {
-    let display_address = if address.len() > 20 {
-        format!("{}...", &address[..20])
-    } else {
-        address.to_string()
-    };
-
-    let mut message = format!(
-        "💰 *Balances for* `{}` on *{}*",
-        escape_markdown_code(&display_address),
-        escape_markdown(chain)
-    );
+const MAX_UPGRADE_ENTRIES: usize = 15;
 
-    for (index, wallet_balance) in balances.iter().enumerate() {
-        if index > 0 {
-            message.push_str("\n\n\\-\\-\\-");
-        }
+pub fn format_software_card(chain: &str, software: &ChainSoftware) -> String {
+    let mut message = format!("🛠 *Software for {}*", escape_markdown(chain));
 
-        let label = display_asset_label(
-            &wallet_balance.balance.denom,
-            wallet_balance.ibc_trace.as_ref(),
-            wallet_balance.asset_label.as_deref(),
-        );
-        message.push_str(&format!(
-            "\n\n*{}*\nAmount: `{}`",
-            escape_markdown(&label),
-            escape_markdown_code(&format_amount(&wallet_balance.balance.amount))
-        ));
+    message.push_str("\n\n*Current Release*");
+    if let Some(repo) = &software.git_repo {
+        message.push_str(&format!("\nRepo: {}", escape_markdown(repo)));
+    }
+    message.push_str(&format!(
+        "\nRecommended: `{}`",
+        escape_markdown_code(
+            software
+                .recommended_version
+                .as_deref()
+                .unwrap_or("not listed")
+        )
+    ));
+    if !software.compatible_versions.is_empty() {
+        let compatible: Vec<String> = software
+            .compatible_versions
+            .iter()
+            .map(|version| format!("`{}`", escape_markdown_code(version)))
+            .collect();
+        message.push_str(&format!("\nCompatible: {}", compatible.join(", ")));
+    }
+    if let Some(genesis_url) = &software.genesis_url {
+        message.push_str(&format!("\nGenesis: {}", escape_markdown(genesis_url)));
+    }
 
-        if let Some(trace) = &wallet_balance.ibc_trace {
+    if !software.libraries.is_empty() {
+        message.push_str("\n\n*Libraries*");
+        for (label, version) in &software.libraries {
             message.push_str(&format!(
-                "\nIBC Denom: `{}`\nIBC Path: `{}`\nBase Denom: `{}`",
-                escape_markdown_code(&wallet_balance.balance.denom),
-                escape_markdown_code(&trace.path),
-                escape_markdown_code(&trace.base_denom)
+                "\n{}: `{}`",
+                escape_markdown(label),
+                escape_markdown_code(version)
             ));
-        } else {
+        }
+    }
+
+    if !software.binaries.is_empty() {
+        message.push_str("\n\n*Binaries*");
+        for (platform, url) in &software.binaries {
             message.push_str(&format!(
-                "\nDenom: `{}`",
-                escape_markdown_code(&wallet_balance.balance.denom)
+                "\n{}: {}",
+                escape_markdown(platform),
+                escape_markdown(url)
             ));
         }
     }
 
-    if more_available {
-        message.push_str("\n\n_Showing first 100 assets; more balances are available\\._");
+    if software.versions.is_empty() {
+        message.push_str("\n\n_No upgrade history in the chain registry\\._");
+        return message;
+    }
+
+    message.push_str(&format!(
+        "\n\n**>*Upgrade History* \\({} releases, newest first\\)",
+        software.versions.len()
+    ));
+    for version in software.versions.iter().rev().take(MAX_UPGRADE_ENTRIES) {
+        let mut line = format!("\n>*{}*", escape_markdown(&version.name));
+        match version.height {
+            Some(height) => line.push_str(&format!(
+                " at height {}",
+                escape_markdown(&format_amount(&height.to_string()))
+            )),
+            None => line.push_str(" at genesis"),
+        }
+        if let Some(tag) = &version.tag {
+            line.push_str(&format!(" \\- `{}`", escape_markdown_code(tag)));
+        }
+        if let Some(proposal) = version.proposal {
+            line.push_str(&format!(" \\(prop {proposal}\\)"));
+        }
+        if let Some(sdk_version) = &version.sdk_version {
+            line.push_str(&format!("\n>  SDK `{}`", escape_markdown_code(sdk_version)));
+        }
+        message.push_str(&line);
     }
+    if software.versions.len() > MAX_UPGRADE_ENTRIES {
+        message.push_str(&format!(
+            "\n>…and {} earlier releases",
+            software.versions.len() - MAX_UPGRADE_ENTRIES
+        ));
+    }
+    message.push_str("||");
 
     message
 }
@@ -1306,7 +7268,11 @@ pub async fn query_osmosis_token_price(
     }
 }
 
-pub fn format_osmosis_pool_info(pool_id: &str, pool: &OsmosisPoolInfo) -> String {
+pub fn format_osmosis_pool_info(
+    pool_id: &str,
+    pool: &OsmosisPoolInfo,
+    units: &DenomUnits,
+) -> String {
     let mut message = format!("Osmosis Pool {pool_id}\nType: {}\n", pool.pool_type);
 
     if let Some(address) = &pool.address {
@@ -1328,14 +7294,14 @@ pub fn format_osmosis_pool_info(pool_id: &str, pool: &OsmosisPoolInfo) -> String
                 message.push_str(&format!(
                     "- {}: {} (weight {})\n",
                     truncate_ibc_hash(&asset.denom),
-                    format_amount(&asset.amount),
+                    units.format(&asset.amount, &asset.denom),
                     weight
                 ));
             } else {
                 message.push_str(&format!(
                     "- {}: {}\n",
                     truncate_ibc_hash(&asset.denom),
-                    format_amount(&asset.amount)
+                    units.format(&asset.amount, &asset.denom)
                 ));
             }
         }
@@ -1348,25 +7314,23 @@ pub fn format_osmosis_pool_info(pool_id: &str, pool: &OsmosisPoolInfo) -> String
     message
 }
 
-fn format_coin_lines(coins: &[Balance]) -> String {
+fn format_coin_lines(coins: &[Balance], units: &DenomUnits) -> String {
     if coins.is_empty() {
         return "none".to_string();
     }
 
     coins
         .iter()
-        .map(|coin| {
-            format!(
-                "{} {}",
-                format_amount(&coin.amount),
-                truncate_ibc_hash(&coin.denom)
-            )
-        })
+        .map(|coin| units.format_coin(&coin.amount, &coin.denom))
         .collect::<Vec<_>>()
         .join(", ")
 }
 
-pub fn format_osmosis_pool_incentives(pool_id: &str, incentives: &OsmosisPoolIncentives) -> String {
+pub fn format_osmosis_pool_incentives(
+    pool_id: &str,
+    incentives: &OsmosisPoolIncentives,
+    units: &DenomUnits,
+) -> String {
     if incentives.gauges.is_empty() && incentives.cl_records.is_empty() {
         return format!("No active incentives found for Osmosis pool {pool_id}.");
     }
@@ -1381,8 +7345,8 @@ pub fn format_osmosis_pool_incentives(pool_id: &str, incentives: &OsmosisPoolInc
                 gauge.gauge_id,
                 gauge.duration,
                 gauge.incentive_percentage,
-                format_coin_lines(&gauge.coins),
-                format_coin_lines(&gauge.distributed_coins)
+                format_coin_lines(&gauge.coins, units),
+                format_coin_lines(&gauge.distributed_coins, units)
             ));
         }
     }
@@ -1391,10 +7355,9 @@ pub fn format_osmosis_pool_incentives(pool_id: &str, incentives: &OsmosisPoolInc
         message.push_str("\nConcentrated Liquidity Records:\n");
         for record in &incentives.cl_records {
             message.push_str(&format!(
-                "- Incentive {}: {} {} remaining, emission {}, starts {}\n",
+                "- Incentive {}: {} remaining, emission {}, starts {}\n",
                 record.incentive_id,
-                format_amount(&record.remaining_amount),
-                truncate_ibc_hash(&record.denom),
+                units.format_coin(&record.remaining_amount, &record.denom),
                 record.emission_rate,
                 record.start_time
             ));
@@ -1415,3 +7378,392 @@ pub fn format_osmosis_token_price(price: &OsmosisTokenPrice) -> String {
         truncate_ibc_hash(&price.quote_denom)
     )
 }
+
+/// Coin type of Ethermint-style chains, whose accounts are Ethereum keys and addresses.
+pub const ETHERMINT_SLIP44: u32 = 60;
+/// Chains listed per block, so long conversion lists split cleanly across messages.
+const ADDRESS_CONVERSION_BLOCK: usize = 25;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainAddressPrefix {
+    pub chain_name: String,
+    pub bech32_prefix: String,
+    pub slip44: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    Account,
+    Validator,
+    Consensus,
+}
+
+impl AddressKind {
+    fn hrp_suffix(self) -> &'static str {
+        match self {
+            Self::Account => "",
+            Self::Validator => "valoper",
+            Self::Consensus => "valcons",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Account => "account",
+            Self::Validator => "validator operator",
+            Self::Consensus => "validator consensus",
+        }
+    }
+}
+
+/// An address reduced to its bytes. `prefix` is the chain's account prefix, without any
+/// `valoper`/`valcons` suffix, and is `None` for hex input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedAddress {
+    pub prefix: Option<String>,
+    pub kind: AddressKind,
+    pub bytes: Vec<u8>,
+}
+
+impl DecodedAddress {
+    pub fn encode(&self, account_prefix: &str) -> anyhow::Result<String> {
+        let hrp = format!("{account_prefix}{}", self.kind.hrp_suffix());
+        Ok(bech32::encode::<bech32::Bech32>(
+            bech32::Hrp::parse(&hrp)?,
+            &self.bytes,
+        )?)
+    }
+
+    /// `0x`-prefixed hex for 20-byte account addresses, the form Ethermint chains show in EVM
+    /// wallets; lowercase, as there is no keccak here for an EIP-55 checksum.
+    pub fn evm_address(&self) -> Option<String> {
+        (self.kind == AddressKind::Account && self.bytes.len() == 20)
+            .then(|| format!("0x{}", hex::encode(&self.bytes)))
+    }
+}
+
+/// Accepts a bech32 account, `valoper` or `valcons` address, or a `0x` EVM address.
+pub fn decode_address(input: &str) -> anyhow::Result<DecodedAddress> {
+    let input = input.trim();
+    if let Some(hex_address) = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        let bytes =
+            hex::decode(hex_address).map_err(|e| anyhow::anyhow!("Invalid hex address: {e}"))?;
+        if bytes.len() != 20 {
+            anyhow::bail!("EVM addresses are 20 bytes, got {}", bytes.len());
+        }
+        return Ok(DecodedAddress {
+            prefix: None,
+            kind: AddressKind::Account,
+            bytes,
+        });
+    }
+
+    let (hrp, bytes) =
+        bech32::decode(input).map_err(|e| anyhow::anyhow!("Invalid bech32 address: {e}"))?;
+    let hrp = hrp.to_lowercase();
+    let (prefix, kind) = [AddressKind::Validator, AddressKind::Consensus]
+        .into_iter()
+        .find_map(|kind| {
+            hrp.strip_suffix(kind.hrp_suffix())
+                .filter(|prefix| !prefix.is_empty())
+                .map(|prefix| (prefix.to_string(), kind))
+        })
+        .unwrap_or((hrp, AddressKind::Account));
+
+    Ok(DecodedAddress {
+        prefix: Some(prefix),
+        kind,
+        bytes,
+    })
+}
+
+/// Coin type of the key behind an address: EVM input is an Ethereum key, otherwise the most
+/// common `slip44` among chains using the prefix.
+pub fn source_slip44(decoded: &DecodedAddress, chains: &[ChainAddressPrefix]) -> Option<u32> {
+    let Some(prefix) = &decoded.prefix else {
+        return Some(ETHERMINT_SLIP44);
+    };
+
+    let mut counts: HashMap<u32, usize> = HashMap::new();
+    for chain in chains.iter().filter(|chain| &chain.bech32_prefix == prefix) {
+        *counts.entry(chain.slip44).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(slip44, count)| (*count, std::cmp::Reverse(*slip44)))
+        .map(|(slip44, _)| slip44)
+}
+
+fn push_conversion_blocks(message: &mut String, lines: &[String]) {
+    for block in lines.chunks(ADDRESS_CONVERSION_BLOCK) {
+        message.push_str("\n\n");
+        message.push_str(&block.join("\n"));
+    }
+}
+
+/// Re-encodes the address for every chain, grouped by whether the chain shares the key's
+/// coin type. Chains sharing a prefix are listed on one line.
+pub fn format_address_conversions(
+    input: &str,
+    decoded: &DecodedAddress,
+    chains: &[ChainAddressPrefix],
+) -> String {
+    let source_slip44 = source_slip44(decoded, chains);
+    let mut message = format!(
+        "🔁 *Address conversions for* `{}`\nType: {} address, {} bytes",
+        escape_markdown_code(input.trim()),
+        decoded.kind.label(),
+        decoded.bytes.len()
+    );
+    match (&decoded.prefix, source_slip44) {
+        (Some(prefix), Some(slip44)) => message.push_str(&format!(
+            "\nPrefix: `{}` \\(coin type {slip44}\\)",
+            escape_markdown_code(prefix)
+        )),
+        (Some(prefix), None) => message.push_str(&format!(
+            "\nPrefix: `{}` is not used by any registry chain, so same\\-key chains are unknown",
+            escape_markdown_code(prefix)
+        )),
+        (None, _) => {}
+    }
+    if let Some(evm) = decoded
+        .evm_address()
+        .filter(|_| source_slip44 == Some(ETHERMINT_SLIP44))
+    {
+        message.push_str(&format!("\nEVM: `{}`", escape_markdown_code(&evm)));
+    }
+
+    let mut by_prefix: BTreeMap<(bool, u32, &str), Vec<&str>> = BTreeMap::new();
+    for chain in chains {
+        let same_key = source_slip44.is_none_or(|slip44| slip44 == chain.slip44);
+        by_prefix
+            .entry((!same_key, chain.slip44, &chain.bech32_prefix))
+            .or_default()
+            .push(&chain.chain_name);
+    }
+
+    let mut same_key = Vec::new();
+    let mut other_key = Vec::new();
+    for ((different, slip44, prefix), names) in by_prefix {
+        let Ok(address) = decoded.encode(prefix) else {
+            continue;
+        };
+        let names = escape_markdown(&names.join(", "));
+        if different {
+            other_key.push(format!(
+                "⚠️ {names} \\(coin type {slip44}\\): `{}`",
+                escape_markdown_code(&address)
+            ));
+        } else {
+            same_key.push(format!("{names}: `{}`", escape_markdown_code(&address)));
+        }
+    }
+
+    let heading = match source_slip44 {
+        Some(slip44) => format!("Same key \\(coin type {slip44}\\)"),
+        None => "Registry chains".to_string(),
+    };
+    message.push_str(&format!("\n\n*{heading}: {} prefixes*", same_key.len()));
+    push_conversion_blocks(&mut message, &same_key);
+    if !other_key.is_empty() {
+        message.push_str(&format!(
+            "\n\n*Different coin type: {} prefixes*\nThe same bytes, but a wallet's key for these chains derives a different address\\.",
+            other_key.len()
+        ));
+        push_conversion_blocks(&mut message, &other_key);
+    }
+
+    message
+}
+
+/// Assets listed per chain in a portfolio scan; the rest are counted but not shown.
+const MAX_PORTFOLIO_ASSETS_PER_CHAIN: usize = 12;
+
+/// Balances held by the derived address on one chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainPortfolio {
+    pub chain: String,
+    pub address: String,
+    pub balances: Vec<WalletBalance>,
+    /// False when the balance pages ran past the scan's page limit.
+    pub complete: bool,
+}
+
+impl ChainPortfolio {
+    pub fn usd_value(&self) -> f64 {
+        self.balances
+            .iter()
+            .filter_map(WalletBalance::usd_value)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioScan {
+    pub slip44: u32,
+    /// Chains queried, including empty and failed ones.
+    pub scanned: usize,
+    /// Chains holding at least one balance.
+    pub chains: Vec<ChainPortfolio>,
+    pub failed_chains: Vec<String>,
+}
+
+fn portfolio_asset_label(wallet_balance: &WalletBalance) -> String {
+    match wallet_balance.asset_label.as_deref() {
+        Some(label) if !label.is_empty() => label.to_string(),
+        _ => truncate_ibc_hash(&wallet_balance.balance.denom),
+    }
+}
+
+/// Non-empty chains, most valuable first, with labelled assets and per-chain and overall
+/// USD totals. Unpriced assets are listed and counted rather than dropped.
+pub fn format_portfolio_scan(address: &str, scan: &PortfolioScan) -> String {
+    let mut chains: Vec<&ChainPortfolio> = scan.chains.iter().collect();
+    chains.sort_by(|a, b| {
+        b.usd_value()
+            .total_cmp(&a.usd_value())
+            .then_with(|| a.chain.cmp(&b.chain))
+    });
+    let total: f64 = chains.iter().map(|portfolio| portfolio.usd_value()).sum();
+    let unpriced = chains
+        .iter()
+        .flat_map(|portfolio| &portfolio.balances)
+        .filter(|wallet_balance| wallet_balance.usd_value().is_none())
+        .count();
+
+    let mut message = format!(
+        "🌐 *Portfolio for* `{}`\nScanned {} chains with coin type {}; {} hold balances\\.",
+        escape_markdown_code(address.trim()),
+        scan.scanned,
+        scan.slip44,
+        chains.len()
+    );
+    if chains.is_empty() {
+        message.push_str("\n\nNo balances found on any scanned chain\\.");
+    } else {
+        message.push_str(&format!(
+            "\n*Total value:* `{}`",
+            escape_markdown_code(&format_usd(total))
+        ));
+        if unpriced > 0 {
+            message.push_str(&format!(
+                "\n_{unpriced} asset{} unpriced and not counted\\._",
+                if unpriced == 1 { "" } else { "s" }
+            ));
+        }
+    }
+
+    for portfolio in chains {
+        message.push_str(&format!(
+            "\n\n*{}* `{}`",
+            escape_markdown(&portfolio.chain),
+            escape_markdown_code(&portfolio.address)
+        ));
+
+        let mut balances: Vec<&WalletBalance> = portfolio.balances.iter().collect();
+        balances.sort_by(|a, b| {
+            b.usd_value()
+                .unwrap_or(-1.0)
+                .total_cmp(&a.usd_value().unwrap_or(-1.0))
+                .then_with(|| portfolio_asset_label(a).cmp(&portfolio_asset_label(b)))
+        });
+        for wallet_balance in balances.iter().take(MAX_PORTFOLIO_ASSETS_PER_CHAIN) {
+            let amount = match &wallet_balance.display {
+                Some(unit) => unit.format(&wallet_balance.balance.amount),
+                None => format_amount(&wallet_balance.balance.amount),
+            };
+            let value = match wallet_balance.usd_value() {
+                Some(value) => format!("`{}`", escape_markdown_code(&format_usd(value))),
+                None => "_unpriced_".to_string(),
+            };
+            message.push_str(&format!(
+                "\n• {}: `{}` ≈ {value}",
+                escape_markdown(&portfolio_asset_label(wallet_balance)),
+                escape_markdown_code(&amount)
+            ));
+        }
+        if balances.len() > MAX_PORTFOLIO_ASSETS_PER_CHAIN {
+            message.push_str(&format!(
+                "\n• …and {} more",
+                balances.len() - MAX_PORTFOLIO_ASSETS_PER_CHAIN
+            ));
+        }
+        if !portfolio.complete {
+            message.push_str("\n_More balances exist beyond the pages scanned\\._");
+        }
+        message.push_str(&format!(
+            "\nChain value: `{}`",
+            escape_markdown_code(&format_usd(portfolio.usd_value()))
+        ));
+    }
+
+    if !scan.failed_chains.is_empty() {
+        let mut failed = scan.failed_chains.clone();
+        failed.sort();
+        message.push_str(&format!(
+            "\n\n_Could not query {} chains: {}_",
+            failed.len(),
+            escape_markdown(&failed.join(", "))
+        ));
+    }
+
+    message
+}
+
+/// What a pasted wallet address turned out to be, relative to the selected chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalletAddressCheck {
+    /// A bech32 account on this chain, re-encoded from hex on EVM chains.
+    Account(String),
+    /// A `valoper` address on this chain.
+    Validator(String),
+    /// A valid account or `valoper` address, but with another chain's prefix.
+    OtherPrefix {
+        prefix: String,
+        kind: AddressKind,
+    },
+    Invalid(String),
+}
+
+/// Decodes the address with checksum verification (bech32 or bech32m) and checks its prefix
+/// against the chain's. Hex addresses are only accepted on EVM-enabled chains.
+pub fn check_wallet_address(
+    input: &str,
+    chain: &str,
+    chain_prefix: &str,
+    evm_enabled: bool,
+) -> WalletAddressCheck {
+    let input = input.trim();
+    let is_hex = input.starts_with("0x") || input.starts_with("0X");
+    if is_hex && !evm_enabled {
+        return WalletAddressCheck::Invalid(format!(
+            "{chain} is not an EVM chain, so hex addresses do not apply; enter a {chain_prefix}1... address"
+        ));
+    }
+
+    let decoded = match decode_address(input) {
+        Ok(decoded) => decoded,
+        Err(e) => return WalletAddressCheck::Invalid(e.to_string()),
+    };
+    let Some(prefix) = &decoded.prefix else {
+        return match decoded.encode(chain_prefix) {
+            Ok(address) => WalletAddressCheck::Account(address),
+            Err(e) => WalletAddressCheck::Invalid(e.to_string()),
+        };
+    };
+    match decoded.kind {
+        AddressKind::Consensus => WalletAddressCheck::Invalid(
+            "This is a validator consensus (valcons) address; enter an account or valoper address"
+                .to_string(),
+        ),
+        kind if prefix != chain_prefix => WalletAddressCheck::OtherPrefix {
+            prefix: prefix.clone(),
+            kind,
+        },
+        AddressKind::Account => WalletAddressCheck::Account(input.to_lowercase()),
+        AddressKind::Validator => WalletAddressCheck::Validator(input.to_lowercase()),
+    }
+}